@@ -0,0 +1,220 @@
+//! Binary data of XML Schema, decoded into bytes.
+
+use crate::text_content::implement_text_content;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+const BASE64_ALPHABET: &[u8; 64] =
+  b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// `xs:base64Binary`, ie. `SGVsbG8=`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Base64Binary(pub Vec<u8>);
+
+/// `xs:hexBinary`, ie. `48656C6C6F`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HexBinary(pub Vec<u8>);
+
+impl FromStr for Base64Binary {
+  type Err = String;
+
+  /// The whitespaces between the characters are ignored.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = |reason: &str| format!("invalid base64 binary {s:?}: {reason}");
+
+    let characters: Vec<u8> = s.bytes().filter(|c| !c.is_ascii_whitespace()).collect();
+    if !characters.chunks_exact(4).remainder().is_empty() {
+      return Err(invalid("length is not a multiple of 4"));
+    }
+
+    let mut bytes = Vec::with_capacity(characters.len() / 4 * 3);
+    let quantum_count = characters.len() / 4;
+    for (index, quantum) in characters.chunks(4).enumerate() {
+      let padding = quantum.iter().rev().take_while(|&&c| c == b'=').count();
+      if padding > 2 || (padding > 0 && index + 1 != quantum_count) {
+        return Err(invalid("misplaced padding"));
+      }
+
+      let mut group = 0u32;
+      for &character in &quantum[..4 - padding] {
+        let value = base64_value(character)
+          .ok_or_else(|| invalid(&format!("unexpected character {:?}", character as char)))?;
+        group = group << 6 | u32::from(value);
+      }
+      group <<= 6 * padding;
+
+      let decoded = [(group >> 16) as u8, (group >> 8) as u8, group as u8];
+      // the bits beyond the decoded bytes must be zero in the canonical form
+      if padding > 0 && decoded[3 - padding..].iter().any(|&byte| byte != 0) {
+        return Err(invalid("non-zero trailing bits"));
+      }
+      bytes.extend_from_slice(&decoded[..3 - padding]);
+    }
+
+    Ok(Base64Binary(bytes))
+  }
+}
+
+impl fmt::Display for Base64Binary {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for chunk in self.0.chunks(3) {
+      let group = chunk
+        .iter()
+        .enumerate()
+        .fold(0u32, |group, (index, &byte)| {
+          group | u32::from(byte) << (16 - 8 * index)
+        });
+
+      for index in 0..4 {
+        if index <= chunk.len() {
+          let value = (group >> (18 - 6 * index)) & 0x3f;
+          write!(f, "{}", BASE64_ALPHABET[value as usize] as char)?;
+        } else {
+          f.write_str("=")?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+impl FromStr for HexBinary {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let digits = s.trim().as_bytes();
+    if !digits.iter().all(u8::is_ascii_hexdigit) {
+      return Err(format!("invalid hex binary {s:?}: unexpected character"));
+    }
+    if !digits.chunks_exact(2).remainder().is_empty() {
+      return Err(format!("invalid hex binary {s:?}: odd number of digits"));
+    }
+
+    let bytes = digits
+      .chunks(2)
+      .map(|pair| hex_value(pair[0]) << 4 | hex_value(pair[1]))
+      .collect();
+
+    Ok(HexBinary(bytes))
+  }
+}
+
+/// The canonical form, with upper case digits.
+impl fmt::Display for HexBinary {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for byte in &self.0 {
+      write!(f, "{byte:02X}")?;
+    }
+    Ok(())
+  }
+}
+
+macro_rules! implement_bytes_conversions {
+  ($($type:ident),*) => {
+    $(
+      impl From<Vec<u8>> for $type {
+        fn from(bytes: Vec<u8>) -> Self {
+          $type(bytes)
+        }
+      }
+
+      impl From<&[u8]> for $type {
+        fn from(bytes: &[u8]) -> Self {
+          $type(bytes.to_vec())
+        }
+      }
+
+      impl From<$type> for Vec<u8> {
+        fn from(binary: $type) -> Self {
+          binary.0
+        }
+      }
+
+      impl Deref for $type {
+        type Target = Vec<u8>;
+
+        fn deref(&self) -> &Self::Target {
+          &self.0
+        }
+      }
+
+      impl DerefMut for $type {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+          &mut self.0
+        }
+      }
+
+      impl AsRef<[u8]> for $type {
+        fn as_ref(&self) -> &[u8] {
+          &self.0
+        }
+      }
+    )*
+  };
+}
+
+implement_bytes_conversions!(Base64Binary, HexBinary);
+
+implement_text_content!(Base64Binary);
+implement_text_content!(HexBinary);
+
+fn base64_value(character: u8) -> Option<u8> {
+  BASE64_ALPHABET
+    .iter()
+    .position(|&c| c == character)
+    .map(|position| position as u8)
+}
+
+fn hex_value(digit: u8) -> u8 {
+  match digit {
+    b'0'..=b'9' => digit - b'0',
+    b'a'..=b'f' => digit - b'a' + 10,
+    _ => digit - b'A' + 10,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn base64_binary() {
+    for (encoded, decoded) in [
+      ("", ""),
+      ("Zg==", "f"),
+      ("Zm8=", "fo"),
+      ("Zm9v", "foo"),
+      ("Zm9vYg==", "foob"),
+      ("Zm9vYmE=", "fooba"),
+      ("Zm9vYmFy", "foobar"),
+    ] {
+      let value: Base64Binary = encoded.parse().unwrap();
+      assert_eq!(value.0, decoded.as_bytes());
+      assert_eq!(value.to_string(), encoded);
+    }
+
+    let value: Base64Binary = " Zm9v\n  YmFy\tZg = = ".parse().unwrap();
+    assert_eq!(value.0, b"foobarf");
+    assert_eq!(
+      Base64Binary::from(vec![0xfb, 0xff, 0xbf]).to_string(),
+      "+/+/"
+    );
+
+    for invalid in ["Zm9", "Zg=", "Z===", "Zg==Zm9v", "Zh==", "Zm9-", "=Zm9"] {
+      assert!(invalid.parse::<Base64Binary>().is_err(), "{}", invalid);
+    }
+  }
+
+  #[test]
+  fn hex_binary() {
+    let value: HexBinary = "0fB7".parse().unwrap();
+    assert_eq!(value.0, vec![0x0f, 0xb7]);
+    assert_eq!(value.to_string(), "0FB7");
+    assert_eq!("".parse::<HexBinary>(), Ok(HexBinary::default()));
+
+    for invalid in ["F", "0G", "+1", "0 1"] {
+      assert!(invalid.parse::<HexBinary>().is_err(), "{}", invalid);
+    }
+  }
+}
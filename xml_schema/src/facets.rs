@@ -0,0 +1,174 @@
+//! Helpers used by the generated code to validate the facets of restricted simple types.
+
+use regex::Regex;
+use std::borrow::Cow;
+
+/// The `whiteSpace` facet, applied to the lexical value before parsing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WhiteSpace {
+  /// The value is kept as it is.
+  Preserve,
+  /// Tabs, line feeds and carriage returns are replaced by spaces.
+  Replace,
+  /// After `Replace`, consecutive spaces are collapsed and leading and trailing spaces removed.
+  Collapse,
+}
+
+impl WhiteSpace {
+  pub fn normalize<'a>(&self, value: &'a str) -> Cow<'a, str> {
+    match self {
+      WhiteSpace::Preserve => Cow::Borrowed(value),
+      WhiteSpace::Replace if !value.contains(['\t', '\n', '\r']) => Cow::Borrowed(value),
+      WhiteSpace::Replace => Cow::Owned(value.replace(['\t', '\n', '\r'], " ")),
+      WhiteSpace::Collapse => {
+        let collapsed = value
+          .split(is_xml_whitespace)
+          .filter(|part| !part.is_empty())
+          .collect::<Vec<&str>>()
+          .join(" ");
+        if collapsed == value {
+          Cow::Borrowed(value)
+        } else {
+          Cow::Owned(collapsed)
+        }
+      }
+    }
+  }
+}
+
+/// The whitespace characters of XML: space, tab, line feed and carriage return.
+pub fn is_xml_whitespace(c: char) -> bool {
+  matches!(c, ' ' | '\t' | '\n' | '\r')
+}
+
+/// The `pattern` facet, a XSD regular expression which matches the whole value.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+  expression: String,
+  regex: Regex,
+}
+
+impl Pattern {
+  pub fn new(expression: &str) -> Result<Self, String> {
+    let regex = Regex::new(&format!("^(?:{})$", translate_expression(expression)))
+      .map_err(|e| format!("Invalid pattern {expression:?}: {e}"))?;
+
+    Ok(Pattern {
+      expression: expression.to_string(),
+      regex,
+    })
+  }
+
+  pub fn expression(&self) -> &str {
+    &self.expression
+  }
+
+  pub fn is_match(&self, value: &str) -> bool {
+    self.regex.is_match(value)
+  }
+}
+
+/// Translate the XSD specific syntax to the `regex` one:
+/// `^` and `$` are not anchors, `\i` and `\c` are the XML name characters,
+/// and a character class subtraction is written `[a-z-[aeiou]]`.
+fn translate_expression(expression: &str) -> String {
+  let mut translated = String::with_capacity(expression.len());
+  let mut chars = expression.chars().peekable();
+  let mut class_depth = 0;
+
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' => match chars.next() {
+        Some('i') => translated.push_str(r"[_:A-Za-z\u{C0}-\u{D6}\u{D8}-\u{F6}\u{F8}-\u{2FF}\u{370}-\u{37D}\u{37F}-\u{1FFF}\u{200C}-\u{200D}\u{2070}-\u{218F}\u{2C00}-\u{2FEF}\u{3001}-\u{D7FF}\u{F900}-\u{FDCF}\u{FDF0}-\u{FFFD}]"),
+        Some('I') => translated.push_str(r"[^_:A-Za-z\u{C0}-\u{D6}\u{D8}-\u{F6}\u{F8}-\u{2FF}\u{370}-\u{37D}\u{37F}-\u{1FFF}\u{200C}-\u{200D}\u{2070}-\u{218F}\u{2C00}-\u{2FEF}\u{3001}-\u{D7FF}\u{F900}-\u{FDCF}\u{FDF0}-\u{FFFD}]"),
+        Some('c') => translated.push_str(r"[-._:A-Za-z0-9\u{B7}\u{C0}-\u{D6}\u{D8}-\u{F6}\u{F8}-\u{37D}\u{37F}-\u{1FFF}\u{200C}-\u{200D}\u{203F}-\u{2040}\u{2070}-\u{218F}\u{2C00}-\u{2FEF}\u{3001}-\u{D7FF}\u{F900}-\u{FDCF}\u{FDF0}-\u{FFFD}]"),
+        Some('C') => translated.push_str(r"[^-._:A-Za-z0-9\u{B7}\u{C0}-\u{D6}\u{D8}-\u{F6}\u{F8}-\u{37D}\u{37F}-\u{1FFF}\u{200C}-\u{200D}\u{203F}-\u{2040}\u{2070}-\u{218F}\u{2C00}-\u{2FEF}\u{3001}-\u{D7FF}\u{F900}-\u{FDCF}\u{FDF0}-\u{FFFD}]"),
+        Some(escaped) => {
+          translated.push('\\');
+          translated.push(escaped);
+        }
+        None => translated.push_str(r"\\"),
+      },
+      '[' => {
+        class_depth += 1;
+        translated.push('[');
+        if chars.peek() == Some(&'^') {
+          translated.push(chars.next().unwrap_or_default());
+        }
+      }
+      ']' if class_depth > 0 => {
+        class_depth -= 1;
+        translated.push(']');
+      }
+      '-' if class_depth > 0 && chars.peek() == Some(&'[') => translated.push_str("--"),
+      '^' | '$' if class_depth == 0 => {
+        translated.push('\\');
+        translated.push(c);
+      }
+      _ => translated.push(c),
+    }
+  }
+
+  translated
+}
+
+/// Number of significant digits of a decimal value, for the `totalDigits` facet.
+pub fn total_digits(value: &str) -> usize {
+  let (integer, fraction) = split_decimal(value);
+  integer.len() + fraction.len()
+}
+
+/// Number of significant fraction digits of a decimal value, for the `fractionDigits` facet.
+pub fn fraction_digits(value: &str) -> usize {
+  split_decimal(value).1.len()
+}
+
+fn split_decimal(value: &str) -> (&str, &str) {
+  let value = value.trim().trim_start_matches(['+', '-']);
+  let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+  (
+    integer.trim_start_matches('0'),
+    fraction.trim_end_matches('0'),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn white_space() {
+    let value = " a\tb \n c ";
+    assert_eq!(WhiteSpace::Preserve.normalize(value), value);
+    assert_eq!(WhiteSpace::Replace.normalize(value), " a b   c ");
+    assert_eq!(WhiteSpace::Collapse.normalize(value), "a b c");
+    // a non-breaking space is not a XML whitespace
+    assert_eq!(WhiteSpace::Collapse.normalize(" a\u{a0}b "), "a\u{a0}b");
+  }
+
+  #[test]
+  fn pattern() {
+    let pattern = Pattern::new(r"[A-Z]{2}\d+").unwrap();
+    assert!(pattern.is_match("FR12"));
+    assert!(!pattern.is_match("FR12 "));
+    assert!(!pattern.is_match("xFR12"));
+
+    let pattern = Pattern::new(r"\$[a-z-[aeiou]]+").unwrap();
+    assert!(pattern.is_match("$bcd"));
+    assert!(!pattern.is_match("$bad"));
+
+    let pattern = Pattern::new(r"\i\c*").unwrap();
+    assert!(pattern.is_match("xs:name-1"));
+    assert!(!pattern.is_match("1name"));
+
+    assert!(Pattern::new("[a-").is_err());
+  }
+
+  #[test]
+  fn digits() {
+    assert_eq!(total_digits("-0012.3400"), 4);
+    assert_eq!(fraction_digits("-0012.3400"), 2);
+    assert_eq!(total_digits("100"), 3);
+    assert_eq!(fraction_digits("100"), 0);
+  }
+}
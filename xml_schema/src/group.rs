@@ -0,0 +1,305 @@
+//! Model groups of XML Schema (`xs:group`), read from the children of the parent element.
+
+use hifa_yaserde::de::Deserializer;
+use hifa_yaserde::YaDeserialize;
+use std::io::Read;
+use xml::reader::XmlEvent;
+use xml::writer::{EmitterConfig, EventWriter};
+
+/// Read an instance of a group, from the current child element and the following ones which
+/// are elements of the group. The instance ends on another element, or on an element already
+/// read which can not be repeated.
+///
+/// `elements` are the local names of the elements of the group, with whether they can be
+/// repeated. The elements are read as the children of a `root` element, the name of the
+/// structure of the group.
+pub fn read_group<R: Read, T: YaDeserialize>(
+  reader: &mut Deserializer<R>,
+  root: &str,
+  elements: &[(&str, bool)],
+) -> Result<T, String> {
+  let mut buffer = vec![];
+  let mut writer = EmitterConfig::new()
+    .write_document_declaration(false)
+    .create_writer(&mut buffer);
+  write(
+    &mut writer,
+    xml::writer::XmlEvent::start_element(root).into(),
+  )?;
+
+  let mut read: Vec<&str> = vec![];
+
+  loop {
+    let name = match reader.peek()? {
+      XmlEvent::StartElement { name, .. } => name.local_name.clone(),
+      XmlEvent::EndElement { .. } | XmlEvent::EndDocument => break,
+      _ => {
+        reader.next_event()?;
+        continue;
+      }
+    };
+
+    let Some((element, repeated)) = elements.iter().find(|(element, _)| *element == name) else {
+      break;
+    };
+    if !repeated && read.contains(element) {
+      break;
+    }
+    read.push(element);
+
+    copy_element(reader, &mut writer)?;
+  }
+
+  write(&mut writer, xml::writer::XmlEvent::end_element().into())?;
+
+  let content = String::from_utf8(buffer).map_err(|e| e.to_string())?;
+  hifa_yaserde::de::from_str(&content)
+}
+
+/// Read the instances of a group in the children of the current element, the other children
+/// are skipped. With `multiple` false, the reading stops after the first instance.
+pub fn read_groups<R: Read, T: YaDeserialize>(
+  reader: &mut Deserializer<R>,
+  root: &str,
+  elements: &[(&str, bool)],
+  multiple: bool,
+) -> Result<Vec<T>, String> {
+  if matches!(reader.peek()?, XmlEvent::StartElement { .. }) {
+    reader.next_event()?;
+  }
+
+  let mut groups = vec![];
+
+  loop {
+    match reader.peek()? {
+      XmlEvent::StartElement { name, .. } => {
+        let is_group_element = is_group_element(&name.local_name, elements);
+        if is_group_element {
+          groups.push(read_group(reader, root, elements)?);
+          if !multiple {
+            break;
+          }
+        } else {
+          reader.next_event()?;
+          reader.skip_element(|_event| {})?;
+        }
+      }
+      XmlEvent::EndElement { .. } | XmlEvent::EndDocument => break,
+      _ => {
+        reader.next_event()?;
+      }
+    }
+  }
+
+  Ok(groups)
+}
+
+/// The element is one of the elements of a group.
+pub fn is_group_element(name: &str, elements: &[(&str, bool)]) -> bool {
+  elements.iter().any(|(element, _)| *element == name)
+}
+
+/// The children of an element which are not read by the other fields of its structure, in the
+/// order of the document. The compositors of the structure are read one after the other from
+/// them, each one removing the elements it has read, so that two compositors accepting the same
+/// element do not read the same occurrence.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Children(Vec<(String, String)>);
+
+impl Children {
+  /// Read a compositor from the first remaining element of the compositor, and the following
+  /// ones which are elements of the compositor. The reading ends on another element, or on an
+  /// element already read which can not be repeated.
+  ///
+  /// `elements` are the local names of the elements of the compositor, with whether they can be
+  /// repeated, they are read by `T` as the children of a `root` element.
+  pub fn read<T: YaDeserialize>(
+    &mut self,
+    root: &str,
+    elements: &[(&str, bool)],
+  ) -> Result<T, String> {
+    let start = self
+      .0
+      .iter()
+      .position(|(name, _)| is_group_element(name, elements))
+      .unwrap_or(self.0.len());
+
+    let mut read: Vec<&str> = vec![];
+    let mut end = start;
+    while let Some((name, _)) = self.0.get(end) {
+      let Some((element, repeated)) = elements.iter().find(|(element, _)| element == name) else {
+        break;
+      };
+      if !repeated && read.contains(element) {
+        break;
+      }
+      read.push(element);
+      end += 1;
+    }
+
+    let mut content = format!("<{root}>");
+    for (_name, element) in &self.0[start..end] {
+      content.push_str(element);
+    }
+    content.push_str(&format!("</{root}>"));
+
+    let mut reader = Deserializer::new_from_reader(content.as_bytes());
+    let value = T::deserialize(&mut reader)?;
+
+    let mut unread = 0;
+    loop {
+      match reader.next_event()? {
+        XmlEvent::StartElement { .. } if reader.depth() == 2 => unread += 1,
+        XmlEvent::EndDocument => break,
+        _ => {}
+      }
+    }
+
+    self.0.drain(start..end - unread);
+    Ok(value)
+  }
+}
+
+impl YaDeserialize for Children {
+  fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
+    if matches!(reader.peek()?, XmlEvent::StartElement { .. }) {
+      reader.next_event()?;
+    }
+
+    let mut children = vec![];
+
+    loop {
+      let name = match reader.peek()? {
+        XmlEvent::StartElement { name, .. } => name.local_name.clone(),
+        XmlEvent::EndElement { .. } | XmlEvent::EndDocument => break,
+        _ => {
+          reader.next_event()?;
+          continue;
+        }
+      };
+
+      let mut buffer = vec![];
+      let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(&mut buffer);
+      copy_element(reader, &mut writer)?;
+
+      let element = String::from_utf8(buffer).map_err(|e| e.to_string())?;
+      children.push((name, element));
+    }
+
+    Ok(Children(children))
+  }
+}
+
+/// Write the current element, up to its end.
+fn copy_element<R: Read, W: std::io::Write>(
+  reader: &mut Deserializer<R>,
+  writer: &mut EventWriter<W>,
+) -> Result<(), String> {
+  let depth = reader.depth();
+  loop {
+    let event = reader.next_event()?;
+    if let Some(event) = event.as_writer_event() {
+      write(writer, event)?;
+    }
+    if reader.depth() == depth {
+      return Ok(());
+    }
+  }
+}
+
+fn write<W: std::io::Write>(
+  writer: &mut EventWriter<W>,
+  event: xml::writer::XmlEvent,
+) -> Result<(), String> {
+  writer.write(event).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hifa_yaserde::de::Deserializer;
+
+  #[derive(Debug, Default, PartialEq)]
+  struct Names(Vec<String>);
+
+  // The local names of the children, to check which elements were read for an instance.
+  impl YaDeserialize for Names {
+    fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
+      let mut names = vec![];
+      loop {
+        match reader.next_event()? {
+          XmlEvent::StartElement { name, .. } if reader.depth() == 2 => names.push(name.local_name),
+          XmlEvent::EndDocument => break,
+          _ => {}
+        }
+      }
+      Ok(Names(names))
+    }
+  }
+
+  #[derive(Debug, Default, PartialEq)]
+  struct First(String);
+
+  // The local name of the first child only, the other children are left unread.
+  impl YaDeserialize for First {
+    fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
+      reader.next_event()?;
+      let XmlEvent::StartElement { name, .. } = reader.next_event()? else {
+        return Err("missing child".to_string());
+      };
+      reader.skip_element(|_event| {})?;
+      Ok(First(name.local_name))
+    }
+  }
+
+  fn names(values: &[&str]) -> Names {
+    Names(values.iter().map(|value| value.to_string()).collect())
+  }
+
+  #[test]
+  fn group_instances() {
+    let elements = [("a", false), ("b", true)];
+    let read = |xml: &str, multiple: bool| {
+      let mut reader = Deserializer::new_from_reader(xml.as_bytes());
+      read_groups::<_, Names>(&mut reader, "Group", &elements, multiple)
+    };
+
+    assert_eq!(
+      read(
+        "<parent><a/><b>1</b><b><c/></b><other/><a/><b/><a/></parent>",
+        true
+      )
+      .unwrap(),
+      vec![names(&["a", "b", "b"]), names(&["a", "b"]), names(&["a"])]
+    );
+    assert_eq!(
+      read("<parent><other/><b/><a/><a/></parent>", false).unwrap(),
+      vec![names(&["b", "a"])]
+    );
+    assert_eq!(read("<parent/>", true).unwrap(), vec![]);
+    assert!(is_group_element("b", &elements));
+    assert!(!is_group_element("c", &elements));
+  }
+
+  #[test]
+  fn children_read_once() {
+    let xml = "<parent><a>1</a><b/><a>2</a><c/></parent>";
+    let mut children: Children = hifa_yaserde::de::from_str(xml).unwrap();
+
+    let first: First = children
+      .read("Group", &[("a", false), ("b", false)])
+      .unwrap();
+    assert_eq!(first, First("a".to_string()));
+
+    let second: Names = children.read("Group", &[("a", false)]).unwrap();
+    assert_eq!(second, names(&["a"]));
+
+    let rest: Names = children
+      .read("Group", &[("b", false), ("c", false)])
+      .unwrap();
+    assert_eq!(rest, names(&["b", "c"]));
+    assert_eq!(children, Children::default());
+  }
+}
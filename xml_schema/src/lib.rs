@@ -2,3 +2,15 @@
 #[allow(unused_imports)]
 #[macro_use]
 extern crate hifa_xml_schema_derive;
+
+mod text_content;
+
+pub mod binary;
+pub mod facets;
+pub mod group;
+pub mod list;
+pub mod nillable;
+pub mod numeric;
+pub mod qname;
+pub mod string;
+pub mod temporal;
@@ -0,0 +1,100 @@
+//! Lists of XML Schema, ie. `xs:NMTOKENS`: items separated by whitespaces.
+
+use crate::facets::is_xml_whitespace;
+use crate::text_content::implement_text_content;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// Whitespace-separated list of values, used for the list built-in types.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct List<T> {
+  pub items: Vec<T>,
+}
+
+impl<T> From<Vec<T>> for List<T> {
+  fn from(items: Vec<T>) -> Self {
+    List { items }
+  }
+}
+
+impl<T> From<List<T>> for Vec<T> {
+  fn from(list: List<T>) -> Self {
+    list.items
+  }
+}
+
+impl<T> Deref for List<T> {
+  type Target = Vec<T>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.items
+  }
+}
+
+impl<T> DerefMut for List<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.items
+  }
+}
+
+impl<T> FromStr for List<T>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    items(s)
+      .map(|item| {
+        item
+          .parse()
+          .map_err(|e| format!("invalid list item {item:?}: {e}"))
+      })
+      .collect::<Result<Vec<T>, String>>()
+      .map(List::from)
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (index, item) in self.items.iter().enumerate() {
+      if index > 0 {
+        f.write_str(" ")?;
+      }
+      write!(f, "{item}")?;
+    }
+    Ok(())
+  }
+}
+
+/// The items of a list, separated by runs of XML whitespaces.
+pub fn items(value: &str) -> impl Iterator<Item = &str> {
+  value
+    .split(is_xml_whitespace)
+    .filter(|item| !item.is_empty())
+}
+
+implement_text_content!(List<T>);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn list() {
+    let list: List<String> = " a\tb\n\nc ".parse().unwrap();
+    assert_eq!(list.items, vec!["a", "b", "c"]);
+    assert_eq!(list.to_string(), "a b c");
+    assert_eq!(list.len(), 3);
+
+    let list: List<u8> = "1 2".parse().unwrap();
+    assert_eq!(Vec::from(list), vec![1, 2]);
+    assert_eq!(
+      "1 x 3".parse::<List<u8>>(),
+      Err(r#"invalid list item "x": invalid digit found in string"#.to_string())
+    );
+    assert!("".parse::<List<u8>>().unwrap().is_empty());
+  }
+}
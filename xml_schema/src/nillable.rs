@@ -0,0 +1,320 @@
+//! Nillable elements of XML Schema, ie. `<price xsi:nil="true"/>`.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Namespace of the `xsi:nil` attribute.
+pub const XML_SCHEMA_INSTANCE_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Value of an element declared `nillable`, read from and written to its text content.
+/// An element with `xsi:nil="true"` is `Nil`, an empty element is a value parsed from `""`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Nillable<T> {
+  #[default]
+  Nil,
+  Value(T),
+}
+
+impl<T> Nillable<T> {
+  pub fn is_nil(&self) -> bool {
+    matches!(self, Nillable::Nil)
+  }
+
+  pub fn value(&self) -> Option<&T> {
+    match self {
+      Nillable::Nil => None,
+      Nillable::Value(value) => Some(value),
+    }
+  }
+
+  pub fn into_value(self) -> Option<T> {
+    match self {
+      Nillable::Nil => None,
+      Nillable::Value(value) => Some(value),
+    }
+  }
+}
+
+impl<T> From<T> for Nillable<T> {
+  fn from(value: T) -> Self {
+    Nillable::Value(value)
+  }
+}
+
+impl<T> From<Option<T>> for Nillable<T> {
+  fn from(value: Option<T>) -> Self {
+    value.map_or(Nillable::Nil, Nillable::Value)
+  }
+}
+
+impl<T> From<Nillable<T>> for Option<T> {
+  fn from(value: Nillable<T>) -> Self {
+    value.into_value()
+  }
+}
+
+impl<T> hifa_yaserde::YaDeserialize for Nillable<T>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  fn deserialize<R: std::io::Read>(
+    reader: &mut hifa_yaserde::de::Deserializer<R>,
+  ) -> Result<Self, String> {
+    let mut name = "Nillable".to_string();
+    let mut is_nil = false;
+    if let xml::reader::XmlEvent::StartElement {
+      name: element_name,
+      attributes,
+      ..
+    } = reader.peek()?
+    {
+      name = element_name.local_name.clone();
+      is_nil = has_nil_attribute(attributes);
+      reader.next_event()?;
+    }
+
+    let content = match reader.peek()?.clone() {
+      xml::reader::XmlEvent::Characters(content) => {
+        reader.next_event()?;
+        content
+      }
+      _ => String::new(),
+    };
+
+    if is_nil {
+      if !content.is_empty() {
+        return Err(format!("{name}: a nil element can not have a content"));
+      }
+      return Ok(Nillable::Nil);
+    }
+
+    content
+      .parse()
+      .map(Nillable::Value)
+      .map_err(|e| format!("{name}: invalid value {content:?}: {e}"))
+  }
+}
+
+impl<T: fmt::Display> hifa_yaserde::YaSerialize for Nillable<T> {
+  fn serialize<W: std::io::Write>(
+    &self,
+    writer: &mut hifa_yaserde::ser::Serializer<W>,
+  ) -> Result<(), String> {
+    let skip = writer.skip_start_end();
+
+    if !skip {
+      let name = writer
+        .get_start_event_name()
+        .unwrap_or_else(|| "Nillable".to_string());
+      let mut start_event = xml::writer::XmlEvent::start_element(name.as_str());
+      if self.is_nil() {
+        start_event = start_event
+          .attr("xsi:nil", "true")
+          .ns("xsi", XML_SCHEMA_INSTANCE_NAMESPACE);
+      }
+      writer.write(start_event).map_err(|e| e.to_string())?;
+    }
+
+    if let Nillable::Value(value) = self {
+      let content = value.to_string();
+      writer
+        .write(xml::writer::XmlEvent::characters(&content))
+        .map_err(|e| e.to_string())?;
+    }
+
+    if !skip {
+      writer
+        .write(xml::writer::XmlEvent::end_element())
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+  }
+
+  fn serialize_attributes(
+    &self,
+    source_attributes: Vec<xml::attribute::OwnedAttribute>,
+    source_namespace: xml::namespace::Namespace,
+  ) -> Result<
+    (
+      Vec<xml::attribute::OwnedAttribute>,
+      xml::namespace::Namespace,
+    ),
+    String,
+  > {
+    Ok((source_attributes, source_namespace))
+  }
+}
+
+/// Value of an element of complex type declared `nillable`, read from and written to its children
+/// and attributes. An element with `xsi:nil="true"` is `Nil`, others are read as a `T`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NillableComplex<T> {
+  #[default]
+  Nil,
+  Value(T),
+}
+
+impl<T> NillableComplex<T> {
+  pub fn is_nil(&self) -> bool {
+    matches!(self, NillableComplex::Nil)
+  }
+
+  pub fn value(&self) -> Option<&T> {
+    match self {
+      NillableComplex::Nil => None,
+      NillableComplex::Value(value) => Some(value),
+    }
+  }
+
+  pub fn into_value(self) -> Option<T> {
+    match self {
+      NillableComplex::Nil => None,
+      NillableComplex::Value(value) => Some(value),
+    }
+  }
+}
+
+impl<T> From<T> for NillableComplex<T> {
+  fn from(value: T) -> Self {
+    NillableComplex::Value(value)
+  }
+}
+
+impl<T> From<Option<T>> for NillableComplex<T> {
+  fn from(value: Option<T>) -> Self {
+    value.map_or(NillableComplex::Nil, NillableComplex::Value)
+  }
+}
+
+impl<T> From<NillableComplex<T>> for Option<T> {
+  fn from(value: NillableComplex<T>) -> Self {
+    value.into_value()
+  }
+}
+
+impl<T: hifa_yaserde::YaDeserialize> hifa_yaserde::YaDeserialize for NillableComplex<T> {
+  fn deserialize<R: std::io::Read>(
+    reader: &mut hifa_yaserde::de::Deserializer<R>,
+  ) -> Result<Self, String> {
+    let is_nil = match reader.peek()? {
+      xml::reader::XmlEvent::StartElement { attributes, .. } => has_nil_attribute(attributes),
+      _ => false,
+    };
+    if !is_nil {
+      return T::deserialize(reader).map(NillableComplex::Value);
+    }
+
+    let xml::reader::XmlEvent::StartElement { name, .. } = reader.next_event()? else {
+      unreachable!("the start element has been peeked")
+    };
+    while let xml::reader::XmlEvent::Whitespace(_) = reader.peek()? {
+      reader.next_event()?;
+    }
+    // as for a structure, the end element is left to the parent
+    match reader.peek()? {
+      xml::reader::XmlEvent::EndElement { .. } => Ok(NillableComplex::Nil),
+      _ => Err(format!(
+        "{}: a nil element can not have a content",
+        name.local_name
+      )),
+    }
+  }
+}
+
+impl<T: hifa_yaserde::YaSerialize> hifa_yaserde::YaSerialize for NillableComplex<T> {
+  fn serialize<W: std::io::Write>(
+    &self,
+    writer: &mut hifa_yaserde::ser::Serializer<W>,
+  ) -> Result<(), String> {
+    if let NillableComplex::Value(value) = self {
+      return value.serialize(writer);
+    }
+    if writer.skip_start_end() {
+      return Ok(());
+    }
+
+    let name = writer
+      .get_start_event_name()
+      .unwrap_or_else(|| "NillableComplex".to_string());
+    writer
+      .write(
+        xml::writer::XmlEvent::start_element(name.as_str())
+          .attr("xsi:nil", "true")
+          .ns("xsi", XML_SCHEMA_INSTANCE_NAMESPACE),
+      )
+      .map_err(|e| e.to_string())?;
+    writer
+      .write(xml::writer::XmlEvent::end_element())
+      .map_err(|e| e.to_string())
+  }
+
+  fn serialize_attributes(
+    &self,
+    source_attributes: Vec<xml::attribute::OwnedAttribute>,
+    source_namespace: xml::namespace::Namespace,
+  ) -> Result<
+    (
+      Vec<xml::attribute::OwnedAttribute>,
+      xml::namespace::Namespace,
+    ),
+    String,
+  > {
+    match self {
+      NillableComplex::Nil => Ok((source_attributes, source_namespace)),
+      NillableComplex::Value(value) => {
+        value.serialize_attributes(source_attributes, source_namespace)
+      }
+    }
+  }
+}
+
+/// Whether the attributes of an element contain `xsi:nil="true"`.
+fn has_nil_attribute(attributes: &[xml::attribute::OwnedAttribute]) -> bool {
+  attributes.iter().any(|attribute| {
+    attribute.name.local_name == "nil"
+      && attribute.name.namespace.as_deref() == Some(XML_SCHEMA_INSTANCE_NAMESPACE)
+      && matches!(attribute.value.trim(), "true" | "1")
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hifa_yaserde::de::from_str;
+  use hifa_yaserde::ser::to_string;
+
+  #[test]
+  fn nillable() {
+    let xsi = r#"xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance""#;
+
+    let value: Nillable<u32> = from_str(&format!(r#"<price {xsi} xsi:nil="true"/>"#)).unwrap();
+    assert_eq!(value, Nillable::Nil);
+    let value: Nillable<u32> = from_str(&format!(r#"<price {xsi} xsi:nil="1"></price>"#)).unwrap();
+    assert!(value.is_nil());
+    let value: Nillable<u32> =
+      from_str(&format!(r#"<price {xsi} xsi:nil="false">12</price>"#)).unwrap();
+    assert_eq!(value.value(), Some(&12));
+    let value: Nillable<String> = from_str(r#"<name/>"#).unwrap();
+    assert_eq!(value, Nillable::Value(String::new()));
+
+    assert_eq!(
+      from_str::<Nillable<u32>>(&format!(r#"<price {xsi} xsi:nil="true">12</price>"#)),
+      Err("price: a nil element can not have a content".to_string())
+    );
+    assert_eq!(
+      from_str::<Nillable<u32>>(r#"<price>x</price>"#),
+      Err(r#"price: invalid value "x": invalid digit found in string"#.to_string())
+    );
+
+    assert_eq!(
+      to_string(&Nillable::<u32>::Nil).unwrap(),
+      format!(r#"<?xml version="1.0" encoding="UTF-8"?><Nillable {xsi} xsi:nil="true" />"#)
+    );
+    assert_eq!(
+      to_string(&Nillable::from(12)).unwrap(),
+      r#"<?xml version="1.0" encoding="UTF-8"?><Nillable>12</Nillable>"#
+    );
+  }
+}
@@ -0,0 +1,547 @@
+//! Arbitrary-precision numbers of XML Schema, parsed from their lexical form and written in their canonical form.
+
+use crate::text_content::implement_text_content;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// `xs:integer` and its unbounded derived types, ie. `-12345678901234567890`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Integer {
+  negative: bool,
+  /// Digits without leading zero, `0` for zero.
+  digits: String,
+}
+
+impl Integer {
+  pub fn is_negative(&self) -> bool {
+    self.negative
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.digits == "0"
+  }
+
+  fn from_digits(negative: bool, digits: &str) -> Self {
+    let digits = match digits.trim_start_matches('0') {
+      "" => "0",
+      digits => digits,
+    };
+
+    Integer {
+      negative: negative && digits != "0",
+      digits: digits.to_string(),
+    }
+  }
+}
+
+impl Default for Integer {
+  fn default() -> Self {
+    Integer {
+      negative: false,
+      digits: "0".to_string(),
+    }
+  }
+}
+
+impl FromStr for Integer {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (negative, digits) = split_sign(s.trim());
+    if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+      return Err(format!("invalid integer {s:?}"));
+    }
+
+    Ok(Integer::from_digits(negative, digits))
+  }
+}
+
+impl fmt::Display for Integer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.negative {
+      f.write_str("-")?;
+    }
+    f.write_str(&self.digits)
+  }
+}
+
+impl Ord for Integer {
+  fn cmp(&self, other: &Self) -> Ordering {
+    let magnitude = compare_digits(&self.digits, &other.digits);
+    match (self.negative, other.negative) {
+      (false, false) => magnitude,
+      (true, true) => magnitude.reverse(),
+      (false, true) => Ordering::Greater,
+      (true, false) => Ordering::Less,
+    }
+  }
+}
+
+impl PartialOrd for Integer {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+/// `xs:decimal`, ie. `-1234.5678`, without loss of precision.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Decimal {
+  negative: bool,
+  /// Digits of the integer part without leading zero, `0` for zero.
+  integer: String,
+  /// Digits of the fraction part without trailing zero.
+  fraction: String,
+}
+
+impl Decimal {
+  pub fn is_negative(&self) -> bool {
+    self.negative
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.integer == "0" && self.fraction.is_empty()
+  }
+
+  pub fn is_integer(&self) -> bool {
+    self.fraction.is_empty()
+  }
+
+  /// The nearest floating point value.
+  pub fn to_f64(&self) -> f64 {
+    self.to_string().parse().unwrap_or_default()
+  }
+}
+
+impl Default for Decimal {
+  fn default() -> Self {
+    Decimal {
+      negative: false,
+      integer: "0".to_string(),
+      fraction: String::new(),
+    }
+  }
+}
+
+impl FromStr for Decimal {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (negative, digits) = split_sign(s.trim());
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if (integer.is_empty() && fraction.is_empty())
+      || !integer.bytes().all(|c| c.is_ascii_digit())
+      || !fraction.bytes().all(|c| c.is_ascii_digit())
+    {
+      return Err(format!("invalid decimal {s:?}"));
+    }
+
+    let integer = Integer::from_digits(false, integer).digits;
+    let fraction = fraction.trim_end_matches('0').to_string();
+    let is_zero = integer == "0" && fraction.is_empty();
+
+    Ok(Decimal {
+      negative: negative && !is_zero,
+      integer,
+      fraction,
+    })
+  }
+}
+
+/// The canonical form of XSD 1.1, an integer value is written without decimal point.
+impl fmt::Display for Decimal {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.negative {
+      f.write_str("-")?;
+    }
+    f.write_str(&self.integer)?;
+    if !self.fraction.is_empty() {
+      write!(f, ".{}", self.fraction)?;
+    }
+    Ok(())
+  }
+}
+
+impl Ord for Decimal {
+  fn cmp(&self, other: &Self) -> Ordering {
+    // without trailing zeros, the fractions are ordered as strings
+    let magnitude = compare_digits(&self.integer, &other.integer)
+      .then_with(|| self.fraction.cmp(&other.fraction));
+
+    match (self.negative, other.negative) {
+      (false, false) => magnitude,
+      (true, true) => magnitude.reverse(),
+      (false, true) => Ordering::Greater,
+      (true, false) => Ordering::Less,
+    }
+  }
+}
+
+impl PartialOrd for Decimal {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl From<Integer> for Decimal {
+  fn from(integer: Integer) -> Self {
+    Decimal {
+      negative: integer.negative,
+      integer: integer.digits,
+      fraction: String::new(),
+    }
+  }
+}
+
+impl TryFrom<Decimal> for Integer {
+  type Error = String;
+
+  fn try_from(decimal: Decimal) -> Result<Self, Self::Error> {
+    if !decimal.is_integer() {
+      return Err(format!("{decimal} is not an integer"));
+    }
+
+    Ok(Integer {
+      negative: decimal.negative,
+      digits: decimal.integer,
+    })
+  }
+}
+
+impl TryFrom<f64> for Decimal {
+  type Error = String;
+
+  /// The shortest decimal value which converts back to the same floating point value.
+  fn try_from(value: f64) -> Result<Self, Self::Error> {
+    if !value.is_finite() {
+      return Err(format!("{value} is not a decimal value"));
+    }
+
+    value.to_string().parse()
+  }
+}
+
+macro_rules! implement_primitive_conversions {
+  ($($primitive:ty),*) => {
+    $(
+      impl From<$primitive> for Integer {
+        fn from(value: $primitive) -> Self {
+          let value = value.to_string();
+          let (negative, digits) = split_sign(&value);
+          Integer::from_digits(negative, digits)
+        }
+      }
+
+      impl From<$primitive> for Decimal {
+        fn from(value: $primitive) -> Self {
+          Integer::from(value).into()
+        }
+      }
+
+      impl TryFrom<Integer> for $primitive {
+        type Error = String;
+
+        fn try_from(integer: Integer) -> Result<Self, Self::Error> {
+          integer
+            .to_string()
+            .parse()
+            .map_err(|_| format!("{} is out of the range of {}", integer, stringify!($primitive)))
+        }
+      }
+    )*
+  };
+}
+
+implement_primitive_conversions!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+/// Integer types derived from `xs:integer` by the sign of their values.
+macro_rules! implement_signed_integer {
+  ($($(#[$doc:meta])* $type:ident, $description:literal, $check:expr, $default:literal;)*) => {
+    $(
+      $(#[$doc])*
+      #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+      pub struct $type(Integer);
+
+      impl $type {
+        /// Create the value, checking its sign.
+        pub fn new(value: Integer) -> Result<Self, String> {
+          let check: fn(&Integer) -> bool = $check;
+          if !check(&value) {
+            return Err(format!("{} is not {}", value, $description));
+          }
+          Ok($type(value))
+        }
+
+        pub fn value(&self) -> &Integer {
+          &self.0
+        }
+
+        pub fn into_value(self) -> Integer {
+          self.0
+        }
+      }
+
+      impl Default for $type {
+        fn default() -> Self {
+          $type(Integer::from($default))
+        }
+      }
+
+      impl FromStr for $type {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          $type::new(s.parse()?)
+        }
+      }
+
+      impl fmt::Display for $type {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          self.0.fmt(f)
+        }
+      }
+
+      impl From<$type> for Integer {
+        fn from(value: $type) -> Self {
+          value.0
+        }
+      }
+
+      impl TryFrom<Integer> for $type {
+        type Error = String;
+
+        fn try_from(value: Integer) -> Result<Self, Self::Error> {
+          $type::new(value)
+        }
+      }
+
+      implement_text_content!($type);
+    )*
+  };
+}
+
+implement_signed_integer!(
+  /// `xs:nonPositiveInteger`, ie. `0` or `-12345678901234567890`.
+  NonPositiveInteger, "a non-positive integer", |value| value.is_negative() || value.is_zero(), 0;
+  /// `xs:negativeInteger`, ie. `-12345678901234567890`.
+  NegativeInteger, "a negative integer", |value| value.is_negative(), -1;
+  /// `xs:nonNegativeInteger`, ie. `0` or `12345678901234567890`.
+  NonNegativeInteger, "a non-negative integer", |value| !value.is_negative(), 0;
+  /// `xs:positiveInteger`, ie. `12345678901234567890`.
+  PositiveInteger, "a positive integer", |value| !value.is_negative() && !value.is_zero(), 1;
+);
+
+/// Integer types derived from `xs:integer` by the sign of their values, in native precision:
+/// a primitive checked by its sign, when the primitive type does not reject the other values.
+macro_rules! implement_native_signed_integer {
+  ($($(#[$doc:meta])* $type:ident, $primitive:ty, $description:literal, $check:expr, $default:literal;)*) => {
+    $(
+      $(#[$doc])*
+      #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+      pub struct $type($primitive);
+
+      impl $type {
+        /// Create the value, checking its sign.
+        pub fn new(value: $primitive) -> Result<Self, String> {
+          let check: fn($primitive) -> bool = $check;
+          if !check(value) {
+            return Err(format!("{} is not {}", value, $description));
+          }
+          Ok($type(value))
+        }
+
+        pub fn value(&self) -> $primitive {
+          self.0
+        }
+      }
+
+      impl Default for $type {
+        fn default() -> Self {
+          $type($default)
+        }
+      }
+
+      impl FromStr for $type {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          let value = s
+            .trim()
+            .parse::<$primitive>()
+            .map_err(|e| format!("invalid integer {:?}: {}", s, e))?;
+          $type::new(value)
+        }
+      }
+
+      impl fmt::Display for $type {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          self.0.fmt(f)
+        }
+      }
+
+      impl From<$type> for $primitive {
+        fn from(value: $type) -> Self {
+          value.0
+        }
+      }
+
+      impl TryFrom<$primitive> for $type {
+        type Error = String;
+
+        fn try_from(value: $primitive) -> Result<Self, Self::Error> {
+          $type::new(value)
+        }
+      }
+
+      implement_text_content!($type);
+    )*
+  };
+}
+
+implement_native_signed_integer!(
+  /// `xs:nonPositiveInteger` in native precision, ie. `0` or `-42`.
+  NonPositiveI64, i64, "a non-positive integer", |value| value <= 0, 0;
+  /// `xs:negativeInteger` in native precision, ie. `-42`.
+  NegativeI64, i64, "a negative integer", |value| value < 0, -1;
+  /// `xs:positiveInteger` in native precision, ie. `42`.
+  PositiveU64, u64, "a positive integer", |value| value > 0, 1;
+);
+
+implement_text_content!(Integer);
+implement_text_content!(Decimal);
+
+fn split_sign(value: &str) -> (bool, &str) {
+  if let Some(digits) = value.strip_prefix('-') {
+    (true, digits)
+  } else {
+    (false, value.strip_prefix('+').unwrap_or(value))
+  }
+}
+
+/// Order of digits without leading zero.
+fn compare_digits(digits: &str, other: &str) -> Ordering {
+  digits
+    .len()
+    .cmp(&other.len())
+    .then_with(|| digits.cmp(other))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn integer() {
+    let value: Integer = "+000123456789012345678901234567890".parse().unwrap();
+    assert_eq!(value.to_string(), "123456789012345678901234567890");
+    assert_eq!("-0".parse::<Integer>().unwrap().to_string(), "0");
+    assert_eq!(" -42 ".parse::<Integer>(), Ok(Integer::from(-42)));
+
+    for invalid in ["", "-", "1.0", "1e3", "--1", "0x10"] {
+      assert!(invalid.parse::<Integer>().is_err(), "{}", invalid);
+    }
+
+    assert!("-10".parse::<Integer>().unwrap() < "-9".parse::<Integer>().unwrap());
+    assert!("100".parse::<Integer>().unwrap() > "99".parse::<Integer>().unwrap());
+
+    assert_eq!(i64::try_from(Integer::from(i64::MIN)), Ok(i64::MIN));
+    assert!(u8::try_from(Integer::from(256)).is_err());
+    assert!(u64::try_from(Integer::from(-1)).is_err());
+  }
+
+  #[test]
+  fn signed_integers() {
+    assert_eq!(
+      "12345678901234567890"
+        .parse::<PositiveInteger>()
+        .unwrap()
+        .to_string(),
+      "12345678901234567890"
+    );
+    assert_eq!(
+      "-5".parse::<PositiveInteger>(),
+      Err("-5 is not a positive integer".to_string())
+    );
+    assert!("0".parse::<PositiveInteger>().is_err());
+    assert_eq!(
+      "5".parse::<NegativeInteger>(),
+      Err("5 is not a negative integer".to_string())
+    );
+    assert!("0".parse::<NegativeInteger>().is_err());
+    assert!("-1".parse::<NonNegativeInteger>().is_err());
+    assert!("1".parse::<NonPositiveInteger>().is_err());
+    assert_eq!(
+      "-0".parse::<NonNegativeInteger>().unwrap().value(),
+      &Integer::default()
+    );
+    assert_eq!(
+      "-0".parse::<NonPositiveInteger>().unwrap(),
+      NonPositiveInteger::default()
+    );
+    assert!("-7".parse::<NegativeInteger>().unwrap() < NegativeInteger::default());
+
+    assert_eq!(PositiveInteger::default().to_string(), "1");
+    assert_eq!(NegativeInteger::default().to_string(), "-1");
+    assert!(PositiveInteger::try_from(Integer::from(-3)).is_err());
+    assert_eq!(
+      Integer::from(PositiveInteger::new(Integer::from(3)).unwrap()),
+      Integer::from(3)
+    );
+  }
+
+  #[test]
+  fn native_signed_integers() {
+    assert_eq!(
+      " -0 ".parse::<NonPositiveI64>(),
+      Ok(NonPositiveI64::default())
+    );
+    assert_eq!(
+      "3".parse::<NonPositiveI64>(),
+      Err("3 is not a non-positive integer".to_string())
+    );
+    assert_eq!("-7".parse::<NegativeI64>().unwrap().value(), -7);
+    assert_eq!(
+      "0".parse::<NegativeI64>(),
+      Err("0 is not a negative integer".to_string())
+    );
+    assert!("-7".parse::<NegativeI64>().unwrap() < NegativeI64::default());
+    assert_eq!(PositiveU64::default().to_string(), "1");
+    assert!("0".parse::<PositiveU64>().is_err());
+    assert!("-1".parse::<PositiveU64>().is_err());
+    assert_eq!(i64::from(NegativeI64::try_from(-3).unwrap()), -3);
+    assert!(PositiveU64::try_from(0).is_err());
+  }
+
+  #[test]
+  fn decimal() {
+    let value: Decimal = "-0012.3400".parse().unwrap();
+    assert_eq!(value.to_string(), "-12.34");
+    assert_eq!("1.0".parse::<Decimal>().unwrap().to_string(), "1");
+    assert_eq!(".5".parse::<Decimal>().unwrap().to_string(), "0.5");
+    assert_eq!("5.".parse::<Decimal>().unwrap().to_string(), "5");
+    assert_eq!("-0.0".parse::<Decimal>().unwrap().to_string(), "0");
+    assert_eq!(
+      "0.1".parse::<Decimal>().unwrap(),
+      "0.10".parse::<Decimal>().unwrap()
+    );
+
+    for invalid in ["", ".", "-", "1.2.3", "1e3", "NaN", "1,5"] {
+      assert!(invalid.parse::<Decimal>().is_err(), "{}", invalid);
+    }
+
+    let parse = |value: &str| value.parse::<Decimal>().unwrap();
+    assert!(parse("0.5") < parse("0.51"));
+    assert!(parse("0.6") > parse("0.51"));
+    assert!(parse("-1.5") < parse("-1.25"));
+    assert!(parse("-0.1") < parse("0"));
+    assert!(parse("10") > parse("9.99"));
+
+    assert_eq!(Decimal::try_from(0.1_f64), Ok(parse("0.1")));
+    assert!(Decimal::try_from(f64::NAN).is_err());
+    assert_eq!(parse("2.5").to_f64(), 2.5);
+    assert_eq!(Integer::try_from(parse("12.0")), Ok(Integer::from(12)));
+    assert!(Integer::try_from(parse("12.5")).is_err());
+  }
+}
@@ -0,0 +1,389 @@
+//! Qualified names of XML Schema, ie. `soap:Client`, resolved against the namespaces of the document.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// `xs:QName`, a local name with an optional prefix and the namespace bound to it.
+///
+/// The namespace is resolved from the declarations in scope when the value is read, from the text
+/// content of an element or from an attribute, and is declared on the element when the value is
+/// written.
+/// The values are compared by their namespace and local name, whatever their prefixes.
+#[derive(Clone, Debug, Default)]
+pub struct QName {
+  namespace: Option<String>,
+  prefix: Option<String>,
+  local_name: String,
+}
+
+impl PartialEq for QName {
+  fn eq(&self, other: &Self) -> bool {
+    self.namespace == other.namespace && self.local_name == other.local_name
+  }
+}
+
+impl Eq for QName {}
+
+impl Hash for QName {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.namespace.hash(state);
+    self.local_name.hash(state);
+  }
+}
+
+impl QName {
+  pub fn new(
+    namespace: Option<&str>,
+    prefix: Option<&str>,
+    local_name: &str,
+  ) -> Result<Self, String> {
+    for name in prefix.iter().chain([&local_name]) {
+      if !is_ncname(name) {
+        return Err(format!("invalid QName part {name:?}"));
+      }
+    }
+
+    Ok(QName {
+      namespace: namespace.filter(|uri| !uri.is_empty()).map(String::from),
+      prefix: prefix.map(String::from),
+      local_name: local_name.to_string(),
+    })
+  }
+
+  pub fn namespace(&self) -> Option<&str> {
+    self.namespace.as_deref()
+  }
+
+  pub fn prefix(&self) -> Option<&str> {
+    self.prefix.as_deref()
+  }
+
+  pub fn local_name(&self) -> &str {
+    &self.local_name
+  }
+
+  /// The namespace bound to the prefix, or the default namespace (if any) without prefix.
+  /// A prefix without declaration in scope is an error.
+  pub fn resolve(mut self, namespace: &xml::namespace::Namespace) -> Result<Self, String> {
+    let uri = match &self.prefix {
+      Some(prefix) => match namespace.get(prefix).filter(|uri| !uri.is_empty()) {
+        Some(uri) => Some(uri),
+        None => {
+          return Err(format!(
+            "the prefix of the QName {:?} is not declared",
+            self.to_string()
+          ))
+        }
+      },
+      None => namespace
+        .get(xml::namespace::NS_NO_PREFIX)
+        .filter(|uri| !uri.is_empty()),
+    };
+    self.namespace = uri.map(String::from);
+    Ok(self)
+  }
+
+  /// The prefix to write the value: its own one, or `ns` (or `ns1`, `ns2`, ...) without prefix,
+  /// when the element name uses it or when `is_free` rejects it as bound to another namespace.
+  fn declared_prefix<F>(&self, element_prefix: Option<&str>, is_free: F) -> Option<String>
+  where
+    F: Fn(&str) -> bool,
+  {
+    self.namespace.as_ref()?;
+
+    let generated = (0..).map(|index| match index {
+      0 => "ns".to_string(),
+      index => format!("ns{index}"),
+    });
+    self
+      .prefix
+      .clone()
+      .into_iter()
+      .chain(generated)
+      .find(|prefix| Some(prefix.as_str()) != element_prefix && is_free(prefix))
+  }
+}
+
+/// The namespaces in scope of the element read by the deserializer, to resolve the QNames of its
+/// attributes.
+pub fn in_scope_namespace<R: std::io::Read>(
+  reader: &mut hifa_yaserde::de::Deserializer<R>,
+) -> Result<xml::namespace::Namespace, String> {
+  match reader.peek()? {
+    xml::reader::XmlEvent::StartElement { namespace, .. } => Ok(namespace.clone()),
+    _ => Ok(xml::namespace::Namespace::empty()),
+  }
+}
+
+/// The `xs:QName` attributes of an element, written with the declarations of their namespaces
+/// by `serialize_attributes`.
+/// A prefix bound to another namespace, by the element name or by another value, is replaced.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QNameAttributes {
+  element_prefix: Option<String>,
+  attributes: Vec<(String, QName)>,
+}
+
+impl QNameAttributes {
+  pub fn new(element_name: Option<&str>) -> Self {
+    QNameAttributes {
+      element_prefix: element_name
+        .and_then(|name| name.split_once(':'))
+        .map(|(prefix, _)| prefix.to_string()),
+      attributes: vec![],
+    }
+  }
+
+  /// Add the value of an attribute, a missing optional value is not written.
+  pub fn push(&mut self, name: &str, value: Option<&QName>) {
+    if let Some(value) = value {
+      self.attributes.push((name.to_string(), value.clone()));
+    }
+  }
+}
+
+impl FromStr for QName {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let value = s.trim();
+    let (prefix, local_name) = match value.split_once(':') {
+      Some((prefix, local_name)) => (Some(prefix), local_name),
+      None => (None, value),
+    };
+
+    QName::new(None, prefix, local_name).map_err(|e| format!("invalid QName {s:?}: {e}"))
+  }
+}
+
+impl fmt::Display for QName {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(prefix) = &self.prefix {
+      write!(f, "{prefix}:")?;
+    }
+    f.write_str(&self.local_name)
+  }
+}
+
+impl hifa_yaserde::YaDeserialize for QName {
+  fn deserialize<R: std::io::Read>(
+    reader: &mut hifa_yaserde::de::Deserializer<R>,
+  ) -> Result<Self, String> {
+    let namespace = in_scope_namespace(reader)?;
+    if matches!(reader.peek()?, xml::reader::XmlEvent::StartElement { .. }) {
+      reader.next_event()?;
+    }
+
+    let content = match reader.peek()?.clone() {
+      xml::reader::XmlEvent::Characters(content) => {
+        reader.next_event()?;
+        content
+      }
+      _ => String::new(),
+    };
+
+    content.parse::<QName>()?.resolve(&namespace)
+  }
+}
+
+impl hifa_yaserde::YaSerialize for QName {
+  fn serialize<W: std::io::Write>(
+    &self,
+    writer: &mut hifa_yaserde::ser::Serializer<W>,
+  ) -> Result<(), String> {
+    let skip = writer.skip_start_end();
+    let mut content = self.to_string();
+
+    if !skip {
+      let name = writer
+        .get_start_event_name()
+        .unwrap_or_else(|| "QName".to_string());
+
+      let mut start_event = xml::writer::XmlEvent::start_element(name.as_str());
+      let element_prefix = name.split_once(':').map(|(prefix, _)| prefix);
+      if let (Some(namespace), Some(prefix)) = (
+        &self.namespace,
+        self.declared_prefix(element_prefix, |_| true),
+      ) {
+        content = format!("{prefix}:{}", self.local_name);
+        start_event = start_event.ns(prefix, namespace.as_str());
+      }
+
+      writer.write(start_event).map_err(|e| e.to_string())?;
+    }
+
+    writer
+      .write(xml::writer::XmlEvent::characters(&content))
+      .map_err(|e| e.to_string())?;
+
+    if !skip {
+      writer
+        .write(xml::writer::XmlEvent::end_element())
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+  }
+
+  fn serialize_attributes(
+    &self,
+    source_attributes: Vec<xml::attribute::OwnedAttribute>,
+    source_namespace: xml::namespace::Namespace,
+  ) -> Result<
+    (
+      Vec<xml::attribute::OwnedAttribute>,
+      xml::namespace::Namespace,
+    ),
+    String,
+  > {
+    Ok((source_attributes, source_namespace))
+  }
+}
+
+impl hifa_yaserde::YaSerialize for QNameAttributes {
+  fn serialize<W: std::io::Write>(
+    &self,
+    _writer: &mut hifa_yaserde::ser::Serializer<W>,
+  ) -> Result<(), String> {
+    Ok(())
+  }
+
+  fn serialize_attributes(
+    &self,
+    mut source_attributes: Vec<xml::attribute::OwnedAttribute>,
+    mut source_namespace: xml::namespace::Namespace,
+  ) -> Result<
+    (
+      Vec<xml::attribute::OwnedAttribute>,
+      xml::namespace::Namespace,
+    ),
+    String,
+  > {
+    for (name, value) in &self.attributes {
+      let content = match &value.namespace {
+        Some(namespace) => {
+          let prefix = value
+            .declared_prefix(self.element_prefix.as_deref(), |prefix| {
+              source_namespace
+                .get(prefix)
+                .is_none_or(|uri| uri == namespace)
+            })
+            .ok_or_else(|| format!("no prefix for the namespace {namespace:?}"))?;
+          source_namespace.put(prefix.as_str(), namespace.as_str());
+          format!("{prefix}:{}", value.local_name)
+        }
+        None => value.to_string(),
+      };
+
+      source_attributes.push(xml::attribute::OwnedAttribute::new(
+        xml::name::OwnedName::local(name.as_str()),
+        content,
+      ));
+    }
+
+    Ok((source_attributes, source_namespace))
+  }
+}
+
+fn is_ncname(name: &str) -> bool {
+  let mut characters = name.chars();
+  characters
+    .next()
+    .is_some_and(|first| first.is_alphabetic() || first == '_')
+    && characters.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '\u{b7}'))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hifa_yaserde::de::from_str;
+  use hifa_yaserde::ser::to_string;
+
+  #[test]
+  fn qname() {
+    let value: QName = " soap:Client ".parse().unwrap();
+    assert_eq!(value.prefix(), Some("soap"));
+    assert_eq!(value.local_name(), "Client");
+    assert_eq!(value.namespace(), None);
+    assert_eq!(value.to_string(), "soap:Client");
+    assert_eq!("Client".parse::<QName>().unwrap().prefix(), None);
+
+    for invalid in ["", "a:", ":a", "a:b:c", "1a", "a b"] {
+      assert!(invalid.parse::<QName>().is_err(), "{}", invalid);
+    }
+  }
+
+  #[test]
+  fn qname_namespaces() {
+    let value: QName = from_str(r#"<QName xmlns:s="urn:soap">s:Client</QName>"#).unwrap();
+    assert_eq!(
+      value,
+      QName::new(Some("urn:soap"), Some("s"), "Client").unwrap()
+    );
+
+    let value: QName = from_str(r#"<QName xmlns="urn:default">Client</QName>"#).unwrap();
+    assert_eq!(value.namespace(), Some("urn:default"));
+
+    let value: QName = from_str(r#"<QName>Client</QName>"#).unwrap();
+    assert_eq!(value.namespace(), None);
+
+    assert_eq!(
+      from_str::<QName>(r#"<QName>s:Client</QName>"#),
+      Err(r#"the prefix of the QName "s:Client" is not declared"#.to_string())
+    );
+    assert_eq!(
+      from_str::<QName>(r#"<QName xmlns="urn:default">s:Client</QName>"#),
+      Err(r#"the prefix of the QName "s:Client" is not declared"#.to_string())
+    );
+
+    let value = QName::new(Some("urn:soap"), None, "Client").unwrap();
+    assert_eq!(
+      to_string(&value).unwrap(),
+      r#"<?xml version="1.0" encoding="UTF-8"?><QName xmlns:ns="urn:soap">ns:Client</QName>"#
+    );
+  }
+
+  #[test]
+  fn qname_equality() {
+    use std::collections::HashSet;
+
+    let soap = from_str::<QName>(r#"<QName xmlns:s="urn:soap">s:Client</QName>"#).unwrap();
+    let other = from_str::<QName>(r#"<QName xmlns:env="urn:soap">env:Client</QName>"#).unwrap();
+    assert_eq!(soap, other);
+    assert_eq!(HashSet::from([soap.clone(), other]).len(), 1);
+
+    let default = from_str::<QName>(r#"<QName xmlns="urn:soap">Client</QName>"#).unwrap();
+    assert_eq!(soap, default);
+
+    assert_ne!(
+      soap,
+      QName::new(Some("urn:other"), Some("s"), "Client").unwrap()
+    );
+    assert_ne!(
+      soap,
+      QName::new(Some("urn:soap"), Some("s"), "Server").unwrap()
+    );
+  }
+
+  #[test]
+  fn qname_declared_prefix() {
+    let value = QName::new(Some("urn:soap"), Some("s"), "Client").unwrap();
+    assert_eq!(value.declared_prefix(None, |_| true), Some("s".to_string()));
+    // the prefix of the element name may be bound to another namespace
+    assert_eq!(
+      value.declared_prefix(Some("s"), |_| true),
+      Some("ns".to_string())
+    );
+    assert_eq!(
+      value.declared_prefix(Some("ns"), |prefix| prefix != "s"),
+      Some("ns1".to_string())
+    );
+    assert_eq!(
+      QName::new(None, Some("s"), "Client")
+        .unwrap()
+        .declared_prefix(None, |_| true),
+      None
+    );
+  }
+}
@@ -0,0 +1,124 @@
+//! Strings of XML Schema normalized with their `whiteSpace` facet, ie. `xs:token`.
+
+use crate::facets::WhiteSpace;
+use crate::text_content::implement_text_content;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// `xs:normalizedString`, the tabs, line feeds and carriage returns are replaced by spaces.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NormalizedString(String);
+
+/// `xs:token` and its derived types (ie. `xs:NCName`, `xs:ID`), without leading, trailing
+/// or consecutive whitespaces.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Token(String);
+
+/// The text content of an element as it is, read even when the element is empty,
+/// ie. to apply the `default` value of an element.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Text(String);
+
+macro_rules! implement_normalized_string {
+  ($type:ident, $white_space:ident) => {
+    impl $type {
+      pub fn new(value: &str) -> Self {
+        $type(WhiteSpace::$white_space.normalize(value).into_owned())
+      }
+
+      pub fn as_str(&self) -> &str {
+        &self.0
+      }
+
+      pub fn into_string(self) -> String {
+        self.0
+      }
+    }
+
+    impl FromStr for $type {
+      type Err = String;
+
+      fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok($type::new(s))
+      }
+    }
+
+    impl fmt::Display for $type {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+      }
+    }
+
+    impl From<&str> for $type {
+      fn from(value: &str) -> Self {
+        $type::new(value)
+      }
+    }
+
+    impl From<String> for $type {
+      fn from(value: String) -> Self {
+        match WhiteSpace::$white_space.normalize(&value) {
+          std::borrow::Cow::Borrowed(_) => $type(value),
+          std::borrow::Cow::Owned(normalized) => $type(normalized),
+        }
+      }
+    }
+
+    impl From<$type> for String {
+      fn from(value: $type) -> Self {
+        value.0
+      }
+    }
+
+    impl Deref for $type {
+      type Target = str;
+
+      fn deref(&self) -> &Self::Target {
+        &self.0
+      }
+    }
+
+    impl AsRef<str> for $type {
+      fn as_ref(&self) -> &str {
+        &self.0
+      }
+    }
+
+    impl PartialEq<str> for $type {
+      fn eq(&self, other: &str) -> bool {
+        self.0 == other
+      }
+    }
+
+    impl PartialEq<&str> for $type {
+      fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+      }
+    }
+
+    implement_text_content!($type);
+  };
+}
+
+implement_normalized_string!(NormalizedString, Replace);
+implement_normalized_string!(Token, Collapse);
+implement_normalized_string!(Text, Preserve);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn normalized_strings() {
+    let value = " first\tsecond \r\n third ";
+    assert_eq!(NormalizedString::from(value), " first second    third ");
+    assert_eq!(Token::from(value), "first second third");
+    assert_eq!(
+      Token::from(value.to_string()).into_string(),
+      "first second third"
+    );
+    assert_eq!("ID-1".parse::<Token>().unwrap().as_str(), "ID-1");
+    assert_eq!(Text::from(value), value);
+  }
+}
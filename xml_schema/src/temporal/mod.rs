@@ -0,0 +1,1599 @@
+//! Date and time types of XML Schema, parsed from their lexical form and written in their canonical form.
+//! As in the schema values, the timezone is optional.
+
+#[cfg(feature = "chrono")]
+mod with_chrono;
+#[cfg(feature = "time")]
+mod with_time;
+
+use crate::text_content::implement_text_content;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+const NANOSECONDS_PER_SECOND: u32 = 1_000_000_000;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Timezone offset of a value, from `-14:00` to `+14:00`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Timezone {
+  offset_minutes: i16,
+}
+
+impl Timezone {
+  pub const UTC: Timezone = Timezone { offset_minutes: 0 };
+
+  pub fn new(offset_minutes: i16) -> Result<Self, String> {
+    if !(-14 * 60..=14 * 60).contains(&offset_minutes) {
+      return Err(format!(
+        "timezone offset of {offset_minutes} minutes is out of -14:00..+14:00"
+      ));
+    }
+
+    Ok(Timezone { offset_minutes })
+  }
+
+  pub fn offset_minutes(&self) -> i16 {
+    self.offset_minutes
+  }
+}
+
+impl FromStr for Timezone {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("timezone", s);
+    let timezone = lexer
+      .timezone()?
+      .ok_or_else(|| lexer.error("missing timezone"))?;
+    lexer.end()?;
+    Ok(timezone)
+  }
+}
+
+impl fmt::Display for Timezone {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.offset_minutes == 0 {
+      return f.write_str("Z");
+    }
+
+    let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+    let offset = self.offset_minutes.unsigned_abs();
+    write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+  }
+}
+
+/// `xs:date`, ie. `2024-02-29` or `2024-02-29+01:00`.
+/// The dates are compared by their starting instant.
+#[derive(Clone, Copy, Debug)]
+pub struct Date {
+  year: i32,
+  month: u8,
+  day: u8,
+  timezone: Option<Timezone>,
+}
+
+impl Date {
+  pub fn new(year: i32, month: u8, day: u8) -> Result<Self, String> {
+    check_month(month)?;
+    if day == 0 || day > days_in_month(year, month) {
+      return Err(format!(
+        "day {day} is out of range for {year:04}-{month:02}"
+      ));
+    }
+
+    Ok(Date {
+      year,
+      month,
+      day,
+      timezone: None,
+    })
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    Date { timezone, ..self }
+  }
+
+  pub fn year(&self) -> i32 {
+    self.year
+  }
+
+  pub fn month(&self) -> u8 {
+    self.month
+  }
+
+  pub fn day(&self) -> u8 {
+    self.day
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+
+  fn next_day(self) -> Result<Self, String> {
+    if self.day < days_in_month(self.year, self.month) {
+      Ok(Date {
+        day: self.day + 1,
+        ..self
+      })
+    } else if self.month < 12 {
+      Ok(Date {
+        month: self.month + 1,
+        day: 1,
+        ..self
+      })
+    } else {
+      let year = self
+        .year
+        .checked_add(1)
+        .ok_or_else(|| "year is out of range".to_string())?;
+      Ok(Date {
+        year,
+        month: 1,
+        day: 1,
+        ..self
+      })
+    }
+  }
+}
+
+impl Default for Date {
+  fn default() -> Self {
+    Date {
+      year: 1970,
+      month: 1,
+      day: 1,
+      timezone: None,
+    }
+  }
+}
+
+impl FromStr for Date {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("date", s);
+    let date = lexer.date()?;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+    Ok(date.with_timezone(timezone))
+  }
+}
+
+impl fmt::Display for Date {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_year(f, self.year)?;
+    write!(f, "-{:02}-{:02}", self.month, self.day)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// `xs:time`, ie. `13:20:00.5` or `13:20:00Z`.
+/// The fractional seconds are kept up to the nanosecond, the following digits are truncated.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Time {
+  hour: u8,
+  minute: u8,
+  second: u8,
+  nanosecond: u32,
+  timezone: Option<Timezone>,
+}
+
+impl Time {
+  pub fn new(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Result<Self, String> {
+    if hour > 23 || minute > 59 || second > 59 || nanosecond >= NANOSECONDS_PER_SECOND {
+      return Err(format!(
+        "time {hour:02}:{minute:02}:{second:02}.{nanosecond:09} is out of range"
+      ));
+    }
+
+    Ok(Time {
+      hour,
+      minute,
+      second,
+      nanosecond,
+      timezone: None,
+    })
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    Time { timezone, ..self }
+  }
+
+  pub fn hour(&self) -> u8 {
+    self.hour
+  }
+
+  pub fn minute(&self) -> u8 {
+    self.minute
+  }
+
+  pub fn second(&self) -> u8 {
+    self.second
+  }
+
+  pub fn nanosecond(&self) -> u32 {
+    self.nanosecond
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+}
+
+impl FromStr for Time {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("time", s);
+    let (time, _end_of_day) = lexer.time()?;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+    Ok(time.with_timezone(timezone))
+  }
+}
+
+impl fmt::Display for Time {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+    write_fraction(f, self.nanosecond)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// `xs:dateTime`, ie. `2024-02-29T13:20:00` or `2024-02-29T13:20:00-05:00`.
+/// The end of a day `24:00:00` is read as the start of the next day.
+/// The values with a timezone are equal when they are the same instant, ie. `10:00:00Z` and `11:00:00+01:00`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DateTime {
+  date: Date,
+  time: Time,
+  timezone: Option<Timezone>,
+}
+
+impl DateTime {
+  /// Date time of the local date and time, the timezone is set with `with_timezone`.
+  pub fn new(date: Date, time: Time) -> Self {
+    DateTime {
+      date: date.with_timezone(None),
+      time: time.with_timezone(None),
+      timezone: None,
+    }
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    DateTime { timezone, ..self }
+  }
+
+  pub fn date(&self) -> Date {
+    self.date.with_timezone(self.timezone)
+  }
+
+  pub fn time(&self) -> Time {
+    self.time.with_timezone(self.timezone)
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+}
+
+impl FromStr for DateTime {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("dateTime", s);
+    let date = lexer.date()?;
+    lexer.expect('T')?;
+    let (time, end_of_day) = lexer.time()?;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+
+    let date = if end_of_day {
+      date.next_day().map_err(|reason| lexer.error(&reason))?
+    } else {
+      date
+    };
+
+    Ok(DateTime::new(date, time).with_timezone(timezone))
+  }
+}
+
+impl fmt::Display for DateTime {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}T{}", self.date, self.time)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// `xs:duration`, ie. `P1Y2M3DT4H5M6.7S` or `-PT30M`.
+/// The value is stored as a number of months and a number of seconds, and written in the canonical form:
+/// the months are expressed in years and months, the seconds in days, hours, minutes and seconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Duration {
+  negative: bool,
+  months: u64,
+  seconds: u64,
+  nanoseconds: u32,
+}
+
+impl Duration {
+  pub fn new(negative: bool, months: u64, seconds: u64, nanoseconds: u32) -> Result<Self, String> {
+    if nanoseconds >= NANOSECONDS_PER_SECOND {
+      return Err(format!("{nanoseconds} nanoseconds is out of range"));
+    }
+
+    let is_zero = months == 0 && seconds == 0 && nanoseconds == 0;
+
+    Ok(Duration {
+      negative: negative && !is_zero,
+      months,
+      seconds,
+      nanoseconds,
+    })
+  }
+
+  pub fn is_negative(&self) -> bool {
+    self.negative
+  }
+
+  pub fn years(&self) -> u64 {
+    self.months / 12
+  }
+
+  pub fn months(&self) -> u64 {
+    self.months % 12
+  }
+
+  pub fn days(&self) -> u64 {
+    self.seconds / SECONDS_PER_DAY
+  }
+
+  pub fn hours(&self) -> u64 {
+    self.seconds % SECONDS_PER_DAY / 3600
+  }
+
+  pub fn minutes(&self) -> u64 {
+    self.seconds % 3600 / 60
+  }
+
+  pub fn seconds(&self) -> u64 {
+    self.seconds % 60
+  }
+
+  pub fn nanoseconds(&self) -> u32 {
+    self.nanoseconds
+  }
+
+  /// The years and months of the duration, in months.
+  pub fn total_months(&self) -> u64 {
+    self.months
+  }
+
+  /// The days, hours, minutes and seconds of the duration, in seconds.
+  pub fn total_seconds(&self) -> u64 {
+    self.seconds
+  }
+}
+
+impl FromStr for Duration {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let lexer = Lexer::new("duration", s);
+    let overflow = || lexer.error("value is out of range");
+    let s = s.trim();
+
+    let (negative, rest) = match s.strip_prefix('-') {
+      Some(rest) => (true, rest),
+      None => (false, s),
+    };
+    let rest = rest
+      .strip_prefix('P')
+      .ok_or_else(|| lexer.error("missing P designator"))?;
+    let (date_part, time_part) = match rest.split_once('T') {
+      Some((_, "")) => return Err(lexer.error("missing time component after T")),
+      Some((date_part, time_part)) => (date_part, Some(time_part)),
+      None => (rest, None),
+    };
+
+    if date_part.is_empty() && time_part.is_none() {
+      return Err(lexer.error("missing component"));
+    }
+
+    let mut months: u64 = 0;
+    let mut seconds: u64 = 0;
+    let mut nanoseconds = 0;
+
+    for (value, designator) in duration_components(date_part, "YMD").map_err(|e| lexer.error(&e))? {
+      if value.contains('.') {
+        return Err(lexer.error("only the seconds can have a fraction"));
+      }
+
+      let value: u64 = value.parse().map_err(|_| overflow())?;
+      let (total, factor) = match designator {
+        'Y' => (&mut months, 12),
+        'M' => (&mut months, 1),
+        _ => (&mut seconds, SECONDS_PER_DAY),
+      };
+      *total = value
+        .checked_mul(factor)
+        .and_then(|value| total.checked_add(value))
+        .ok_or_else(overflow)?;
+    }
+
+    for (value, designator) in
+      duration_components(time_part.unwrap_or_default(), "HMS").map_err(|e| lexer.error(&e))?
+    {
+      let (value, fraction) = match value.split_once('.') {
+        Some(_) if designator != 'S' => {
+          return Err(lexer.error("only the seconds can have a fraction"))
+        }
+        Some((value, fraction)) => (value, Some(fraction)),
+        None => (value, None),
+      };
+
+      if let Some(fraction) = fraction {
+        nanoseconds = parse_fraction(fraction).ok_or_else(|| lexer.error("invalid fraction"))?;
+      }
+
+      let value: u64 = value.parse().map_err(|_| overflow())?;
+      let factor = match designator {
+        'H' => 3600,
+        'M' => 60,
+        _ => 1,
+      };
+      seconds = value
+        .checked_mul(factor)
+        .and_then(|value| seconds.checked_add(value))
+        .ok_or_else(overflow)?;
+    }
+
+    Duration::new(negative, months, seconds, nanoseconds)
+  }
+}
+
+impl fmt::Display for Duration {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.months == 0 && self.seconds == 0 && self.nanoseconds == 0 {
+      return f.write_str("PT0S");
+    }
+
+    if self.negative {
+      f.write_str("-")?;
+    }
+    f.write_str("P")?;
+
+    for (value, designator) in [
+      (self.years(), 'Y'),
+      (self.months(), 'M'),
+      (self.days(), 'D'),
+    ] {
+      if value != 0 {
+        write!(f, "{value}{designator}")?;
+      }
+    }
+
+    if self.hours() == 0 && self.minutes() == 0 && self.seconds() == 0 && self.nanoseconds == 0 {
+      return Ok(());
+    }
+
+    f.write_str("T")?;
+    for (value, designator) in [(self.hours(), 'H'), (self.minutes(), 'M')] {
+      if value != 0 {
+        write!(f, "{value}{designator}")?;
+      }
+    }
+
+    if self.seconds() != 0 || self.nanoseconds != 0 {
+      write!(f, "{}", self.seconds())?;
+      write_fraction(f, self.nanoseconds)?;
+      f.write_str("S")?;
+    }
+
+    Ok(())
+  }
+}
+
+impl From<std::time::Duration> for Duration {
+  fn from(duration: std::time::Duration) -> Self {
+    Duration {
+      negative: false,
+      months: 0,
+      seconds: duration.as_secs(),
+      nanoseconds: duration.subsec_nanos(),
+    }
+  }
+}
+
+impl TryFrom<Duration> for std::time::Duration {
+  type Error = String;
+
+  fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+    if duration.negative || duration.months != 0 {
+      return Err(format!(
+        "duration {duration} is not a positive number of seconds"
+      ));
+    }
+
+    Ok(std::time::Duration::new(
+      duration.seconds,
+      duration.nanoseconds,
+    ))
+  }
+}
+
+/// Types of XSD 1.1 restricting a temporal type, the values are checked when they are created.
+macro_rules! implement_restricted_type {
+  ($($(#[$doc:meta])* $type:ident($base:ident), $kind:literal, $check:expr, $reason:literal, $default:expr;)*) => {
+    $(
+      $(#[$doc])*
+      #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd)]
+      pub struct $type($base);
+
+      impl Default for $type {
+        fn default() -> Self {
+          $type($default)
+        }
+      }
+
+      impl $type {
+        pub fn new(value: $base) -> Result<Self, String> {
+          let check: fn(&$base) -> bool = $check;
+          if !check(&value) {
+            return Err(format!("invalid {} {:?}: {}", $kind, value.to_string(), $reason));
+          }
+          Ok($type(value))
+        }
+
+        pub fn value(&self) -> &$base {
+          &self.0
+        }
+
+        pub fn into_value(self) -> $base {
+          self.0
+        }
+      }
+
+      impl FromStr for $type {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          $type::new(s.parse()?)
+        }
+      }
+
+      impl fmt::Display for $type {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+          self.0.fmt(f)
+        }
+      }
+
+      impl From<$type> for $base {
+        fn from(value: $type) -> Self {
+          value.0
+        }
+      }
+
+      impl TryFrom<$base> for $type {
+        type Error = String;
+
+        fn try_from(value: $base) -> Result<Self, Self::Error> {
+          $type::new(value)
+        }
+      }
+
+      implement_text_content!($type);
+    )*
+  };
+}
+
+implement_restricted_type!(
+  /// `xs:dateTimeStamp`, a `DateTime` with a required timezone, ie. `2024-02-29T13:20:00Z`.
+  /// The default value is `1970-01-01T00:00:00Z`.
+  DateTimeStamp(DateTime), "dateTimeStamp", |value| value.timezone.is_some(), "missing timezone",
+  DateTime::default().with_timezone(Some(Timezone::UTC));
+  /// `xs:dayTimeDuration`, a `Duration` without years and months, ie. `P1DT12H`.
+  DayTimeDuration(Duration), "dayTimeDuration", |value| value.months == 0,
+  "unexpected years or months", Duration::default();
+  /// `xs:yearMonthDuration`, a `Duration` of years and months only, ie. `P1Y6M`.
+  YearMonthDuration(Duration), "yearMonthDuration",
+  |value| value.seconds == 0 && value.nanoseconds == 0,
+  "unexpected days, hours, minutes or seconds", Duration::default();
+);
+
+/// `xs:gYear`, ie. `2024`, `-0044` or `2024+02:00`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GYear {
+  year: i32,
+  timezone: Option<Timezone>,
+}
+
+impl GYear {
+  pub fn new(year: i32) -> Self {
+    GYear {
+      year,
+      timezone: None,
+    }
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    GYear { timezone, ..self }
+  }
+
+  pub fn year(&self) -> i32 {
+    self.year
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+}
+
+impl Default for GYear {
+  fn default() -> Self {
+    GYear::new(1970)
+  }
+}
+
+impl FromStr for GYear {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("gYear", s);
+    let year = lexer.year()?;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+
+    Ok(GYear::new(year).with_timezone(timezone))
+  }
+}
+
+impl fmt::Display for GYear {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_year(f, self.year)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// `xs:gYearMonth`, ie. `2024-02`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GYearMonth {
+  year: i32,
+  month: u8,
+  timezone: Option<Timezone>,
+}
+
+impl GYearMonth {
+  pub fn new(year: i32, month: u8) -> Result<Self, String> {
+    check_month(month)?;
+    Ok(GYearMonth {
+      year,
+      month,
+      timezone: None,
+    })
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    GYearMonth { timezone, ..self }
+  }
+
+  pub fn year(&self) -> i32 {
+    self.year
+  }
+
+  pub fn month(&self) -> u8 {
+    self.month
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+}
+
+impl Default for GYearMonth {
+  fn default() -> Self {
+    GYearMonth {
+      year: 1970,
+      month: 1,
+      timezone: None,
+    }
+  }
+}
+
+impl FromStr for GYearMonth {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("gYearMonth", s);
+    let year = lexer.year()?;
+    lexer.expect('-')?;
+    let month = lexer.digits(2)? as u8;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+
+    GYearMonth::new(year, month)
+      .map(|value| value.with_timezone(timezone))
+      .map_err(|reason| lexer.error(&reason))
+  }
+}
+
+impl fmt::Display for GYearMonth {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_year(f, self.year)?;
+    write!(f, "-{:02}", self.month)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// `xs:gMonthDay`, a recurring day of the year, ie. `--02-29`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GMonthDay {
+  month: u8,
+  day: u8,
+  timezone: Option<Timezone>,
+}
+
+impl GMonthDay {
+  pub fn new(month: u8, day: u8) -> Result<Self, String> {
+    check_month(month)?;
+    // the day is checked against a leap year, as the value recurs every year
+    if day == 0 || day > days_in_month(2000, month) {
+      return Err(format!("day {day} is out of range for --{month:02}"));
+    }
+
+    Ok(GMonthDay {
+      month,
+      day,
+      timezone: None,
+    })
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    GMonthDay { timezone, ..self }
+  }
+
+  pub fn month(&self) -> u8 {
+    self.month
+  }
+
+  pub fn day(&self) -> u8 {
+    self.day
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+}
+
+impl Default for GMonthDay {
+  fn default() -> Self {
+    GMonthDay {
+      month: 1,
+      day: 1,
+      timezone: None,
+    }
+  }
+}
+
+impl FromStr for GMonthDay {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("gMonthDay", s);
+    lexer.expect('-')?;
+    lexer.expect('-')?;
+    let month = lexer.digits(2)? as u8;
+    lexer.expect('-')?;
+    let day = lexer.digits(2)? as u8;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+
+    GMonthDay::new(month, day)
+      .map(|value| value.with_timezone(timezone))
+      .map_err(|reason| lexer.error(&reason))
+  }
+}
+
+impl fmt::Display for GMonthDay {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "--{:02}-{:02}", self.month, self.day)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// `xs:gDay`, a recurring day of the month, ie. `---15`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GDay {
+  day: u8,
+  timezone: Option<Timezone>,
+}
+
+impl GDay {
+  pub fn new(day: u8) -> Result<Self, String> {
+    if !(1..=31).contains(&day) {
+      return Err(format!("day {day} is out of range"));
+    }
+
+    Ok(GDay {
+      day,
+      timezone: None,
+    })
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    GDay { timezone, ..self }
+  }
+
+  pub fn day(&self) -> u8 {
+    self.day
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+}
+
+impl Default for GDay {
+  fn default() -> Self {
+    GDay {
+      day: 1,
+      timezone: None,
+    }
+  }
+}
+
+impl FromStr for GDay {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("gDay", s);
+    lexer.expect('-')?;
+    lexer.expect('-')?;
+    lexer.expect('-')?;
+    let day = lexer.digits(2)? as u8;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+
+    GDay::new(day)
+      .map(|value| value.with_timezone(timezone))
+      .map_err(|reason| lexer.error(&reason))
+  }
+}
+
+impl fmt::Display for GDay {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "---{:02}", self.day)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// `xs:gMonth`, a recurring month of the year, ie. `--02`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GMonth {
+  month: u8,
+  timezone: Option<Timezone>,
+}
+
+impl GMonth {
+  pub fn new(month: u8) -> Result<Self, String> {
+    check_month(month)?;
+    Ok(GMonth {
+      month,
+      timezone: None,
+    })
+  }
+
+  pub fn with_timezone(self, timezone: Option<Timezone>) -> Self {
+    GMonth { timezone, ..self }
+  }
+
+  pub fn month(&self) -> u8 {
+    self.month
+  }
+
+  pub fn timezone(&self) -> Option<Timezone> {
+    self.timezone
+  }
+}
+
+impl Default for GMonth {
+  fn default() -> Self {
+    GMonth {
+      month: 1,
+      timezone: None,
+    }
+  }
+}
+
+impl FromStr for GMonth {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut lexer = Lexer::new("gMonth", s);
+    lexer.expect('-')?;
+    lexer.expect('-')?;
+    let month = lexer.digits(2)? as u8;
+    let timezone = lexer.timezone()?;
+    lexer.end()?;
+
+    GMonth::new(month)
+      .map(|value| value.with_timezone(timezone))
+      .map_err(|reason| lexer.error(&reason))
+  }
+}
+
+impl fmt::Display for GMonth {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "--{:02}", self.month)?;
+    write_timezone(f, self.timezone)
+  }
+}
+
+/// The instants of the values on the timeline, they are equal and ordered as these instants.
+trait Timeline {
+  /// Seconds and nanoseconds of the instant, in UTC when the value has a timezone.
+  fn instant(&self) -> (i64, u32);
+
+  fn has_timezone(&self) -> bool;
+}
+
+impl Timeline for Date {
+  fn instant(&self) -> (i64, u32) {
+    DateTime::new(*self, Time::default())
+      .with_timezone(self.timezone)
+      .instant()
+  }
+
+  fn has_timezone(&self) -> bool {
+    self.timezone.is_some()
+  }
+}
+
+impl Timeline for Time {
+  fn instant(&self) -> (i64, u32) {
+    let seconds =
+      i64::from(self.hour) * 3600 + i64::from(self.minute) * 60 + i64::from(self.second);
+    (seconds - timezone_seconds(self.timezone), self.nanosecond)
+  }
+
+  fn has_timezone(&self) -> bool {
+    self.timezone.is_some()
+  }
+}
+
+impl Timeline for DateTime {
+  fn instant(&self) -> (i64, u32) {
+    let date = &self.date;
+    let time = &self.time;
+    let seconds = days_from_civil(date.year, date.month, date.day) * SECONDS_PER_DAY as i64
+      + i64::from(time.hour) * 3600
+      + i64::from(time.minute) * 60
+      + i64::from(time.second);
+    (seconds - timezone_seconds(self.timezone), time.nanosecond)
+  }
+
+  fn has_timezone(&self) -> bool {
+    self.timezone.is_some()
+  }
+}
+
+macro_rules! implement_timeline_comparison {
+  ($($type:ident),*) => {
+    $(
+      /// The values with a timezone and the values without one are never equal.
+      impl PartialEq for $type {
+        fn eq(&self, other: &Self) -> bool {
+          self.has_timezone() == other.has_timezone() && self.instant() == other.instant()
+        }
+      }
+
+      impl Eq for $type {}
+
+      impl std::hash::Hash for $type {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+          self.has_timezone().hash(state);
+          self.instant().hash(state);
+        }
+      }
+
+      impl PartialOrd for $type {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+          timeline_cmp(self, other)
+        }
+      }
+    )*
+  };
+}
+
+implement_timeline_comparison!(Date, Time, DateTime);
+
+/// Durations are ordered when they differ only by their months, or only by their seconds,
+/// as a month does not have a fixed number of seconds.
+impl PartialOrd for Duration {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    let signed = |duration: &Duration| {
+      let value = (
+        i128::from(duration.months),
+        i128::from(duration.seconds),
+        i128::from(duration.nanoseconds),
+      );
+      if duration.negative {
+        (-value.0, -value.1, -value.2)
+      } else {
+        value
+      }
+    };
+
+    let (months, seconds, nanoseconds) = signed(self);
+    let (other_months, other_seconds, other_nanoseconds) = signed(other);
+
+    if months == other_months {
+      Some((seconds, nanoseconds).cmp(&(other_seconds, other_nanoseconds)))
+    } else if seconds == other_seconds && nanoseconds == other_nanoseconds {
+      Some(months.cmp(&other_months))
+    } else {
+      None
+    }
+  }
+}
+
+/// The recurring values are ordered when they have the same timezone.
+impl PartialOrd for GYear {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    (self.timezone == other.timezone).then(|| self.year.cmp(&other.year))
+  }
+}
+
+impl PartialOrd for GYearMonth {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    (self.timezone == other.timezone)
+      .then(|| (self.year, self.month).cmp(&(other.year, other.month)))
+  }
+}
+
+impl PartialOrd for GMonthDay {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    (self.timezone == other.timezone).then(|| (self.month, self.day).cmp(&(other.month, other.day)))
+  }
+}
+
+impl PartialOrd for GDay {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    (self.timezone == other.timezone).then(|| self.day.cmp(&other.day))
+  }
+}
+
+impl PartialOrd for GMonth {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    (self.timezone == other.timezone).then(|| self.month.cmp(&other.month))
+  }
+}
+
+implement_text_content!(Timezone);
+implement_text_content!(Date);
+implement_text_content!(Time);
+implement_text_content!(DateTime);
+implement_text_content!(Duration);
+implement_text_content!(GYear);
+implement_text_content!(GYearMonth);
+implement_text_content!(GMonthDay);
+implement_text_content!(GDay);
+implement_text_content!(GMonth);
+
+/// Reader of a lexical value, the surrounding whitespaces are ignored (`whiteSpace` is `collapse`).
+struct Lexer<'a> {
+  kind: &'static str,
+  value: &'a str,
+  rest: &'a str,
+}
+
+impl<'a> Lexer<'a> {
+  fn new(kind: &'static str, value: &'a str) -> Self {
+    Lexer {
+      kind,
+      value,
+      rest: value.trim(),
+    }
+  }
+
+  fn error(&self, reason: &str) -> String {
+    format!("invalid {} {:?}: {}", self.kind, self.value, reason)
+  }
+
+  fn end(&self) -> Result<(), String> {
+    if self.rest.is_empty() {
+      Ok(())
+    } else {
+      Err(self.error(&format!("unexpected {:?}", self.rest)))
+    }
+  }
+
+  fn expect(&mut self, expected: char) -> Result<(), String> {
+    match self.rest.strip_prefix(expected) {
+      Some(rest) => {
+        self.rest = rest;
+        Ok(())
+      }
+      None => Err(self.error(&format!("expected {expected:?}"))),
+    }
+  }
+
+  fn take_digits(&mut self) -> &'a str {
+    let length = self
+      .rest
+      .find(|c: char| !c.is_ascii_digit())
+      .unwrap_or(self.rest.len());
+    let (digits, rest) = self.rest.split_at(length);
+    self.rest = rest;
+    digits
+  }
+
+  fn digits(&mut self, count: usize) -> Result<u32, String> {
+    let digits = self.take_digits();
+    if digits.len() != count {
+      return Err(self.error(&format!("expected {count} digits")));
+    }
+
+    digits
+      .parse()
+      .map_err(|_| self.error(&format!("expected {count} digits")))
+  }
+
+  /// At least 4 digits, without leading zero when there are more, and an optional minus sign.
+  fn year(&mut self) -> Result<i32, String> {
+    let negative = self.rest.starts_with('-');
+    if negative {
+      self.rest = &self.rest[1..];
+    }
+
+    let digits = self.take_digits();
+    if digits.len() < 4 || (digits.len() > 4 && digits.starts_with('0')) {
+      return Err(self.error("the year has at least 4 digits, without leading zero when more"));
+    }
+
+    let year: i32 = digits
+      .parse()
+      .map_err(|_| self.error("year is out of range"))?;
+    Ok(if negative { -year } else { year })
+  }
+
+  fn date(&mut self) -> Result<Date, String> {
+    let year = self.year()?;
+    self.expect('-')?;
+    let month = self.digits(2)? as u8;
+    self.expect('-')?;
+    let day = self.digits(2)? as u8;
+
+    Date::new(year, month, day).map_err(|reason| self.error(&reason))
+  }
+
+  /// The time, and whether it is the end of the day `24:00:00`, read as `00:00:00`.
+  fn time(&mut self) -> Result<(Time, bool), String> {
+    let hour = self.digits(2)? as u8;
+    self.expect(':')?;
+    let minute = self.digits(2)? as u8;
+    self.expect(':')?;
+    let second = self.digits(2)? as u8;
+
+    let nanosecond = if self.rest.starts_with('.') {
+      self.rest = &self.rest[1..];
+      let fraction = self.take_digits();
+      parse_fraction(fraction).ok_or_else(|| self.error("invalid fraction of seconds"))?
+    } else {
+      0
+    };
+
+    if hour == 24 && minute == 0 && second == 0 && nanosecond == 0 {
+      return Ok((Time::default(), true));
+    }
+
+    let time = Time::new(hour, minute, second, nanosecond).map_err(|reason| self.error(&reason))?;
+    Ok((time, false))
+  }
+
+  fn timezone(&mut self) -> Result<Option<Timezone>, String> {
+    if let Some(rest) = self.rest.strip_prefix('Z') {
+      self.rest = rest;
+      return Ok(Some(Timezone::UTC));
+    }
+
+    let negative = match self.rest.chars().next() {
+      Some('+') => false,
+      Some('-') => true,
+      _ => return Ok(None),
+    };
+    self.rest = &self.rest[1..];
+
+    let hours = self.digits(2)?;
+    self.expect(':')?;
+    let minutes = self.digits(2)?;
+    if minutes > 59 || hours > 14 || (hours == 14 && minutes != 0) {
+      return Err(self.error("timezone is out of -14:00..+14:00"));
+    }
+
+    let offset = (hours * 60 + minutes) as i16;
+    Timezone::new(if negative { -offset } else { offset })
+      .map(Some)
+      .map_err(|reason| self.error(&reason))
+  }
+}
+
+/// The components of a part of a duration, ie. `1Y2M` is `[("1", 'Y'), ("2", 'M')]`.
+/// Each designator is present at most once, in order.
+fn duration_components<'a>(
+  part: &'a str,
+  designators: &str,
+) -> Result<Vec<(&'a str, char)>, String> {
+  let mut components = vec![];
+  let mut rest = part;
+  let mut designators = designators.chars();
+
+  while !rest.is_empty() {
+    let length = rest
+      .find(|c: char| !c.is_ascii_digit() && c != '.')
+      .ok_or_else(|| format!("missing designator after {rest:?}"))?;
+    let (value, tail) = rest.split_at(length);
+    let designator = tail.chars().next().unwrap_or_default();
+
+    if value.is_empty() || value.starts_with('.') || value.ends_with('.') {
+      return Err(format!("missing number before {designator:?}"));
+    }
+    if !designators.any(|expected| expected == designator) {
+      return Err(format!("unexpected designator {designator:?}"));
+    }
+
+    components.push((value, designator));
+    rest = &tail[designator.len_utf8()..];
+  }
+
+  Ok(components)
+}
+
+/// Nanoseconds of the digits of a fraction of seconds, the digits after the ninth are truncated.
+fn parse_fraction(fraction: &str) -> Option<u32> {
+  if fraction.is_empty() || !fraction.bytes().all(|c| c.is_ascii_digit()) {
+    return None;
+  }
+
+  let digits: String = fraction
+    .chars()
+    .chain("000000000".chars())
+    .take(9)
+    .collect();
+  digits.parse().ok()
+}
+
+fn check_month(month: u8) -> Result<(), String> {
+  if (1..=12).contains(&month) {
+    Ok(())
+  } else {
+    Err(format!("month {month} is out of range"))
+  }
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+  match month {
+    2 if is_leap_year(year) => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  }
+}
+
+/// Leap years of the proleptic Gregorian calendar, the year `0000` (1 BCE) is a leap year.
+fn is_leap_year(year: i32) -> bool {
+  year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+}
+
+/// Order of values on the timeline. A value without timezone is ordered against a value with
+/// a timezone only when they differ by more than 14 hours, as its timezone can be any offset
+/// from `-14:00` to `+14:00`.
+fn timeline_cmp<T: Timeline>(value: &T, other: &T) -> Option<Ordering> {
+  const MAX_OFFSET: i64 = 14 * 3600;
+
+  let instant = value.instant();
+  let other_instant = other.instant();
+
+  match (value.has_timezone(), other.has_timezone()) {
+    (true, false) => {
+      if instant < (other_instant.0 - MAX_OFFSET, other_instant.1) {
+        Some(Ordering::Less)
+      } else if instant > (other_instant.0 + MAX_OFFSET, other_instant.1) {
+        Some(Ordering::Greater)
+      } else {
+        None
+      }
+    }
+    (false, true) => timeline_cmp(other, value).map(Ordering::reverse),
+    _ => Some(instant.cmp(&other_instant)),
+  }
+}
+
+fn timezone_seconds(timezone: Option<Timezone>) -> i64 {
+  timezone
+    .map(|timezone| i64::from(timezone.offset_minutes) * 60)
+    .unwrap_or_default()
+}
+
+/// Number of days since 1970-01-01 of a date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+  let year = i64::from(year) - i64::from(month <= 2);
+  let era = year.div_euclid(400);
+  let year_of_era = year.rem_euclid(400);
+  let month = i64::from(month);
+  let day_of_year =
+    (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+  era * 146_097 + day_of_era - 719_468
+}
+
+fn write_year(f: &mut fmt::Formatter<'_>, year: i32) -> fmt::Result {
+  if year < 0 {
+    f.write_str("-")?;
+  }
+  write!(f, "{:04}", year.unsigned_abs())
+}
+
+fn write_fraction(f: &mut fmt::Formatter<'_>, nanoseconds: u32) -> fmt::Result {
+  if nanoseconds == 0 {
+    return Ok(());
+  }
+
+  let fraction = format!("{nanoseconds:09}");
+  write!(f, ".{}", fraction.trim_end_matches('0'))
+}
+
+fn write_timezone(f: &mut fmt::Formatter<'_>, timezone: Option<Timezone>) -> fmt::Result {
+  match timezone {
+    Some(timezone) => write!(f, "{timezone}"),
+    None => Ok(()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn canonical<T: FromStr<Err = String> + fmt::Display>(value: &str) -> Result<String, String> {
+    value.parse::<T>().map(|value| value.to_string())
+  }
+
+  #[test]
+  fn date() {
+    assert_eq!(
+      canonical::<Date>("2024-02-29"),
+      Ok("2024-02-29".to_string())
+    );
+    assert_eq!(
+      canonical::<Date>(" 2024-02-29Z "),
+      Ok("2024-02-29Z".to_string())
+    );
+    assert_eq!(
+      canonical::<Date>("-12345-01-31+14:00"),
+      Ok("-12345-01-31+14:00".to_string())
+    );
+    assert_eq!(
+      canonical::<Date>("2023-02-29"),
+      Err(r#"invalid date "2023-02-29": day 29 is out of range for 2023-02"#.to_string())
+    );
+    assert!("24-02-01".parse::<Date>().is_err());
+    assert!("02024-02-01".parse::<Date>().is_err());
+    assert!("2024-2-01".parse::<Date>().is_err());
+    assert!("2024-02-01+15:00".parse::<Date>().is_err());
+    assert!("2024-02-01T".parse::<Date>().is_err());
+
+    let date = "2024-02-29-05:30".parse::<Date>().unwrap();
+    assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+    assert_eq!(date.timezone(), Some(Timezone::new(-330).unwrap()));
+  }
+
+  #[test]
+  fn time() {
+    assert_eq!(canonical::<Time>("13:20:00"), Ok("13:20:00".to_string()));
+    assert_eq!(
+      canonical::<Time>("13:20:00.500+00:00"),
+      Ok("13:20:00.5Z".to_string())
+    );
+    assert_eq!(canonical::<Time>("24:00:00"), Ok("00:00:00".to_string()));
+    assert_eq!(
+      canonical::<Time>("00:00:00.1234567891"),
+      Ok("00:00:00.123456789".to_string())
+    );
+    assert!("24:00:01".parse::<Time>().is_err());
+    assert!("13:60:00".parse::<Time>().is_err());
+    assert!("13:20:00.".parse::<Time>().is_err());
+    assert!("13:20".parse::<Time>().is_err());
+  }
+
+  #[test]
+  fn date_time() {
+    assert_eq!(
+      canonical::<DateTime>("2024-02-29T13:20:00.250-05:00"),
+      Ok("2024-02-29T13:20:00.25-05:00".to_string())
+    );
+    assert_eq!(
+      canonical::<DateTime>("2024-12-31T24:00:00Z"),
+      Ok("2025-01-01T00:00:00Z".to_string())
+    );
+    assert!("2024-02-29 13:20:00".parse::<DateTime>().is_err());
+    assert!("2024-02-29".parse::<DateTime>().is_err());
+
+    let date_time = "2024-02-29T13:20:00Z".parse::<DateTime>().unwrap();
+    assert_eq!(date_time.date().to_string(), "2024-02-29Z");
+    assert_eq!(date_time.time().to_string(), "13:20:00Z");
+    assert_eq!(DateTime::default().to_string(), "1970-01-01T00:00:00");
+  }
+
+  #[test]
+  fn duration() {
+    assert_eq!(
+      canonical::<Duration>("P1Y2M3DT4H5M6.7S"),
+      Ok("P1Y2M3DT4H5M6.7S".to_string())
+    );
+    assert_eq!(canonical::<Duration>("P14M"), Ok("P1Y2M".to_string()));
+    assert_eq!(canonical::<Duration>("PT36H"), Ok("P1DT12H".to_string()));
+    assert_eq!(canonical::<Duration>("-PT90S"), Ok("-PT1M30S".to_string()));
+    assert_eq!(canonical::<Duration>("P0D"), Ok("PT0S".to_string()));
+    assert_eq!(canonical::<Duration>("-P0Y"), Ok("PT0S".to_string()));
+    assert_eq!(canonical::<Duration>("PT0.5S"), Ok("PT0.5S".to_string()));
+
+    for invalid in [
+      "P", "PT", "P1YT", "1Y", "P1M1Y", "P1.5Y", "PT1.5M", "P-1Y", "P1S", "PT1D", "P1Y1Y",
+    ] {
+      assert!(invalid.parse::<Duration>().is_err(), "{}", invalid);
+    }
+
+    let duration = "P1Y14M40DT25H".parse::<Duration>().unwrap();
+    assert_eq!(duration.total_months(), 26);
+    assert_eq!((duration.years(), duration.months()), (2, 2));
+    assert_eq!((duration.days(), duration.hours()), (41, 1));
+
+    assert_eq!(
+      std::time::Duration::try_from("PT1M0.5S".parse::<Duration>().unwrap()),
+      Ok(std::time::Duration::from_millis(60_500))
+    );
+    assert!(std::time::Duration::try_from("P1M".parse::<Duration>().unwrap()).is_err());
+  }
+
+  #[test]
+  fn restricted_types() {
+    assert_eq!(
+      canonical::<DateTimeStamp>("2024-02-29T13:20:00+01:00"),
+      Ok("2024-02-29T13:20:00+01:00".to_string())
+    );
+    assert_eq!(
+      canonical::<DateTimeStamp>("2024-02-29T13:20:00"),
+      Err(r#"invalid dateTimeStamp "2024-02-29T13:20:00": missing timezone"#.to_string())
+    );
+    assert_eq!(DateTimeStamp::default().to_string(), "1970-01-01T00:00:00Z");
+
+    assert_eq!(
+      canonical::<DayTimeDuration>("PT36H"),
+      Ok("P1DT12H".to_string())
+    );
+    assert_eq!(
+      canonical::<DayTimeDuration>("P1Y"),
+      Err(r#"invalid dayTimeDuration "P1Y": unexpected years or months"#.to_string())
+    );
+    assert!("P1MT1H".parse::<DayTimeDuration>().is_err());
+
+    assert_eq!(
+      canonical::<YearMonthDuration>("-P18M"),
+      Ok("-P1Y6M".to_string())
+    );
+    assert!("P1D".parse::<YearMonthDuration>().is_err());
+    assert!("P1YT0.5S".parse::<YearMonthDuration>().is_err());
+    assert_eq!(
+      YearMonthDuration::default().value(),
+      &"P0Y".parse::<Duration>().unwrap()
+    );
+  }
+
+  #[test]
+  fn gregorian() {
+    assert_eq!(
+      canonical::<GYearMonth>("2024-02Z"),
+      Ok("2024-02Z".to_string())
+    );
+    assert_eq!(canonical::<GMonthDay>("--02-29"), Ok("--02-29".to_string()));
+    assert_eq!(
+      canonical::<GDay>("---15+01:00"),
+      Ok("---15+01:00".to_string())
+    );
+    assert_eq!(canonical::<GMonth>("--12"), Ok("--12".to_string()));
+    for year in ["2024Z", "2024+02:00", "-0044", "12345"] {
+      assert_eq!(canonical::<GYear>(year), Ok(year.to_string()));
+    }
+    assert_eq!(canonical::<GYear>("2024+00:00"), Ok("2024Z".to_string()));
+
+    assert!("2024-13".parse::<GYearMonth>().is_err());
+    assert!("--02-30".parse::<GMonthDay>().is_err());
+    assert!("---32".parse::<GDay>().is_err());
+    assert!("--00".parse::<GMonth>().is_err());
+    assert!("--12--".parse::<GMonth>().is_err());
+    assert!("024".parse::<GYear>().is_err());
+    assert!("02024".parse::<GYear>().is_err());
+    assert!("2024-01".parse::<GYear>().is_err());
+  }
+
+  #[test]
+  fn ordering() {
+    let parse = |value: &str| value.parse::<DateTime>().unwrap();
+    assert!(parse("2024-02-29T13:00:00+01:00") < parse("2024-02-29T12:30:00Z"));
+    assert!(parse("2024-12-31T23:00:00") > parse("2024-01-01T00:00:00"));
+    assert_eq!(
+      parse("2024-02-29T13:00:00Z").partial_cmp(&parse("2024-02-29T13:00:00")),
+      None
+    );
+    assert!(parse("2024-02-29T13:00:00Z") < parse("2024-03-01T03:00:01"));
+    assert!(parse("2024-03-01T04:00:01Z") > parse("2024-02-29T14:00:00"));
+    assert!(parse("2024-02-29T23:00:00") > parse("2024-02-29T08:59:59Z"));
+    assert_eq!(
+      parse("2024-02-29T13:00:00Z").partial_cmp(&parse("2024-02-29T03:00:00")),
+      None
+    );
+
+    assert!("1969-12-31".parse::<Date>().unwrap() < "1970-01-01".parse::<Date>().unwrap());
+    assert!("10:00:00.1".parse::<Time>().unwrap() > "10:00:00".parse::<Time>().unwrap());
+
+    let parse = |value: &str| value.parse::<Duration>().unwrap();
+    assert!(parse("PT1H") > parse("PT59M"));
+    assert!(parse("-PT1H") < parse("PT0S"));
+    assert!(parse("P1Y") > parse("P11M"));
+    assert_eq!(parse("P1M").partial_cmp(&parse("P30D")), None);
+  }
+
+  #[test]
+  fn same_instant() {
+    use std::collections::HashSet;
+
+    let parse = |value: &str| value.parse::<DateTime>().unwrap();
+    let utc = parse("2024-01-01T10:00:00Z");
+    let offset = parse("2024-01-01T11:00:00+01:00");
+    assert_eq!(utc, offset);
+    assert_eq!(utc.partial_cmp(&offset), Some(Ordering::Equal));
+    assert_eq!(
+      parse("2024-01-01T00:30:00+01:00"),
+      parse("2023-12-31T23:30:00Z")
+    );
+    assert_ne!(utc, parse("2024-01-01T10:00:00"));
+    assert_eq!(
+      parse("2024-01-01T10:00:00"),
+      parse("2024-01-01T10:00:00.000")
+    );
+    assert_eq!(
+      [utc, offset, parse("2024-01-01T10:00:00")]
+        .iter()
+        .collect::<HashSet<_>>()
+        .len(),
+      2
+    );
+
+    let parse = |value: &str| value.parse::<Time>().unwrap();
+    assert_eq!(parse("10:00:00Z"), parse("11:00:00+01:00"));
+    assert_eq!(
+      parse("10:00:00Z").partial_cmp(&parse("11:00:00+01:00")),
+      Some(Ordering::Equal)
+    );
+    assert_ne!(parse("10:00:00Z"), parse("10:00:00"));
+
+    let parse = |value: &str| value.parse::<Date>().unwrap();
+    assert_eq!(parse("2024-01-02+14:00"), parse("2024-01-01-10:00"));
+    assert_eq!(
+      parse("2024-01-02+14:00").partial_cmp(&parse("2024-01-01-10:00")),
+      Some(Ordering::Equal)
+    );
+    assert_ne!(parse("2024-01-02Z"), parse("2024-01-02"));
+  }
+
+  #[test]
+  fn timezone() {
+    assert_eq!(canonical::<Timezone>("+00:00"), Ok("Z".to_string()));
+    assert_eq!(canonical::<Timezone>("-14:00"), Ok("-14:00".to_string()));
+    assert!("+14:30".parse::<Timezone>().is_err());
+    assert!("01:00".parse::<Timezone>().is_err());
+    assert!(Timezone::new(15 * 60).is_err());
+  }
+}
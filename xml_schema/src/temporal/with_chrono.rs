@@ -0,0 +1,173 @@
+//! Conversions with the `chrono` types.
+
+use super::{Date, DateTime, Duration, Time, Timezone};
+use chrono::{Datelike, Offset, TimeZone, Timelike};
+use std::convert::TryFrom;
+
+impl From<chrono::FixedOffset> for Timezone {
+  /// The seconds of the offset are truncated.
+  fn from(offset: chrono::FixedOffset) -> Self {
+    Timezone {
+      offset_minutes: (offset.local_minus_utc() / 60) as i16,
+    }
+  }
+}
+
+impl From<Timezone> for chrono::FixedOffset {
+  fn from(timezone: Timezone) -> Self {
+    chrono::FixedOffset::east_opt(i32::from(timezone.offset_minutes) * 60)
+      .expect("timezone in -14:00..+14:00")
+  }
+}
+
+impl From<chrono::NaiveDate> for Date {
+  fn from(date: chrono::NaiveDate) -> Self {
+    Date {
+      year: date.year(),
+      month: date.month() as u8,
+      day: date.day() as u8,
+      timezone: None,
+    }
+  }
+}
+
+impl TryFrom<Date> for chrono::NaiveDate {
+  type Error = String;
+
+  /// The timezone of the date is ignored.
+  fn try_from(date: Date) -> Result<Self, Self::Error> {
+    chrono::NaiveDate::from_ymd_opt(date.year, u32::from(date.month), u32::from(date.day))
+      .ok_or_else(|| format!("date {date} is out of the chrono range"))
+  }
+}
+
+impl From<chrono::NaiveTime> for Time {
+  /// A leap second is read as the last nanosecond of the previous second.
+  fn from(time: chrono::NaiveTime) -> Self {
+    Time {
+      hour: time.hour() as u8,
+      minute: time.minute() as u8,
+      second: time.second() as u8,
+      nanosecond: time.nanosecond().min(999_999_999),
+      timezone: None,
+    }
+  }
+}
+
+impl From<Time> for chrono::NaiveTime {
+  /// The timezone of the time is ignored.
+  fn from(time: Time) -> Self {
+    chrono::NaiveTime::from_hms_nano_opt(
+      u32::from(time.hour),
+      u32::from(time.minute),
+      u32::from(time.second),
+      time.nanosecond,
+    )
+    .expect("valid time")
+  }
+}
+
+impl From<chrono::NaiveDateTime> for DateTime {
+  fn from(date_time: chrono::NaiveDateTime) -> Self {
+    DateTime::new(date_time.date().into(), date_time.time().into())
+  }
+}
+
+impl TryFrom<DateTime> for chrono::NaiveDateTime {
+  type Error = String;
+
+  /// The local date and time, the timezone is ignored.
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    Ok(chrono::NaiveDateTime::new(
+      chrono::NaiveDate::try_from(date_time.date)?,
+      date_time.time.into(),
+    ))
+  }
+}
+
+impl<Tz: TimeZone> From<chrono::DateTime<Tz>> for DateTime {
+  fn from(date_time: chrono::DateTime<Tz>) -> Self {
+    let timezone = date_time.offset().fix().into();
+    DateTime::from(date_time.naive_local()).with_timezone(Some(timezone))
+  }
+}
+
+impl TryFrom<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+  type Error = String;
+
+  /// A date time without timezone is rejected.
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    let timezone = date_time
+      .timezone
+      .ok_or_else(|| format!("date time {date_time} has no timezone"))?;
+    let offset = chrono::FixedOffset::from(timezone);
+
+    offset
+      .from_local_datetime(&chrono::NaiveDateTime::try_from(date_time)?)
+      .single()
+      .ok_or_else(|| format!("date time {date_time} is out of the chrono range"))
+  }
+}
+
+impl TryFrom<chrono::Duration> for Duration {
+  type Error = String;
+
+  fn try_from(duration: chrono::Duration) -> Result<Self, Self::Error> {
+    let negative = duration < chrono::Duration::zero();
+    let duration = if negative { -duration } else { duration };
+    let duration = duration.to_std().map_err(|e| e.to_string())?;
+
+    Duration::new(negative, 0, duration.as_secs(), duration.subsec_nanos())
+  }
+}
+
+impl TryFrom<Duration> for chrono::Duration {
+  type Error = String;
+
+  /// Only a duration of days, hours, minutes and seconds is converted, as the months have no fixed length.
+  fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+    if duration.months != 0 {
+      return Err(format!("duration {duration} has years or months"));
+    }
+
+    let value = chrono::Duration::from_std(std::time::Duration::new(
+      duration.seconds,
+      duration.nanoseconds,
+    ))
+    .map_err(|e| e.to_string())?;
+
+    Ok(if duration.negative { -value } else { value })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn chrono_conversions() {
+    let date_time: DateTime = "2024-02-29T13:20:00.5+01:00".parse().unwrap();
+    let chrono_date_time = chrono::DateTime::<chrono::FixedOffset>::try_from(date_time).unwrap();
+    assert_eq!(chrono_date_time.offset().local_minus_utc(), 3600);
+    assert_eq!(chrono_date_time.hour(), 13);
+    assert_eq!(chrono_date_time.timestamp_subsec_millis(), 500);
+    assert_eq!(DateTime::from(chrono_date_time), date_time);
+
+    let date_time: DateTime = "2024-02-29T13:20:00".parse().unwrap();
+    assert!(chrono::DateTime::<chrono::FixedOffset>::try_from(date_time).is_err());
+    let naive = chrono::NaiveDateTime::try_from(date_time).unwrap();
+    assert_eq!(DateTime::from(naive), date_time);
+
+    let date: Date = "2024-02-29".parse().unwrap();
+    assert_eq!(
+      chrono::NaiveDate::try_from(date),
+      Ok(chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+    );
+
+    let duration: Duration = "-P1DT0.5S".parse().unwrap();
+    let chrono_duration = chrono::Duration::try_from(duration).unwrap();
+    assert_eq!(chrono_duration.num_milliseconds(), -86_400_500);
+    assert_eq!(Duration::try_from(chrono_duration), Ok(duration));
+    assert!(chrono::Duration::try_from("P1M".parse::<Duration>().unwrap()).is_err());
+  }
+}
@@ -0,0 +1,153 @@
+//! Conversions with the `time` types.
+
+use super::{Date, DateTime, Duration, Time, Timezone};
+use std::convert::TryFrom;
+
+impl From<time::UtcOffset> for Timezone {
+  /// The seconds of the offset are truncated.
+  fn from(offset: time::UtcOffset) -> Self {
+    Timezone {
+      offset_minutes: (offset.whole_seconds() / 60) as i16,
+    }
+  }
+}
+
+impl From<Timezone> for time::UtcOffset {
+  fn from(timezone: Timezone) -> Self {
+    time::UtcOffset::from_whole_seconds(i32::from(timezone.offset_minutes) * 60)
+      .expect("timezone in -14:00..+14:00")
+  }
+}
+
+impl From<time::Date> for Date {
+  fn from(date: time::Date) -> Self {
+    Date {
+      year: date.year(),
+      month: u8::from(date.month()),
+      day: date.day(),
+      timezone: None,
+    }
+  }
+}
+
+impl TryFrom<Date> for time::Date {
+  type Error = String;
+
+  /// The timezone of the date is ignored.
+  fn try_from(date: Date) -> Result<Self, Self::Error> {
+    let month = time::Month::try_from(date.month).map_err(|e| e.to_string())?;
+    time::Date::from_calendar_date(date.year, month, date.day).map_err(|e| e.to_string())
+  }
+}
+
+impl From<time::Time> for Time {
+  fn from(time: time::Time) -> Self {
+    Time {
+      hour: time.hour(),
+      minute: time.minute(),
+      second: time.second(),
+      nanosecond: time.nanosecond(),
+      timezone: None,
+    }
+  }
+}
+
+impl From<Time> for time::Time {
+  /// The timezone of the time is ignored.
+  fn from(time: Time) -> Self {
+    time::Time::from_hms_nano(time.hour, time.minute, time.second, time.nanosecond)
+      .expect("valid time")
+  }
+}
+
+impl From<time::PrimitiveDateTime> for DateTime {
+  fn from(date_time: time::PrimitiveDateTime) -> Self {
+    DateTime::new(date_time.date().into(), date_time.time().into())
+  }
+}
+
+impl TryFrom<DateTime> for time::PrimitiveDateTime {
+  type Error = String;
+
+  /// The local date and time, the timezone is ignored.
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    Ok(time::PrimitiveDateTime::new(
+      time::Date::try_from(date_time.date)?,
+      date_time.time.into(),
+    ))
+  }
+}
+
+impl From<time::OffsetDateTime> for DateTime {
+  fn from(date_time: time::OffsetDateTime) -> Self {
+    DateTime::new(date_time.date().into(), date_time.time().into())
+      .with_timezone(Some(date_time.offset().into()))
+  }
+}
+
+impl TryFrom<DateTime> for time::OffsetDateTime {
+  type Error = String;
+
+  /// A date time without timezone is rejected.
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    let timezone = date_time
+      .timezone
+      .ok_or_else(|| format!("date time {date_time} has no timezone"))?;
+
+    Ok(time::PrimitiveDateTime::try_from(date_time)?.assume_offset(timezone.into()))
+  }
+}
+
+impl TryFrom<time::Duration> for Duration {
+  type Error = String;
+
+  fn try_from(duration: time::Duration) -> Result<Self, Self::Error> {
+    Duration::new(
+      duration.is_negative(),
+      0,
+      duration.whole_seconds().unsigned_abs(),
+      duration.subsec_nanoseconds().unsigned_abs(),
+    )
+  }
+}
+
+impl TryFrom<Duration> for time::Duration {
+  type Error = String;
+
+  /// Only a duration of days, hours, minutes and seconds is converted, as the months have no fixed length.
+  fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+    if duration.months != 0 {
+      return Err(format!("duration {duration} has years or months"));
+    }
+
+    let seconds = i64::try_from(duration.seconds).map_err(|e| e.to_string())?;
+    let value = time::Duration::new(seconds, duration.nanoseconds as i32);
+
+    Ok(if duration.negative { -value } else { value })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn time_conversions() {
+    let date_time: DateTime = "2024-02-29T13:20:00.5-05:00".parse().unwrap();
+    let offset_date_time = time::OffsetDateTime::try_from(date_time).unwrap();
+    assert_eq!(offset_date_time.offset().whole_minutes(), -300);
+    assert_eq!(offset_date_time.millisecond(), 500);
+    assert_eq!(DateTime::from(offset_date_time), date_time);
+
+    let date_time: DateTime = "2024-02-29T13:20:00".parse().unwrap();
+    assert!(time::OffsetDateTime::try_from(date_time).is_err());
+    let primitive = time::PrimitiveDateTime::try_from(date_time).unwrap();
+    assert_eq!(DateTime::from(primitive), date_time);
+
+    let duration: Duration = "-PT1M0.5S".parse().unwrap();
+    let time_duration = time::Duration::try_from(duration).unwrap();
+    assert_eq!(time_duration.whole_milliseconds(), -60_500);
+    assert_eq!(Duration::try_from(time_duration), Ok(duration));
+    assert!(time::Duration::try_from("P1Y".parse::<Duration>().unwrap()).is_err());
+  }
+}
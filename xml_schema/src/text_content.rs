@@ -0,0 +1,79 @@
+/// (De)serialization of a type from its text content, through `FromStr` and `Display`,
+/// as the derive does for the generated simple types. A generic type lists its parameters,
+/// ie. `implement_text_content!(List<T>)`.
+macro_rules! implement_text_content {
+  ($type:ident $(<$($parameter:ident),*>)?) => {
+    impl$(<$($parameter),*>)? hifa_yaserde::YaDeserialize for $type$(<$($parameter),*>)?
+    where
+      Self: std::str::FromStr<Err = String>,
+    {
+      fn deserialize<R: std::io::Read>(
+        reader: &mut hifa_yaserde::de::Deserializer<R>,
+      ) -> Result<Self, String> {
+        if matches!(reader.peek()?, xml::reader::XmlEvent::StartElement { .. }) {
+          reader.next_event()?;
+        }
+
+        let content = match reader.peek()?.clone() {
+          xml::reader::XmlEvent::Characters(content) => {
+            reader.next_event()?;
+            content
+          }
+          _ => String::new(),
+        };
+
+        content.parse()
+      }
+    }
+
+    impl$(<$($parameter),*>)? hifa_yaserde::YaSerialize for $type$(<$($parameter),*>)?
+    where
+      Self: std::fmt::Display,
+    {
+      fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut hifa_yaserde::ser::Serializer<W>,
+      ) -> Result<(), String> {
+        let skip = writer.skip_start_end();
+
+        if !skip {
+          let name = writer
+            .get_start_event_name()
+            .unwrap_or_else(|| stringify!($type).to_string());
+          writer
+            .write(xml::writer::XmlEvent::start_element(name.as_str()))
+            .map_err(|e| e.to_string())?;
+        }
+
+        let content = self.to_string();
+        writer
+          .write(xml::writer::XmlEvent::characters(&content))
+          .map_err(|e| e.to_string())?;
+
+        if !skip {
+          writer
+            .write(xml::writer::XmlEvent::end_element())
+            .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+      }
+
+      fn serialize_attributes(
+        &self,
+        source_attributes: Vec<xml::attribute::OwnedAttribute>,
+        source_namespace: xml::namespace::Namespace,
+      ) -> Result<
+        (
+          Vec<xml::attribute::OwnedAttribute>,
+          xml::namespace::Namespace,
+        ),
+        String,
+      > {
+        Ok((source_attributes, source_namespace))
+      }
+    }
+  };
+}
+
+pub(crate) use implement_text_content;
@@ -0,0 +1,68 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn all_any_order() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/all.xsd")]
+  struct AllSchema;
+
+  let xml_1 = r#"
+  <DateRange>
+    <tag>first</tag>
+    <end>20</end>
+    <tag>second</tag>
+    <begin>10</begin>
+  </DateRange>
+  "#;
+
+  let sample_1: xml_schema_types::DateRange = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::DateRange {
+    begin: 10,
+    end: 20,
+    label: None,
+    tag_list: vec!["first".to_string(), "second".to_string()],
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><DateRange><begin>10</begin><end>20</end><tag>first</tag><tag>second</tag></DateRange>"#
+  );
+}
+
+#[test]
+fn all_in_integrated_complex_type() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/all.xsd")]
+  struct AllSchema;
+
+  use xml_schema_types::{Frame, Picture};
+
+  let xml_1 = r#"<Picture><title>Sunset</title><frame><height>20</height><width>30</width></frame></Picture>"#;
+
+  let sample_1: Picture = from_str(xml_1).unwrap();
+
+  let model = Picture {
+    title: "Sunset".to_string(),
+    frame: Frame {
+      width: 30,
+      height: 20,
+    },
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Picture><title>Sunset</title><frame><width>30</width><height>20</height></frame></Picture>"#
+  );
+  assert_eq!(from_str::<Picture>(&data).unwrap(), model);
+}
@@ -0,0 +1,43 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn attribute_group() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/attribute_group.xsd")]
+  struct AttributeGroupSchema;
+
+  use hifa_xml_schema::string::Token;
+  use xml_schema_types::{Div, Span};
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Div title="Notes" id="notes" lang="en"><span class="first" dir="rtl">One</span><span>Two</span></Div>"#;
+
+  let model = Div {
+    span_list: vec![
+      Span {
+        base: "One".to_string(),
+        id: None,
+        class: Some("first".to_string()),
+        lang: None,
+        dir: Some("rtl".to_string()),
+      },
+      Span {
+        base: "Two".to_string(),
+        ..Default::default()
+      },
+    ],
+    title: "Notes".to_string(),
+    id: Some(Token::from("notes")),
+    class: None,
+    lang: Some(Token::from("en")),
+    dir: None,
+  };
+
+  let sample: Div = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(data, xml_content);
+}
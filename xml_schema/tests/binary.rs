@@ -0,0 +1,50 @@
+use hifa_xml_schema::binary::{Base64Binary, HexBinary};
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn binary_data() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/binary.xsd")]
+  struct BinarySchema;
+
+  use xml_schema_types::{Attachment, Digest};
+
+  assert_eq!(
+    "0A0B0C".parse::<Digest>(),
+    Err("Digest: the length 3 of \"0A0B0C\" is not 4".to_string())
+  );
+
+  let xml_1 = r#"
+  <Attachment checksum="deadbeef">
+    <data>
+      SGVsbG8s
+      IHdvcmxk
+      IQ==
+    </data>
+  </Attachment>
+  "#;
+
+  let sample_1: Attachment = from_str(xml_1).unwrap();
+
+  let model = Attachment {
+    data: Base64Binary::from(b"Hello, world!".to_vec()),
+    checksum: Digest::new(HexBinary::from(vec![0xde, 0xad, 0xbe, 0xef])).unwrap(),
+    key: None,
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Attachment checksum="DEADBEEF"><data>SGVsbG8sIHdvcmxkIQ==</data></Attachment>"#
+  );
+
+  assert!(from_str::<Attachment>(
+    r#"<Attachment checksum="DEADBEEF"><data>SGVsbG8</data></Attachment>"#
+  )
+  .is_err());
+}
@@ -0,0 +1,76 @@
+use hifa_xml_schema::list::List;
+use hifa_xml_schema::numeric::NonPositiveI64;
+use hifa_xml_schema::qname::QName;
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn built_in_types() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/built_in.xsd")]
+  struct BuiltInSchema;
+
+  use xml_schema_types::Record;
+
+  let xml_1 = r#"
+  <Record id=" r1 " refs="r2
+    r3">
+    <name>record</name>
+    <kind xmlns:xs="http://www.w3.org/2001/XMLSchema">xs:string</kind>
+    <title>The   quick
+      brown	fox</title>
+    <summary>First	line
+second line</summary>
+    <keywords> alpha  beta	gamma </keywords>
+    <delay>PT36H</delay>
+    <created>2024-02-29T13:20:00Z</created>
+    <balance>-12</balance>
+  </Record>
+  "#;
+
+  let sample_1: Record = from_str(xml_1).unwrap();
+
+  let model = Record {
+    name: "record".into(),
+    kind: QName::new(
+      Some("http://www.w3.org/2001/XMLSchema"),
+      Some("xs"),
+      "string",
+    )
+    .unwrap(),
+    title: "The quick brown fox".into(),
+    summary: "First line second line".into(),
+    keywords: List::from(vec![
+      "alpha".to_string(),
+      "beta".to_string(),
+      "gamma".to_string(),
+    ]),
+    delay: "P1DT12H".parse().unwrap(),
+    created: "2024-02-29T13:20:00Z".parse().unwrap(),
+    balance: NonPositiveI64::new(-12).unwrap(),
+    note: None,
+    id: "r1".into(),
+    refs: Some(List::from(vec!["r2".to_string(), "r3".to_string()])),
+    entities: None,
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Record id="r1" refs="r2 r3"><name>record</name><kind xmlns:xs="http://www.w3.org/2001/XMLSchema">xs:string</kind><title>The quick brown fox</title><summary>First line second line</summary><keywords>alpha beta gamma</keywords><delay>P1DT12H</delay><created>2024-02-29T13:20:00Z</created><balance>-12</balance></Record>"#
+  );
+
+  let invalid = |delay: &str, created: &str| {
+    from_str::<Record>(&format!(
+      r#"<Record id="r1"><name>record</name><kind>a</kind><title/><summary/><keywords/><delay>{delay}</delay><created>{created}</created><balance>0</balance></Record>"#
+    ))
+    .unwrap_err()
+  };
+
+  assert!(invalid("P1M", "2024-02-29T13:20:00Z").contains("unexpected years or months"));
+  assert!(invalid("PT1H", "2024-02-29T13:20:00").contains("missing timezone"));
+}
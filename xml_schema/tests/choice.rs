@@ -0,0 +1,166 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn choice_single_and_optional() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/choice.xsd")]
+  struct ChoiceSchema;
+
+  let xml_1 = r#"
+  <Payment>
+    <Amount>12</Amount>
+    <Transfer iban="FR76" />
+    <Reference>42</Reference>
+  </Payment>
+  "#;
+
+  let sample_1: xml_schema_types::Payment = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Payment {
+    amount: 12,
+    choice: xml_schema_types::PaymentChoice::Transfer(xml_schema_types::Transfer {
+      iban: "FR76".to_string(),
+    }),
+    choice_2: Some(xml_schema_types::PaymentChoice2::Reference(42)),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Payment><Amount>12</Amount><Transfer iban="FR76" /><Reference>42</Reference></Payment>"#
+  );
+
+  let xml_2 = r#"<Payment><Amount>5</Amount><Card>1234</Card></Payment>"#;
+
+  let sample_2: xml_schema_types::Payment = from_str(xml_2).unwrap();
+
+  assert_eq!(
+    sample_2,
+    xml_schema_types::Payment {
+      amount: 5,
+      choice: xml_schema_types::PaymentChoice::Card("1234".to_string()),
+      choice_2: None,
+    }
+  );
+}
+
+#[test]
+fn choice_repeated_in_element() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/choice.xsd")]
+  struct ChoiceSchema;
+
+  let xml_1 = r#"
+  <Order>
+    <Id>A1</Id>
+    <Item>Book</Item>
+    <Gift>Card</Gift>
+    <Item>Pen</Item>
+  </Order>
+  "#;
+
+  let sample_1: Order = from_str(xml_1).unwrap();
+
+  let model = Order {
+    id: "A1".to_string(),
+    choice_list: vec![
+      OrderChoice::Item("Book".to_string()),
+      OrderChoice::Gift("Card".to_string()),
+      OrderChoice::Item("Pen".to_string()),
+    ],
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Order><Id>A1</Id><Item>Book</Item><Gift>Card</Gift><Item>Pen</Item></Order>"#
+  );
+}
+
+#[test]
+fn sibling_choices_with_same_element() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/choice.xsd")]
+  struct ChoiceSchema;
+
+  use xml_schema_types::{Route, RouteChoice, RouteChoice2};
+
+  let xml_1 =
+    r#"<?xml version="1.0" encoding="UTF-8"?><Route><Via>Lyon</Via><Via>Dijon</Via></Route>"#;
+
+  let sample_1: Route = from_str(xml_1).unwrap();
+
+  let model = Route {
+    choice: RouteChoice::Via("Lyon".to_string()),
+    choice_2: RouteChoice2::Via("Dijon".to_string()),
+  };
+
+  assert_eq!(sample_1, model);
+  assert_eq!(to_string(&model).unwrap(), xml_1);
+
+  let xml_2 = r#"<Route><From>Paris</From><Via>Lyon</Via></Route>"#;
+
+  let sample_2: Route = from_str(xml_2).unwrap();
+
+  assert_eq!(
+    sample_2,
+    Route {
+      choice: RouteChoice::From("Paris".to_string()),
+      choice_2: RouteChoice2::Via("Lyon".to_string()),
+    }
+  );
+}
+
+#[test]
+fn choice_of_simple_types() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/choice.xsd")]
+  struct ChoiceSchema;
+
+  use hifa_xml_schema::numeric::PositiveU64;
+  use xml_schema_types::{Shipment, ShipmentChoice, Speed};
+
+  let xml_1 = r#"
+  <Shipment>
+    <Id>s1</Id>
+    <Speed>express</Speed>
+    <Carrier>  Post   Office </Carrier>
+    <Parcels>3</Parcels>
+  </Shipment>
+  "#;
+
+  let sample_1: Shipment = from_str(xml_1).unwrap();
+
+  let model = Shipment {
+    id: "s1".to_string(),
+    choice_list: vec![
+      ShipmentChoice::Speed(Speed::Express),
+      ShipmentChoice::Carrier("Post Office".into()),
+      ShipmentChoice::Parcels(PositiveU64::new(3).unwrap()),
+    ],
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Shipment><Id>s1</Id><Speed>express</Speed><Carrier>Post Office</Carrier><Parcels>3</Parcels></Shipment>"#
+  );
+
+  assert!(
+    from_str::<Shipment>(r#"<Shipment><Id>s1</Id><Parcels>0</Parcels></Shipment>"#)
+      .unwrap_err()
+      .contains("0 is not a positive integer")
+  );
+}
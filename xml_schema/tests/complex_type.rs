@@ -5,6 +5,7 @@ use hifa_yaserde::ser::to_string;
 
 #[test]
 fn complex_type_string() {
+  #[allow(dead_code)]
   #[derive(Debug, XmlSchema)]
   #[xml_schema(source = "tests/complex_type.xsd")]
   struct ComplexTypeSchema;
@@ -34,6 +35,7 @@ fn complex_type_string() {
 
 #[test]
 fn complex_type_sequence_choice() {
+  #[allow(dead_code)]
   #[derive(Debug, XmlSchema)]
   #[xml_schema(source = "tests/complex_type_sequence_choice.xsd")]
   struct ComplexTypeSchema;
@@ -49,17 +51,17 @@ fn complex_type_sequence_choice() {
   let sample_1: xml_schema_types::Animals = from_str(xml_1).unwrap();
 
   let model = xml_schema_types::Animals {
-    cat_list: vec![
-      Boxed::from(xml_schema_types::Cat {
+    choice_list: vec![
+      xml_schema_types::AnimalsChoice::Cat(Boxed::from(xml_schema_types::Cat {
         name: "AAA".to_string(),
-      }),
-      Boxed::from(xml_schema_types::Cat {
+      })),
+      xml_schema_types::AnimalsChoice::Dog(Boxed::from(xml_schema_types::Dog {
+        name: "BBB".to_string(),
+      })),
+      xml_schema_types::AnimalsChoice::Cat(Boxed::from(xml_schema_types::Cat {
         name: "CCC".to_string(),
-      }),
+      })),
     ],
-    dog_list: vec![Boxed::from(xml_schema_types::Dog {
-      name: "BBB".to_string(),
-    })],
   };
 
   assert_eq!(sample_1, model);
@@ -67,6 +69,6 @@ fn complex_type_sequence_choice() {
   let data = to_string(&model).unwrap();
   assert_eq!(
     data,
-    r#"<?xml version="1.0" encoding="UTF-8"?><Animals><Cat name="AAA" /><Cat name="CCC" /><Dog name="BBB" /></Animals>"#
+    r#"<?xml version="1.0" encoding="UTF-8"?><Animals><Cat name="AAA" /><Dog name="BBB" /><Cat name="CCC" /></Animals>"#
   );
 }
@@ -0,0 +1,201 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn nested_sequences() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/compositor.xsd")]
+  struct CompositorSchema;
+
+  use xml_schema_types::{Entry, EntrySequence, EntrySequence2};
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Entry><id>1</id><key>color</key><value>red</value><value>blue</value><key>size</key><value>L</value><note>sale</note></Entry>"#;
+  let model = Entry {
+    id: 1,
+    sequence_list: vec![
+      EntrySequence {
+        key: "color".to_string(),
+        value_list: vec!["red".to_string(), "blue".to_string()],
+      },
+      EntrySequence {
+        key: "size".to_string(),
+        value_list: vec!["L".to_string()],
+      },
+    ],
+    sequence_2: Some(EntrySequence2 {
+      note: "sale".to_string(),
+    }),
+  };
+
+  let sample: Entry = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+
+  let sample: Entry = from_str(r#"<Entry><id>2</id><key>empty</key></Entry>"#).unwrap();
+  assert_eq!(sample.sequence_list.len(), 1);
+  assert_eq!(sample.sequence_2, None);
+}
+
+#[test]
+fn repeated_sequence_of_type() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/compositor.xsd")]
+  struct CompositorSchema;
+
+  use xml_schema_types::{Pairs, PairsSequence};
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Pairs><name>a</name><value>1</value><name>b</name><value>2</value></Pairs>"#;
+  let model = Pairs {
+    sequence_list: vec![
+      PairsSequence {
+        name: "a".to_string(),
+        value: 1,
+      },
+      PairsSequence {
+        name: "b".to_string(),
+        value: 2,
+      },
+    ],
+  };
+
+  let sample: Pairs = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+}
+
+#[test]
+fn sequence_in_choice() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/compositor.xsd")]
+  struct CompositorSchema;
+
+  use xml_schema_types::{Shape, ShapeChoice, ShapeChoiceSequence};
+
+  let xml_content =
+    r#"<?xml version="1.0" encoding="UTF-8"?><Shape><width>3</width><height>4</height></Shape>"#;
+  let model = Shape {
+    choice: ShapeChoice::Sequence(ShapeChoiceSequence {
+      width: 3,
+      height: 4,
+    }),
+  };
+
+  let sample: Shape = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+
+  let sample: Shape = from_str(r#"<Shape><radius>2</radius></Shape>"#).unwrap();
+  assert_eq!(sample.choice, ShapeChoice::Radius(2));
+}
+
+#[test]
+fn choice_in_declared_order() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/compositor.xsd")]
+  struct CompositorSchema;
+
+  use xml_schema_types::{Frame, FrameChoice, FrameChoiceSequence};
+
+  // the sequence declared first is the default variant
+  assert_eq!(
+    Frame::default().choice,
+    FrameChoice::Sequence(FrameChoiceSequence {
+      width: 0,
+      height: 0,
+    })
+  );
+
+  let xml_content =
+    r#"<?xml version="1.0" encoding="UTF-8"?><Frame><width>3</width><height>4</height></Frame>"#;
+  let sample: Frame = from_str(xml_content).unwrap();
+  assert_eq!(
+    sample.choice,
+    FrameChoice::Sequence(FrameChoiceSequence {
+      width: 3,
+      height: 4,
+    })
+  );
+  assert_eq!(to_string(&sample).unwrap(), xml_content);
+
+  let sample: Frame = from_str(r#"<Frame><side>2</side></Frame>"#).unwrap();
+  assert_eq!(sample.choice, FrameChoice::Side(2));
+}
+
+#[test]
+fn choice_in_choice() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/compositor.xsd")]
+  struct CompositorSchema;
+
+  use xml_schema_types::{Expression, ExpressionChoice, ExpressionChoiceChoice};
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Expression><word>sum</word><number>1</number><symbol>+</symbol><word>end</word></Expression>"#;
+  let model = Expression {
+    choice_list: vec![
+      ExpressionChoice::Word("sum".to_string()),
+      ExpressionChoice::Choice(ExpressionChoiceChoice::Number(1)),
+      ExpressionChoice::Choice(ExpressionChoiceChoice::Symbol("+".to_string())),
+      ExpressionChoice::Word("end".to_string()),
+    ],
+  };
+
+  let sample: Expression = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+}
+
+#[test]
+fn nested_compositors_in_element() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/compositor.xsd")]
+  struct CompositorSchema;
+
+  let xml_content =
+    r#"<?xml version="1.0" encoding="UTF-8"?><Range><name>years</name><from>1990</from></Range>"#;
+  let model = Range {
+    name: "years".to_string(),
+    choice: RangeChoice::Sequence(RangeChoiceSequence {
+      from: 1990,
+      to: None,
+    }),
+  };
+
+  let sample: Range = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+
+  let sample: Range = from_str(r#"<Range><name>any</name><all>true</all></Range>"#).unwrap();
+  assert_eq!(sample.choice, RangeChoice::All(true));
+}
+
+#[test]
+fn interleaved_particles() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/compositor.xsd")]
+  struct CompositorSchema;
+
+  use xml_schema_types::{Address, Parcel, ParcelChoice};
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Parcel><street>Main Street</street><city>London</city><weight>3</weight><carrier>Post</carrier><label>fragile</label></Parcel>"#;
+  let model = Parcel {
+    address: Address {
+      street: "Main Street".to_string(),
+      city: "London".to_string(),
+    },
+    weight: 3,
+    choice: ParcelChoice::Carrier("Post".to_string()),
+    label: "fragile".to_string(),
+  };
+
+  let sample: Parcel = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+}
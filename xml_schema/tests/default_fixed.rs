@@ -0,0 +1,295 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn attribute_default_and_fixed() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/default_fixed.xsd")]
+  struct DefaultFixedSchema;
+
+  use xml_schema_types::{Measure, Unit};
+
+  assert_eq!(Measure::UNIT_DEFAULT, "cm");
+  assert_eq!(Measure::VERSION_FIXED, "1.0");
+
+  let model = Measure {
+    value: 1.5,
+    unit: Unit::Cm,
+    precision: 2,
+    exact: false,
+    label: "measure".to_string(),
+    version: "1.0".to_string(),
+    scale: 10,
+  };
+
+  assert_eq!(
+    Measure::default(),
+    Measure {
+      value: 0.0,
+      ..model.clone()
+    }
+  );
+
+  let sample_1: Measure = from_str(r#"<Measure><value>1.5</value></Measure>"#).unwrap();
+  assert_eq!(sample_1, model);
+
+  let sample_2: Measure = from_str(
+    r#"<Measure unit="mm" precision="4" exact="true" label="" version="1.0" scale="10"><value>1.5</value></Measure>"#,
+  )
+  .unwrap();
+  assert_eq!(
+    sample_2,
+    Measure {
+      unit: Unit::Mm,
+      precision: 4,
+      exact: true,
+      label: String::new(),
+      ..model.clone()
+    }
+  );
+
+  assert_eq!(
+    from_str::<Measure>(r#"<Measure version="2.0"><value>1.5</value></Measure>"#),
+    Err("Measure: the value 2.0 of version is not the fixed value 1.0".to_string())
+  );
+  assert_eq!(
+    from_str::<Measure>(r#"<Measure scale="5"><value>1.5</value></Measure>"#),
+    Err("Measure: the value 5 of scale is not the fixed value 10".to_string())
+  );
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Measure unit="cm" precision="2" exact="false" label="measure" version="1.0" scale="10"><value>1.5</value></Measure>"#
+  );
+}
+
+#[test]
+fn element_default_fixed_and_nillable() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/default_fixed.xsd")]
+  struct DefaultFixedSchema;
+
+  use hifa_xml_schema::nillable::Nillable;
+  use xml_schema_types::{Order, Unit};
+
+  assert_eq!(Order::CURRENCY_DEFAULT, "EUR");
+  assert_eq!(Order::UNIT_FIXED, "cm");
+
+  let model = Order {
+    currency: "EUR".to_string(),
+    quantity: Some(1),
+    unit: Unit::Cm,
+    tag_list: vec!["none".to_string(), "gift".to_string()],
+    discount: Nillable::Nil,
+    note: Some(Nillable::Value(String::new())),
+    rate: Nillable::Value(5),
+  };
+
+  assert_eq!(
+    Order::default(),
+    Order {
+      quantity: None,
+      tag_list: vec![],
+      note: None,
+      rate: Nillable::Nil,
+      ..model.clone()
+    }
+  );
+
+  let sample_1: Order = from_str(
+    r#"<Order xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><currency/><quantity></quantity><unit/><tag/><tag>gift</tag><discount xsi:nil="true"/><note/><rate/></Order>"#,
+  )
+  .unwrap();
+  assert_eq!(sample_1, model);
+
+  let sample_2: Order = from_str(
+    r#"<Order xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><currency>USD</currency><unit>cm</unit><discount>0.5</discount><rate xsi:nil="true"/></Order>"#,
+  )
+  .unwrap();
+  assert_eq!(
+    sample_2,
+    Order {
+      currency: "USD".to_string(),
+      quantity: None,
+      tag_list: vec![],
+      discount: Nillable::Value(0.5),
+      note: None,
+      rate: Nillable::Nil,
+      ..model.clone()
+    }
+  );
+
+  assert_eq!(
+    from_str::<Order>(r#"<Order><unit>mm</unit><discount>1</discount></Order>"#),
+    Err("Order: the value mm of unit is not the fixed value cm".to_string())
+  );
+  assert_eq!(
+    from_str::<Order>(r#"<Order><quantity>many</quantity><discount>1</discount></Order>"#),
+    Err(r#"Order: invalid value "many" of quantity: invalid digit found in string"#.to_string())
+  );
+
+  let data = to_string(&sample_2).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Order><currency>USD</currency><unit>cm</unit><discount>0.5</discount><rate xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true" /></Order>"#
+  );
+}
+
+#[test]
+fn nillable_complex_element() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/default_fixed.xsd")]
+  struct DefaultFixedSchema;
+
+  use hifa_xml_schema::nillable::NillableComplex;
+  use xml_schema_types::{Address, Customer};
+
+  let model = Customer {
+    address: NillableComplex::Value(Address {
+      city: "Paris".to_string(),
+      country: Some("FR".to_string()),
+    }),
+    billing: Some(NillableComplex::Nil),
+  };
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Customer><address country="FR"><city>Paris</city></address><billing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true" /></Customer>"#
+  );
+  let sample_1: Customer = from_str(&data).unwrap();
+  assert_eq!(sample_1, model);
+
+  let sample_2: Customer = from_str(
+    r#"<Customer xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><address xsi:nil="true">
+    </address></Customer>"#,
+  )
+  .unwrap();
+  assert_eq!(
+    sample_2,
+    Customer {
+      address: NillableComplex::Nil,
+      billing: None,
+    }
+  );
+
+  assert_eq!(
+    from_str::<Customer>(
+      r#"<Customer xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><address xsi:nil="true"><city>Paris</city></address></Customer>"#,
+    ),
+    Err("address: a nil element can not have a content".to_string())
+  );
+}
+
+#[test]
+fn typed_default_values() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/default_fixed.xsd")]
+  struct DefaultFixedSchema;
+
+  use xml_schema_types::Range;
+
+  let model = Range {
+    low: -128,
+    high: 255,
+    limit: f64::INFINITY,
+  };
+  assert_eq!(Range::default(), model);
+
+  let sample_1: Range = from_str(r#"<Range />"#).unwrap();
+  assert_eq!(sample_1, model);
+
+  let sample_2: Range = from_str(r#"<Range low="-5" limit="-INF" />"#).unwrap();
+  assert_eq!(
+    sample_2,
+    Range {
+      low: -5,
+      limit: f64::NEG_INFINITY,
+      ..model
+    }
+  );
+}
+
+#[test]
+fn default_values_typed_by_the_generation() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/default_fixed.xsd")]
+  struct DefaultFixedSchema;
+
+  use hifa_xml_schema::string::Token;
+  use xml_schema_types::{Code, Label, Setting};
+
+  let model = Setting {
+    level: 7,
+    name: Token::from("main setting"),
+    code: Code {
+      content: "A1".to_string(),
+    },
+    label: Label::from("first label".to_string()),
+    amount: "2.50".to_string(),
+  };
+  assert_eq!(Setting::default(), model);
+
+  let sample_1: Setting = from_str(r#"<Setting />"#).unwrap();
+  assert_eq!(sample_1, model);
+
+  let sample_2: Setting = from_str(r#"<Setting label="first   label" />"#).unwrap();
+  assert_eq!(sample_2, model);
+
+  assert_eq!(
+    from_str::<Setting>(r#"<Setting label="second label" />"#),
+    Err(
+      "Setting: the value second label of label is not the fixed value  first  label ".to_string()
+    )
+  );
+}
+
+#[test]
+fn default_values_parsed_at_runtime() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/default_fixed.xsd")]
+  struct DefaultFixedSchema;
+
+  use hifa_xml_schema::temporal::Date;
+  use xml_schema_types::{Days, Priority, Schedule};
+
+  assert_eq!(Schedule::START_DEFAULT, "2024-01-01");
+
+  let model = Schedule {
+    due: Date::new(2024, 12, 31).unwrap(),
+    start: Date::new(2024, 1, 1).unwrap(),
+    days: Days {
+      items: vec![1, 3, 5],
+    },
+    priority: Priority::new(5).unwrap(),
+  };
+  assert_eq!(Schedule::default(), model);
+
+  let sample_1: Schedule = from_str(r#"<Schedule><due /></Schedule>"#).unwrap();
+  assert_eq!(sample_1, model);
+
+  let sample_2: Schedule =
+    from_str(r#"<Schedule start="2024-06-01" days="2"><due>2024-06-30</due></Schedule>"#).unwrap();
+  assert_eq!(
+    sample_2,
+    Schedule {
+      due: Date::new(2024, 6, 30).unwrap(),
+      start: Date::new(2024, 6, 1).unwrap(),
+      days: Days { items: vec![2] },
+      ..model
+    }
+  );
+
+  assert_eq!(
+    from_str::<Schedule>(r#"<Schedule priority="7"><due /></Schedule>"#),
+    Err("Schedule: the value 7 of priority is not the fixed value 5".to_string())
+  );
+}
@@ -3,6 +3,7 @@ use hifa_yaserde::de::from_str;
 
 #[test]
 fn dmarc_rua_string() {
+  #[allow(dead_code)]
   #[derive(Debug, XmlSchema)]
   #[xml_schema(source = "tests/dmarc_rua.xsd", target_prefix = "dmarc")]
   struct DmarcRuaSchema;
@@ -56,5 +57,9 @@ fn dmarc_rua_string() {
 </feedback>
   "#;
 
-  let _: Feedback = from_str(xml_1).unwrap();
+  let feedback: Feedback = from_str(xml_1).unwrap();
+
+  let date_range = &feedback.report_metadata.date_range;
+  assert_eq!(date_range.begin, 1706832000);
+  assert_eq!(date_range.end, 1706918399);
 }
@@ -0,0 +1,199 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn enumeration_variant_names() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/enumeration.xsd")]
+  struct EnumerationSchema;
+
+  use xml_schema_types::MediaType;
+
+  assert_eq!(
+    "application/json".parse::<MediaType>(),
+    Ok(MediaType::ApplicationJson)
+  );
+  assert_eq!(MediaType::TextPlain.to_string(), "text/plain");
+  assert_eq!(MediaType::Self_.value(), "self");
+}
+
+#[test]
+fn enumeration_non_string_base() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/enumeration.xsd")]
+  struct EnumerationSchema;
+
+  use xml_schema_types::{KnownLevel, Level, Limit, Ratio, StatusCode, Version};
+
+  assert_eq!(StatusCode::_404.value(), 404);
+  assert_eq!(" 200 ".parse::<StatusCode>(), Ok(StatusCode::_200));
+  assert_eq!(
+    "500".parse::<StatusCode>(),
+    Err(r#"StatusCode: invalid value "500""#.to_string())
+  );
+
+  assert_eq!(Version::V2.value(), "v2");
+  assert_eq!(" v1 ".parse::<Version>(), Ok(Version::V1));
+
+  assert_eq!(Ratio::Minus1.value(), -1.0);
+  assert_eq!("0.50".parse::<Ratio>(), Ok(Ratio::_0_5));
+
+  assert_eq!(Limit::Inf.value(), f32::INFINITY);
+  assert_eq!(Limit::MinusInf.value(), f32::NEG_INFINITY);
+  assert_eq!("-INF".parse::<Limit>(), Ok(Limit::MinusInf));
+  assert_eq!("0.0".parse::<Limit>(), Ok(Limit::_0));
+
+  assert_eq!(KnownLevel::_5.value(), Level::new(5).unwrap());
+  assert!("11".parse::<KnownLevel>().is_err());
+
+  let xml_1 = r#"
+  <Response version="v2">
+    <status>404</status>
+    <ratio>0.5</ratio>
+    <level>1</level>
+  </Response>
+  "#;
+
+  let sample_1: xml_schema_types::Response = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Response {
+    status: StatusCode::_404,
+    ratio: Ratio::_0_5,
+    level: KnownLevel::_1,
+    version: Some(Version::V2),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Response version="v2"><status>404</status><ratio>0.5</ratio><level>1</level></Response>"#
+  );
+}
+
+#[test]
+fn enumeration_default_first_value() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/enumeration.xsd")]
+  struct EnumerationSchema;
+
+  use xml_schema_types::{KnownLevel, Ratio, Response, StatusCode};
+
+  assert_eq!(StatusCode::default(), StatusCode::_200);
+
+  let data = to_string(&Response::default()).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Response><status>200</status><ratio>0.5</ratio><level>1</level></Response>"#
+  );
+
+  let model = Response {
+    status: StatusCode::_200,
+    ratio: Ratio::_0_5,
+    level: KnownLevel::_1,
+    version: None,
+  };
+  assert_eq!(Response::default(), model);
+}
+
+#[test]
+fn enumeration_default_option() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/enumeration.xsd", enum_default = "option")]
+  struct EnumerationSchema;
+
+  use xml_schema_types::{Response, StatusCode};
+
+  let data = to_string(&Response::default()).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Response />"#
+  );
+
+  let xml_1 = r#"
+  <Response>
+    <status>404</status>
+  </Response>
+  "#;
+
+  let sample_1: Response = from_str(xml_1).unwrap();
+
+  let model = Response {
+    status: Some(StatusCode::_404),
+    ratio: None,
+    level: None,
+    version: None,
+  };
+
+  assert_eq!(sample_1, model);
+}
+
+#[test]
+fn open_enumeration() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/enumeration.xsd", open_enums = true)]
+  struct EnumerationSchema;
+
+  use xml_schema_types::{KnownLevel, Level, MediaType, Ratio, Response, StatusCode, Version};
+
+  assert_eq!(
+    "image/png".parse::<MediaType>(),
+    Ok(MediaType::Other("image/png".to_string()))
+  );
+  assert_eq!(
+    MediaType::Other("image/png".to_string()).value(),
+    "image/png"
+  );
+  assert_eq!("text/plain".parse::<MediaType>(), Ok(MediaType::TextPlain));
+
+  assert_eq!(
+    " 0500".parse::<StatusCode>(),
+    Ok(StatusCode::Other {
+      value: 500,
+      lexical: "0500".to_string()
+    })
+  );
+  let status = StatusCode::Other {
+    value: 500,
+    lexical: "500".to_string(),
+  };
+  assert_eq!(status.value(), 500);
+  assert_eq!(status.to_string(), "500");
+  assert!("abc".parse::<StatusCode>().is_err());
+  assert!("11".parse::<KnownLevel>().is_err());
+
+  let xml_1 = r#"
+  <Response version="v3">
+    <status>500</status>
+    <ratio>0.5</ratio>
+    <level>7</level>
+  </Response>
+  "#;
+
+  let sample_1: Response = from_str(xml_1).unwrap();
+
+  let model = Response {
+    status: status.clone(),
+    ratio: Ratio::_0_5,
+    level: KnownLevel::Other {
+      value: Level::new(7).unwrap(),
+      lexical: "7".to_string(),
+    },
+    version: Some(Version::Other("v3".to_string())),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Response version="v3"><status>500</status><ratio>0.5</ratio><level>7</level></Response>"#
+  );
+}
@@ -0,0 +1,154 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn facets_validation() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/facets.xsd")]
+  struct FacetsSchema;
+
+  use xml_schema_types::{
+    Code, Gauge, Label, Percent, Price, Quantity, Reading, Temperature, Triple, Zip,
+  };
+
+  assert_eq!(*Percent::new(100).unwrap().value(), 100);
+  assert_eq!(
+    Percent::new(101),
+    Err("Percent: 101 is greater than the maximum 100".to_string())
+  );
+  assert_eq!(
+    "-1".parse::<Percent>(),
+    Err("Percent: -1 is less than the minimum 0".to_string())
+  );
+  assert!(" 42 ".parse::<Percent>().is_ok());
+  assert!("many".parse::<Percent>().is_err());
+
+  assert!(Temperature::new(-273.0).is_ok());
+  assert_eq!(
+    Temperature::new(-273.15),
+    Err("Temperature: -273.15 is not greater than -273.15".to_string())
+  );
+  assert!(Temperature::new(1000.0).is_err());
+
+  assert_eq!(*Gauge::new(200).unwrap().value(), 200);
+  assert_eq!(
+    Gauge::new(201),
+    Err("Gauge: 201 is greater than the maximum 200".to_string())
+  );
+
+  assert!(Reading::new(f32::INFINITY).is_ok());
+  assert!(Reading::new(-1e30).is_ok());
+  assert!(Reading::new(f32::NEG_INFINITY).is_err());
+
+  assert!(Quantity::new(999).is_ok());
+  assert_eq!(
+    Quantity::new(1000),
+    Err("Quantity: 1000 has more than 3 digits".to_string())
+  );
+
+  assert!("AB123".parse::<Code>().is_ok());
+  assert!("XXX".parse::<Code>().is_ok());
+  assert_eq!(
+    "ab123".parse::<Code>(),
+    Err(r#"Code: "ab123" does not match the pattern "[A-Z]{2}\\d{3}|X+""#.to_string())
+  );
+  assert_eq!(
+    "XXXXXX".parse::<Code>(),
+    Err(r#"Code: the length 6 of "XXXXXX" is greater than 5"#.to_string())
+  );
+
+  assert_eq!(
+    "  Blue \n  Pen ".parse::<Label>().unwrap().value(),
+    "Blue Pen"
+  );
+  assert!(" a ".parse::<Label>().is_err());
+
+  assert_eq!(*"01234".parse::<Zip>().unwrap().value(), 1234);
+  assert_eq!(
+    "1234".parse::<Zip>(),
+    Err(r#"Zip: "1234" does not match the pattern "[0-9]{5}""#.to_string())
+  );
+  assert_eq!(
+    Zip::new(1234),
+    Err(r#"Zip: "1234" does not match the pattern "[0-9]{5}""#.to_string())
+  );
+  assert_eq!(*"007".parse::<Triple>().unwrap().value(), 7);
+  assert_eq!(
+    "7".parse::<Triple>(),
+    Err(r#"Triple: the length 1 of "7" is not 3"#.to_string())
+  );
+  assert_eq!(*" 1.50 ".parse::<Price>().unwrap().value(), 1.5);
+  assert!("1.5".parse::<Price>().is_err());
+  assert_eq!(*"0012".parse::<Quantity>().unwrap().value(), 12);
+  assert_eq!(
+    "1000".parse::<Quantity>(),
+    Err("Quantity: 1000 has more than 3 digits".to_string())
+  );
+
+  // the text read is written back, to be read again
+  let zip = "01234".parse::<Zip>().unwrap();
+  assert_eq!(zip.to_string(), "01234");
+  assert_eq!(zip.to_string().parse::<Zip>(), Ok(zip));
+  let triple = "007".parse::<Triple>().unwrap();
+  assert_eq!(triple.to_string().parse::<Triple>(), Ok(triple));
+  let price = " 1.50 ".parse::<Price>().unwrap();
+  assert_eq!(price.to_string(), "1.50");
+  assert_eq!(price.to_string().parse::<Price>(), Ok(price));
+  let quantity = "0012".parse::<Quantity>().unwrap();
+  assert_eq!(quantity.to_string().parse::<Quantity>(), Ok(quantity));
+  assert_eq!(
+    Zip::default().to_string().parse::<Zip>(),
+    Ok(Zip::default())
+  );
+  assert_eq!(
+    Triple::default().to_string().parse::<Triple>(),
+    Ok(Triple::default())
+  );
+
+  let xml_address = r#"<?xml version="1.0" encoding="UTF-8"?><Address><zip>01234</zip><code>007</code><price>1.50</price></Address>"#;
+  let address: xml_schema_types::Address = from_str(xml_address).unwrap();
+  let data = to_string(&address).unwrap();
+  assert_eq!(data, xml_address);
+  assert_eq!(from_str::<xml_schema_types::Address>(&data), Ok(address));
+
+  // the default values satisfy the facets
+  assert_eq!(*Temperature::default().value(), -273.0);
+  assert_eq!(Code::default().value(), "AA000");
+  assert_eq!(Label::default().value(), "aa");
+  let product = xml_schema_types::Product::default();
+  let data = to_string(&product).unwrap();
+  assert_eq!(from_str::<xml_schema_types::Product>(&data), Ok(product));
+
+  let xml_1 = r#"
+  <Product code="AB123">
+    <label>Blue pen</label>
+    <discount>15</discount>
+    <quantity>12</quantity>
+  </Product>
+  "#;
+
+  let sample_1: xml_schema_types::Product = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Product {
+    label: Label::new("Blue pen".to_string()).unwrap(),
+    discount: Percent::new(15).unwrap(),
+    quantity: Quantity::new(12).unwrap(),
+    storage: None,
+    code: Code::new("AB123".to_string()).unwrap(),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Product code="AB123"><label>Blue pen</label><discount>15</discount><quantity>12</quantity></Product>"#
+  );
+
+  let xml_2 = r#"<Product code="AB123"><label>Pen</label><discount>150</discount><quantity>1</quantity></Product>"#;
+
+  let error = from_str::<xml_schema_types::Product>(xml_2).unwrap_err();
+  assert_eq!(error, "Percent: 150 is greater than the maximum 100");
+}
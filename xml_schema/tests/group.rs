@@ -0,0 +1,98 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn group_references() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/group.xsd")]
+  struct GroupSchema;
+
+  use xml_schema_types::{
+    Address, Card, Contact, ContactChoice, Details, Person, Recipient, RecipientChoice, Route,
+  };
+
+  let address = |street: &str, city: &str| Address {
+    street: street.to_string(),
+    city: city.to_string(),
+  };
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Person><name>Ada</name><street>Main Street</street><city>London</city><age>36</age></Person>"#;
+  let model = Person {
+    name: "Ada".to_string(),
+    address: address("Main Street", "London"),
+    details: Some(Details {
+      age: 36,
+      nickname: None,
+    }),
+  };
+
+  let sample: Person = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+
+  let sample: Person =
+    from_str(r#"<Person><name>Bob</name><city>Paris</city><street>Rue</street></Person>"#).unwrap();
+  assert_eq!(sample.address, address("Rue", "Paris"));
+  assert_eq!(sample.details, None);
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Card><phone>0123</phone></Card>"#;
+  let model = Card {
+    contact: Contact {
+      choice: ContactChoice::Phone("0123".to_string()),
+    },
+  };
+  let sample: Card = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Route><name>Tour</name><street>A</street><city>Lyon</city><street>B</street><city>Nice</city><street>C</street></Route>"#;
+  let sample: Route = from_str(xml_content).unwrap();
+  assert_eq!(
+    sample.address_list,
+    vec![
+      address("A", "Lyon"),
+      address("B", "Nice"),
+      Address {
+        street: "C".to_string(),
+        ..Default::default()
+      }
+    ]
+  );
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Recipient><street>Main Street</street><city>London</city></Recipient>"#;
+  let model = Recipient {
+    choice: RecipientChoice::Address(address("Main Street", "London")),
+  };
+  let sample: Recipient = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+
+  let sample: Recipient = from_str(r#"<Recipient><company>ACME</company></Recipient>"#).unwrap();
+  assert_eq!(sample.choice, RecipientChoice::Company("ACME".to_string()));
+}
+
+#[test]
+fn group_reference_position() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/group.xsd")]
+  struct GroupSchema;
+
+  use xml_schema_types::{Address, Envelope};
+
+  let xml_content = r#"<?xml version="1.0" encoding="UTF-8"?><Envelope><sender>Ada</sender><street>Main Street</street><city>London</city><stamp>1p</stamp></Envelope>"#;
+  let model = Envelope {
+    sender: "Ada".to_string(),
+    address: Address {
+      street: "Main Street".to_string(),
+      city: "London".to_string(),
+    },
+    stamp: "1p".to_string(),
+  };
+
+  let sample: Envelope = from_str(xml_content).unwrap();
+  assert_eq!(sample, model);
+  assert_eq!(to_string(&model).unwrap(), xml_content);
+}
@@ -0,0 +1,52 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+
+#[test]
+fn import_schema_location() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/import.xsd")]
+  struct ImportSchema;
+
+  let xml_1 = r#"
+  <Library>
+    <name>Public library</name>
+    <book>
+      <title>Notre-Dame de Paris</title>
+      <author name="Victor Hugo" />
+    </book>
+    <publisher name="Hetzel">
+      <founder name="Pierre-Jules Hetzel" />
+    </publisher>
+    <edition year="1831" />
+  </Library>
+  "#;
+
+  let sample_1: xml_schema_types::Library = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Library {
+    name: "Public library".to_string(),
+    book_list: vec![
+      xml_schema_types::example_com_book::xml_schema_types::BookType {
+        title: "Notre-Dame de Paris".to_string(),
+        author: xml_schema_types::example_com_author::xml_schema_types::AuthorType {
+          name: "Victor Hugo".to_string(),
+        },
+      },
+    ],
+    publisher: Some(
+      xml_schema_types::example_com_book::xml_schema_types::PublisherType {
+        founder: xml_schema_types::example_com_author::xml_schema_types::AuthorType {
+          name: "Pierre-Jules Hetzel".to_string(),
+        },
+        name: "Hetzel".to_string(),
+      },
+    ),
+    // `https://example.com/book` is numbered, not to collide with `http://example.com/book`
+    edition: Some(
+      xml_schema_types::example_com_book_2::xml_schema_types::EditionType { year: 1831 },
+    ),
+  };
+
+  assert_eq!(sample_1, model);
+}
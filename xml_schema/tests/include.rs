@@ -0,0 +1,38 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+
+#[test]
+fn include_chameleon_schemas() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/include.xsd", target_prefix = "ord")]
+  struct IncludeSchema;
+
+  let xml_1 = r#"
+  <ord:Order xmlns:ord="http://example.com/order">
+    <ord:id>A-123</ord:id>
+    <ord:buyer>
+      <ord:name>ACME</ord:name>
+      <ord:address>
+        <ord:city>Paris</ord:city>
+        <ord:zip_code>75001</ord:zip_code>
+      </ord:address>
+    </ord:buyer>
+  </ord:Order>
+  "#;
+
+  let sample_1: xml_schema_types::Order = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Order {
+    id: "A-123".to_string(),
+    buyer: xml_schema_types::PartyType {
+      name: "ACME".to_string(),
+      address: xml_schema_types::AddressType {
+        city: "Paris".to_string(),
+        zip_code: 75001,
+      },
+    },
+  };
+
+  assert_eq!(sample_1, model);
+}
@@ -0,0 +1,54 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn lenient_mode() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/lenient.xsd", mode = "lenient")]
+  struct LenientSchema;
+
+  let xml_1 = r#"<?xml version="1.0" encoding="UTF-8"?>
+  <Document code="A12">
+    <title>Report</title>
+    <published>2024-01-31</published>
+    <restricted><value>raw</value></restricted>
+  </Document>
+  "#;
+
+  let sample_1: xml_schema_types::Document = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Document {
+    title: "Report".to_string(),
+    published: "2024-01-31".to_string(),
+    restricted: xml_schema_types::Restricted {
+      content: "<value>raw</value>".to_string(),
+    },
+    code: Some(xml_schema_types::Code {
+      content: "A12".to_string(),
+    }),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Document code="A12"><title>Report</title><published>2024-01-31</published><restricted><value>raw</value></restricted></Document>"#
+  );
+
+  let skipped: Vec<&str> = SKIPPED_COMPONENTS
+    .iter()
+    .map(|component| component.rsplit("lenient.xsd:").next().unwrap())
+    .collect();
+
+  assert_eq!(
+    skipped,
+    vec![
+      r#"4:5: simpleType "Code" / union: Type "unknownCode" not implemented"#,
+      r#"8:5: complexType "Restricted" / complexContent: only extension is supported in complexContent"#,
+      r#"20:7: complexType "Document" / element "published": Type "unknownDate" not implemented"#,
+    ]
+  );
+}
@@ -0,0 +1,147 @@
+use hifa_xml_schema::numeric::{Decimal, Integer, NegativeI64, NonPositiveI64, NonPositiveInteger};
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn arbitrary_precision() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/numeric.xsd", precision = "arbitrary")]
+  struct NumericSchema;
+
+  use xml_schema_types::{Amount, Payment, Rate};
+
+  assert_eq!(
+    "12345678901234567.891".parse::<Amount>(),
+    Err("Amount: 12345678901234567.891 has more than 2 fraction digits".to_string())
+  );
+  assert!("-0.01".parse::<Amount>().is_err());
+  assert_eq!("0.20".parse::<Rate>(), Ok(Rate::_0_2));
+  assert_eq!(Rate::_0_055.value(), "0.055".parse::<Decimal>().unwrap());
+
+  let xml_1 = r#"
+  <Payment>
+    <amount>123456789012345678.90</amount>
+    <rate>0.055</rate>
+    <serial>123456789012345678901234567890</serial>
+    <adjustment>-0</adjustment>
+  </Payment>
+  "#;
+
+  let sample_1: Payment = from_str(xml_1).unwrap();
+
+  let model = Payment {
+    amount: Amount::new("123456789012345678.9".parse().unwrap()).unwrap(),
+    rate: Rate::_0_055,
+    serial: "123456789012345678901234567890".parse::<Integer>().unwrap(),
+    adjustment: NonPositiveInteger::default(),
+    debt: None,
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Payment><amount>123456789012345678.9</amount><rate>0.055</rate><serial>123456789012345678901234567890</serial><adjustment>0</adjustment></Payment>"#
+  );
+
+  let xml_2 = r#"
+  <Payment>
+    <amount>1</amount>
+    <rate>0.2</rate>
+    <serial>1</serial>
+    <adjustment>5</adjustment>
+  </Payment>
+  "#;
+
+  assert!(from_str::<Payment>(xml_2)
+    .unwrap_err()
+    .contains("5 is not a non-positive integer"));
+
+  let xml_3 = r#"
+  <Payment>
+    <amount>1</amount>
+    <rate>0.2</rate>
+    <serial>1</serial>
+    <adjustment>0</adjustment>
+    <debt>0</debt>
+  </Payment>
+  "#;
+
+  assert!(from_str::<Payment>(xml_3)
+    .unwrap_err()
+    .contains("0 is not a negative integer"));
+}
+
+#[test]
+fn native_precision() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/numeric.xsd")]
+  struct NumericSchema;
+
+  use xml_schema_types::{Amount, Payment, Rate};
+
+  let xml_1 = r#"
+  <Payment>
+    <amount>12.5</amount>
+    <rate>0.2</rate>
+    <serial>9000000000</serial>
+    <adjustment>-3</adjustment>
+    <debt>-100</debt>
+  </Payment>
+  "#;
+
+  let sample_1: Payment = from_str(xml_1).unwrap();
+
+  let model = Payment {
+    amount: Amount::new("12.5".to_string()).unwrap(),
+    rate: Rate::_0_2,
+    // `xs:integer` is as wide as its 64-bit subtypes
+    serial: 9_000_000_000,
+    adjustment: NonPositiveI64::new(-3).unwrap(),
+    debt: Some(NegativeI64::new(-100).unwrap()),
+  };
+
+  assert_eq!(sample_1, model);
+
+  // the sign of the unbounded integer types is checked as in arbitrary precision
+  let xml_2 = r#"
+  <Payment>
+    <amount>1</amount>
+    <rate>0.2</rate>
+    <serial>1</serial>
+    <adjustment>5</adjustment>
+  </Payment>
+  "#;
+
+  assert!(from_str::<Payment>(xml_2)
+    .unwrap_err()
+    .contains("5 is not a non-positive integer"));
+
+  let xml_3 = r#"
+  <Payment>
+    <amount>1</amount>
+    <rate>0.2</rate>
+    <serial>1</serial>
+    <adjustment>0</adjustment>
+    <debt>0</debt>
+  </Payment>
+  "#;
+
+  assert!(from_str::<Payment>(xml_3)
+    .unwrap_err()
+    .contains("0 is not a negative integer"));
+
+  // the decimals are kept as text, checked and compared as `Decimal`
+  assert_eq!(
+    "123456789012345678.90".parse::<Amount>().unwrap().value(),
+    "123456789012345678.90"
+  );
+  assert!("-0.01".parse::<Amount>().is_err());
+  assert!("abc".parse::<Amount>().is_err());
+  assert_eq!("0.20".parse::<Rate>(), Ok(Rate::_0_2));
+  assert_eq!(Rate::_0_055.value(), "0.055");
+}
@@ -0,0 +1,127 @@
+use hifa_xml_schema::qname::QName;
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn qname_namespaces() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/qname.xsd")]
+  struct QNameSchema;
+
+  use xml_schema_types::Fault;
+
+  let xml_1 = r#"
+  <Fault xmlns:env="http://www.w3.org/2003/05/soap-envelope" kind="env:Fault">
+    <code>env:Sender</code>
+    <subcode xmlns:m="http://example.com/errors">m:MessageTimeout</subcode>
+    <reason>Timeout</reason>
+  </Fault>
+  "#;
+
+  let sample_1: Fault = from_str(xml_1).unwrap();
+
+  let model = Fault {
+    code: QName::new(
+      Some("http://www.w3.org/2003/05/soap-envelope"),
+      Some("env"),
+      "Sender",
+    )
+    .unwrap(),
+    subcode: Some(
+      QName::new(
+        Some("http://example.com/errors"),
+        Some("m"),
+        "MessageTimeout",
+      )
+      .unwrap(),
+    ),
+    reason: "Timeout".to_string(),
+    kind: Some(
+      QName::new(
+        Some("http://www.w3.org/2003/05/soap-envelope"),
+        Some("env"),
+        "Fault",
+      )
+      .unwrap(),
+    ),
+    origin: None,
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Fault xmlns:env="http://www.w3.org/2003/05/soap-envelope" kind="env:Fault"><code>env:Sender</code><subcode xmlns:m="http://example.com/errors">m:MessageTimeout</subcode><reason>Timeout</reason></Fault>"#
+  );
+  assert_eq!(from_str::<Fault>(&data).unwrap(), model);
+
+  let model = Fault {
+    code: QName::new(Some("http://example.com/errors"), None, "Unknown").unwrap(),
+    ..Default::default()
+  };
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Fault><code xmlns:ns="http://example.com/errors">ns:Unknown</code><reason></reason></Fault>"#
+  );
+}
+
+#[test]
+fn qname_attribute_prefixes() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/qname.xsd")]
+  struct QNameSchema;
+
+  use xml_schema_types::Fault;
+
+  // the same prefix is bound to two namespaces, the second value gets another one
+  let model = Fault {
+    kind: Some(QName::new(Some("urn:a"), Some("p"), "First").unwrap()),
+    origin: Some(QName::new(Some("urn:b"), Some("p"), "Second").unwrap()),
+    code: "Client".parse().unwrap(),
+    ..Default::default()
+  };
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Fault xmlns:ns="urn:b" xmlns:p="urn:a" kind="p:First" origin="ns:Second"><code>Client</code><reason></reason></Fault>"#
+  );
+
+  let sample: Fault = from_str(&data).unwrap();
+  assert_eq!(sample.kind, model.kind);
+  assert_eq!(sample.origin.as_ref().unwrap().namespace(), Some("urn:b"));
+  assert_eq!(sample.origin.unwrap().local_name(), "Second");
+}
+
+#[test]
+fn qname_undeclared_prefix() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/qname.xsd")]
+  struct QNameSchema;
+
+  use xml_schema_types::Fault;
+
+  assert_eq!(
+    from_str::<Fault>(r#"<Fault><code>env:Sender</code><reason>Timeout</reason></Fault>"#),
+    Err(r#"the prefix of the QName "env:Sender" is not declared"#.to_string())
+  );
+  assert_eq!(
+    from_str::<Fault>(
+      r#"<Fault kind="env:Fault"><code>Sender</code><reason>Timeout</reason></Fault>"#
+    ),
+    Err(r#"the prefix of the QName "env:Fault" is not declared"#.to_string())
+  );
+
+  // without prefix, the value is in the default namespace, if any
+  let sample: Fault =
+    from_str(r#"<Fault kind="Fault"><code>Sender</code><reason>Timeout</reason></Fault>"#).unwrap();
+  assert_eq!(sample.code.namespace(), None);
+  assert_eq!(sample.kind.unwrap().namespace(), None);
+}
@@ -0,0 +1,36 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+
+#[test]
+fn redefine_complex_type() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/redefine.xsd")]
+  struct RedefineSchema;
+
+  let xml_1 = r#"
+  <Employee>
+    <person>
+      <name>John</name>
+      <age>42</age>
+    </person>
+    <country>France</country>
+  </Employee>
+  "#;
+
+  let sample_1: xml_schema_types::Employee = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Employee {
+    person: xml_schema_types::Person {
+      base: xml_schema_types::PersonOriginal {
+        name: "John".to_string(),
+      },
+      age: 42,
+    },
+    country: xml_schema_types::Country {
+      content: "France".to_string(),
+    },
+  };
+
+  assert_eq!(sample_1, model);
+}
@@ -4,6 +4,7 @@ use hifa_yaserde::ser::to_string;
 
 #[test]
 fn simple_type_string() {
+  #[allow(dead_code)]
   #[derive(Debug, XmlSchema)]
   #[xml_schema(source = "tests/simple_type_string.xsd", target_prefix = "st")]
   struct SimpleTypeSchema;
@@ -25,6 +26,7 @@ fn simple_type_string() {
 
 #[test]
 fn simple_type_list() {
+  #[allow(dead_code)]
   #[derive(Debug, XmlSchema)]
   #[xml_schema(source = "tests/simple_type_list.xsd")]
   struct SimpleTypeSchema;
@@ -47,9 +49,86 @@ fn simple_type_list() {
 
   assert_eq!(sample_1, model);
 
+  let xml_2 =
+    "<BaseType strings=\"  value1 \t\n value2 \" integers=\"3  6\" booleans=\"true\tfalse\" />";
+
+  let sample_2: xml_schema_types::BaseType = from_str(xml_2).unwrap();
+
+  assert_eq!(sample_2, model);
+
   let data = to_string(&model).unwrap();
   assert_eq!(
     data,
     r#"<?xml version="1.0" encoding="UTF-8"?><BaseType strings="value1 value2" integers="3 6" booleans="true false" />"#
   );
 }
+
+#[test]
+fn simple_type_list_items() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/simple_type_list.xsd")]
+  struct SimpleTypeSchema;
+
+  use xml_schema_types::{Color, ColorList, LevelList, Palette, SizeList, SizeListItem};
+
+  let xml_1 = r#"
+  <Palette sizes="1 2
+    3">
+    <colors>
+      red
+      blue	green
+    </colors>
+    <levels>0.5 1</levels>
+  </Palette>
+  "#;
+
+  let sample_1: Palette = from_str(xml_1).unwrap();
+
+  let model = Palette {
+    colors: ColorList {
+      items: vec![Color::Red, Color::Blue, Color::Green],
+    },
+    levels: Some(LevelList {
+      items: vec![0.5, 1.0],
+    }),
+    sizes: Some(SizeList {
+      items: vec![
+        SizeListItem::new(1).unwrap(),
+        SizeListItem::new(2).unwrap(),
+        SizeListItem::new(3).unwrap(),
+      ],
+    }),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Palette sizes="1 2 3"><colors>red blue green</colors><levels>0.5 1</levels></Palette>"#
+  );
+
+  assert_eq!(
+    from_str::<Palette>(r#"<Palette><colors>red purple</colors></Palette>"#),
+    Err(r#"ColorList: invalid list item "purple": Color: invalid value "purple""#.to_string())
+  );
+  assert_eq!(
+    from_str::<Palette>(r#"<Palette sizes="1 x"><colors/></Palette>"#),
+    Err(
+      r#"SizeList: invalid list item "x": SizeListItem: invalid value "x": invalid digit found in string"#
+        .to_string()
+    )
+  );
+  assert_eq!(
+    from_str::<Palette>(r#"<Palette sizes="0"><colors/></Palette>"#),
+    Err(
+      r#"SizeList: invalid list item "0": SizeListItem: 0 is less than the minimum 1"#.to_string()
+    )
+  );
+  assert!(from_str::<Palette>(r#"<Palette><colors/></Palette>"#)
+    .unwrap()
+    .colors
+    .items
+    .is_empty());
+}
@@ -0,0 +1,60 @@
+use hifa_xml_schema::temporal::{DateTime, Duration, GDay, GMonth, GMonthDay, GYear, Time};
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn temporal_types() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/temporal.xsd")]
+  struct TemporalSchema;
+
+  use xml_schema_types::{Event, LaterDate, RecentDate, UtcStamp};
+
+  assert!("1999-12-31".parse::<RecentDate>().is_err());
+  assert_eq!(RecentDate::default().to_string(), "2000-01-01");
+
+  // without a value known to satisfy their facets, the types do not implement `Default`
+  assert!("2000-01-01".parse::<LaterDate>().is_err());
+  assert!("2000-01-02".parse::<LaterDate>().is_ok());
+  assert!("2024-02-29T12:00:00Z".parse::<UtcStamp>().is_ok());
+  assert!("2024-02-29T12:00:00".parse::<UtcStamp>().is_err());
+
+  let xml_1 = r#"
+  <Event at="09:30:00+02:00">
+    <start>2024-02-29T24:00:00Z</start>
+    <day>2024-03-01</day>
+    <length>PT90M</length>
+    <year>-0044</year>
+    <anniversary>--02-29</anniversary>
+    <payday>---28</payday>
+    <season>--06</season>
+  </Event>
+  "#;
+
+  let sample_1: Event = from_str(xml_1).unwrap();
+
+  let model = Event {
+    start: "2024-03-01T00:00:00Z".parse::<DateTime>().unwrap(),
+    day: "2024-03-01".parse::<RecentDate>().unwrap(),
+    length: "PT1H30M".parse::<Duration>().unwrap(),
+    year: Some(GYear::new(-44)),
+    month: None,
+    anniversary: Some(GMonthDay::new(2, 29).unwrap()),
+    payday: Some(GDay::new(28).unwrap()),
+    season: Some(GMonth::new(6).unwrap()),
+    at: "09:30:00+02:00".parse::<Time>().unwrap(),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Event at="09:30:00+02:00"><start>2024-03-01T00:00:00Z</start><day>2024-03-01</day><length>PT1H30M</length><year>-0044</year><anniversary>--02-29</anniversary><payday>---28</payday><season>--06</season></Event>"#
+  );
+
+  let xml_2 = r#"<Event at="09:30:00"><start>2024-02-30T00:00:00</start></Event>"#;
+  assert!(from_str::<Event>(xml_2).is_err());
+}
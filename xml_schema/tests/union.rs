@@ -0,0 +1,44 @@
+use hifa_xml_schema_derive::XmlSchema;
+use hifa_yaserde::de::from_str;
+use hifa_yaserde::ser::to_string;
+
+#[test]
+fn union_member_types() {
+  #[allow(dead_code)]
+  #[derive(Debug, XmlSchema)]
+  #[xml_schema(source = "tests/union.xsd")]
+  struct UnionSchema;
+
+  use xml_schema_types::{Size, Width, WidthMember1};
+
+  assert_eq!("12".parse::<Width>(), Ok(Width::Int(12)));
+  assert_eq!("large".parse::<Width>(), Ok(Width::Size(Size::Large)));
+  assert_eq!(
+    "auto".parse::<Width>(),
+    Ok(Width::Member1(WidthMember1::Auto))
+  );
+  assert!("wide".parse::<Width>().is_err());
+  assert_eq!(Width::Int(3).to_string(), "3");
+
+  let xml_1 = r#"
+  <Box height="auto">
+    <width>12</width>
+    <width>small</width>
+  </Box>
+  "#;
+
+  let sample_1: xml_schema_types::Box = from_str(xml_1).unwrap();
+
+  let model = xml_schema_types::Box {
+    width_list: vec![Width::Int(12), Width::Size(Size::Small)],
+    height: Some(Width::Member1(WidthMember1::Auto)),
+  };
+
+  assert_eq!(sample_1, model);
+
+  let data = to_string(&model).unwrap();
+  assert_eq!(
+    data,
+    r#"<?xml version="1.0" encoding="UTF-8"?><Box height="auto"><width>12</width><width>small</width></Box>"#
+  );
+}
@@ -1,3 +1,4 @@
+use crate::xsd::{EnumDefault, Error, Mode, Precision};
 use darling::FromDeriveInput;
 use log::Level;
 use std::collections::BTreeMap;
@@ -6,8 +7,13 @@ use std::collections::BTreeMap;
 #[darling(attributes(xml_schema), forward_attrs(allow, doc, cfg))]
 pub struct XmlSchemaAttributes {
   ident: syn::Ident,
+  pub enum_default: Option<String>,
   pub log_level: Option<String>,
+  pub mode: Option<String>,
   pub module_namespace_mappings: Option<String>,
+  #[darling(default)]
+  pub open_enums: bool,
+  pub precision: Option<String>,
   pub source: String,
   pub store_generated_code: Option<String>,
   pub target_prefix: Option<String>,
@@ -29,6 +35,30 @@ impl XmlSchemaAttributes {
     }
   }
 
+  pub fn mode(&self) -> Result<Mode, Error> {
+    self
+      .mode
+      .as_deref()
+      .map(|mode| mode.parse())
+      .unwrap_or(Ok(Mode::Strict))
+  }
+
+  pub fn enum_default(&self) -> Result<EnumDefault, Error> {
+    self
+      .enum_default
+      .as_deref()
+      .map(|enum_default| enum_default.parse())
+      .unwrap_or(Ok(EnumDefault::First))
+  }
+
+  pub fn precision(&self) -> Result<Precision, Error> {
+    self
+      .precision
+      .as_deref()
+      .map(|precision| precision.parse())
+      .unwrap_or(Ok(Precision::Native))
+  }
+
   pub fn module_namespace_mappings(&self) -> BTreeMap<String, String> {
     let module_namespace_mappings = self.module_namespace_mappings.clone().unwrap_or_default();
     if module_namespace_mappings.is_empty() {
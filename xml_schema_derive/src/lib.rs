@@ -14,14 +14,29 @@ mod xsd;
 
 #[proc_macro_derive(XmlSchema, attributes(xml_schema))]
 pub fn hifa_xml_schema_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-  let input: DeriveInput =
-    syn::parse2(proc_macro2::TokenStream::from(input)).expect("Failed to parse input");
+  let input: DeriveInput = match syn::parse2(proc_macro2::TokenStream::from(input)) {
+    Ok(input) => input,
+    Err(error) => return error.to_compile_error().into(),
+  };
 
-  let attributes =
-    XmlSchemaAttributes::from_derive_input(&input).expect("Failed to parse attributes");
+  let attributes = match XmlSchemaAttributes::from_derive_input(&input) {
+    Ok(attributes) => attributes,
+    Err(error) => return error.write_errors().into(),
+  };
 
   match expander::expand_derive(&attributes) {
     Ok(expanded) => expanded.into(),
-    Err(msg) => panic!("{}", msg),
+    Err(error) => {
+      // report the error on the `#[xml_schema(...)]` attribute which declares the XSD source
+      let error = match input
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path().is_ident("xml_schema"))
+      {
+        Some(attribute) => syn::Error::new_spanned(attribute, error),
+        None => syn::Error::new(proc_macro2::Span::call_site(), error),
+      };
+      error.to_compile_error().into()
+    }
   }
 }
@@ -0,0 +1,85 @@
+use crate::xsd::{element::Element, Result, XsdContext};
+use proc_macro2::TokenStream;
+
+/// The children of an `xs:all` are deserialized in any order, and serialized in the declaration order.
+#[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
+#[yaserde(prefix = "xs", namespaces = {
+    "xs" = "http://www.w3.org/2001/XMLSchema"
+  })]
+pub struct All {
+  #[yaserde(rename = "minOccurs", attribute = true)]
+  pub min_occurences: Option<u64>,
+  #[yaserde(rename = "element")]
+  pub elements: Vec<Element>,
+}
+
+impl All {
+  pub fn get_sub_types_implementation(
+    &self,
+    context: &XsdContext,
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
+  ) -> Result<TokenStream> {
+    self
+      .elements
+      .iter()
+      .map(|element| {
+        element
+          .get_subtypes_implementation(namespace_definition, prefix, context)
+          .map_err(|error| error.with_component("element", Some(&element.name)))
+      })
+      .collect()
+  }
+
+  pub fn get_field_implementation(
+    &self,
+    context: &XsdContext,
+    prefix: &Option<String>,
+  ) -> Result<TokenStream> {
+    let optional = self.min_occurences == Some(0);
+
+    self
+      .elements
+      .iter()
+      .map(|element| {
+        element
+          .get_field_implementation(context, prefix, false, optional && !element.is_multiple())
+          .map_err(|error| error.with_component("element", Some(&element.name)))
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use hifa_yaserde::de::from_str;
+
+  #[test]
+  fn optional_all() {
+    let xml = r#"
+      <all minOccurs="0">
+        <element name="begin" type="xs:int" />
+        <element name="note" type="xs:string" maxOccurs="unbounded" />
+      </all>
+    "#;
+
+    let all: All = from_str(xml).unwrap();
+
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    let implementation = all
+      .get_field_implementation(&context, &None)
+      .unwrap()
+      .to_string();
+
+    assert_eq!(
+      implementation,
+      "# [yaserde (rename = \"begin\")] pub begin : Option < i32 > , \
+# [yaserde (rename = \"note\")] pub note_list : Vec < String > ,"
+    );
+  }
+}
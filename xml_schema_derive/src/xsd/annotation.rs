@@ -1,4 +1,4 @@
-use crate::xsd::{attribute::Attribute, Implementation, XsdContext};
+use crate::xsd::{attribute::Attribute, Implementation, Result, XsdContext};
 use proc_macro2::TokenStream;
 
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
@@ -30,7 +30,7 @@ impl Implementation for Annotation {
     _namespace_definition: &TokenStream,
     _prefix: &Option<String>,
     _context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     log::info!("Generate annotation");
 
     let documentation = self
@@ -38,6 +38,6 @@ impl Implementation for Annotation {
       .iter()
       .map(|documentation| quote!(#[doc = #documentation]));
 
-    quote!(#(#documentation)*)
+    Ok(quote!(#(#documentation)*))
   }
 }
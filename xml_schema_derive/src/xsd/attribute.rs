@@ -1,5 +1,8 @@
 use crate::xsd::{
-  rust_types_mapping::RustTypesMapping, simple_type::SimpleType, Implementation, XsdContext,
+  complex_type::value_constraint_attribute,
+  rust_types_mapping::RustTypesMapping,
+  simple_type::{self, SimpleType},
+  Error, Implementation, Result, XsdContext,
 };
 use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
@@ -18,10 +21,10 @@ pub struct Attribute {
   pub name: Option<String>,
   #[yaserde(rename = "type", attribute = true)]
   pub kind: Option<String>,
-  //#[yaserde(attribute = true)]
-  // pub default: Option<String>,
-  //#[yaserde(attribute = true)]
-  // pub fixed: Option<String>,
+  #[yaserde(attribute = true)]
+  pub default: Option<String>,
+  #[yaserde(attribute = true)]
+  pub fixed: Option<String>,
   #[yaserde(rename = "use", attribute = true)]
   pub required: Required,
   #[yaserde(rename = "ref", attribute = true)]
@@ -45,9 +48,9 @@ impl Implementation for Attribute {
     _namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     if self.name.is_none() {
-      return quote!();
+      return Ok(quote!());
     }
 
     let raw_name = self.get_raw_name().unwrap();
@@ -63,23 +66,53 @@ impl Implementation for Attribute {
       (None, Some(kind), None) => RustTypesMapping::get(context, kind),
       (Some(reference), None, None) => RustTypesMapping::get(context, reference),
       (None, None, Some(simple_type)) => {
-        let struct_name: Option<Ident> = if let Some(name) = self.name.as_ref() {
-          Some(Ident::new(&name.to_upper_camel_case(), Span::call_site()))
-        } else {
-          None
-        };
+        let struct_name: Option<Ident> = self
+          .name
+          .as_ref()
+          .map(|name| Ident::new(&name.to_upper_camel_case(), Span::call_site()));
 
         simple_type.get_type_implementation(context, prefix, struct_name.as_ref())
       }
-      (_, _, _) => panic!("Not implemented Rust type for: {:?}", self),
+      (_, _, _) => Err(Error::new(
+        "attribute requires exactly one of type, ref or simpleType",
+      )),
     };
+    let rust_type = context.fallback("attribute", Some(&raw_name), rust_type, || quote!(String))?;
 
-    let required = match self.simple_type.as_ref() {
-      Some(SimpleType { list: Some(_), .. }) => true,
-      _ => false,
-    };
-
-    let rust_type = if !required && self.required == Required::Optional {
+    let required = matches!(
+      self.simple_type.as_ref(),
+      Some(SimpleType { list: Some(_), .. })
+    );
+
+    let optional_enumeration = self
+      .kind
+      .as_deref()
+      .map(|kind| context.is_optional_enumeration(kind))
+      .unwrap_or_default();
+
+    // an attribute with a default or fixed value always has a value
+    let value_constraint = simple_type::typed_value_constraint(
+      context,
+      self.kind.as_deref().or(self.reference.as_deref()),
+      self.simple_type.as_ref(),
+      &rust_type,
+      self.default.as_deref(),
+      self.fixed.as_deref(),
+    )
+    .and_then(|value| {
+      value_constraint_attribute(self.default.as_deref(), self.fixed.as_deref(), value)
+    });
+    let value_constraint = context.fallback(
+      "attribute",
+      Some(&raw_name),
+      value_constraint,
+      TokenStream::new,
+    )?;
+
+    let rust_type = if !required
+      && value_constraint.is_empty()
+      && (self.required == Required::Optional || optional_enumeration)
+    {
       quote!(Option<#rust_type>)
     } else {
       quote!(#rust_type)
@@ -91,16 +124,12 @@ impl Implementation for Attribute {
       quote!(attribute = true, rename=#raw_name)
     };
 
-    // TODO: add support for default and fixed attributes
-    // let prefix_attribute = prefix
-    //   .as_ref()
-    //   .map(|prefix| quote!(, prefix=#prefix))
-    //   .unwrap_or_default();
-
-    quote!(
+    Ok(quote!(
       #[yaserde(#attributes)]
+      #[xml_schema(attribute = #raw_name)]
+      #value_constraint
       pub #field_name: #rust_type,
-    )
+    ))
   }
 }
 
@@ -110,7 +139,7 @@ impl Attribute {
     namespace_definition: &TokenStream,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     let simple_types: TokenStream = {
       let mut context = context.clone();
       context.set_is_in_sub_module(true);
@@ -122,17 +151,17 @@ impl Attribute {
           if simple_type.name.is_empty() {
             let mut simple_type = simple_type.clone();
             simple_type.name = self.get_name().unwrap_or_default();
-            simple_type.implement(&namespace_definition, prefix, &context)
+            simple_type.implement(namespace_definition, prefix, &context)
           } else {
-            simple_type.implement(&namespace_definition, prefix, &context)
+            simple_type.implement(namespace_definition, prefix, &context)
           }
         })
-        .collect()
+        .collect::<Result<_>>()?
     };
 
-    quote! {
+    Ok(quote! {
       #simple_types
-    }
+    })
   }
 
   fn get_name(&self) -> Option<String> {
@@ -150,11 +179,7 @@ impl Attribute {
   }
 
   fn get_raw_name(&self) -> Option<String> {
-    if let Some(name) = &self.name {
-      Some(name.clone())
-    } else {
-      None
-    }
+    self.name.clone()
   }
 }
 
@@ -173,6 +198,8 @@ mod tests {
     let attribute = Attribute {
       name: Some("language".to_string()),
       kind: Some("xs:string".to_string()),
+      default: None,
+      fixed: None,
       reference: None,
       required: Required::Required,
       simple_type: None,
@@ -182,11 +209,14 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = attribute.implement(&TokenStream::new(), &None, &context);
+    let implementation = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap();
 
     let expected = TokenStream::from_str(
       r#"
        #[yaserde(attribute = true)]
+        #[xml_schema(attribute = "language")]
         pub language: String,
       "#,
     )
@@ -200,6 +230,8 @@ mod tests {
     let attribute = Attribute {
       name: Some("language".to_string()),
       kind: Some("xs:string".to_string()),
+      default: None,
+      fixed: None,
       reference: None,
       required: Required::Optional,
       simple_type: None,
@@ -209,11 +241,14 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = attribute.implement(&TokenStream::new(), &None, &context);
+    let implementation = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap();
 
     let expected = TokenStream::from_str(
       r#"
        #[yaserde(attribute = true)]
+        #[xml_schema(attribute = "language")]
         pub language: Option<String> ,
       "#,
     )
@@ -222,11 +257,59 @@ mod tests {
     assert_eq!(implementation.to_string(), expected.to_string());
   }
 
+  #[test]
+  fn default_and_fixed_attributes() {
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    let attribute = Attribute {
+      name: Some("language".to_string()),
+      kind: Some("xs:string".to_string()),
+      default: Some("en".to_string()),
+      fixed: None,
+      reference: None,
+      required: Required::Optional,
+      simple_type: None,
+    };
+
+    let implementation = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap();
+
+    let expected = TokenStream::from_str(
+      r#"
+        #[yaserde(attribute = true)]
+        #[xml_schema(attribute = "language")]
+        #[xml_schema(default = "en", value = <String> ::from("en"))]
+        pub language: String,
+      "#,
+    )
+    .unwrap();
+
+    assert_eq!(implementation.to_string(), expected.to_string());
+
+    let attribute = Attribute {
+      fixed: Some("fr".to_string()),
+      ..attribute
+    };
+
+    let error = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "default and fixed can not be both present"
+    );
+  }
+
   #[test]
   fn type_attribute() {
     let attribute = Attribute {
       name: Some("type".to_string()),
       kind: Some("xs:string".to_string()),
+      default: None,
+      fixed: None,
       reference: None,
       required: Required::Optional,
       simple_type: None,
@@ -236,11 +319,14 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = attribute.implement(&TokenStream::new(), &None, &context);
+    let implementation = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap();
 
     let expected = TokenStream::from_str(
       r#"
         #[yaserde(attribute = true, rename="type")]
+        #[xml_schema(attribute = "type")]
         pub kind: Option<String> ,
       "#,
     )
@@ -254,6 +340,8 @@ mod tests {
     let attribute = Attribute {
       name: Some("type".to_string()),
       kind: None,
+      default: None,
+      fixed: None,
       reference: Some("MyType".to_string()),
       required: Required::Optional,
       simple_type: None,
@@ -263,11 +351,14 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = attribute.implement(&TokenStream::new(), &None, &context);
+    let implementation = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap();
 
     let expected = TokenStream::from_str(
       r#"
         #[yaserde(attribute = true, rename="type")]
+        #[xml_schema(attribute = "type")]
         pub kind: Option<MyType> ,
       "#,
     )
@@ -277,11 +368,12 @@ mod tests {
   }
 
   #[test]
-  #[should_panic]
   fn bad_type_attribute() {
     let attribute = Attribute {
       name: Some("type".to_string()),
       kind: None,
+      default: None,
+      fixed: None,
       reference: None,
       required: Required::Optional,
       simple_type: None,
@@ -291,7 +383,99 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    attribute.implement(&TokenStream::new(), &None, &context);
+    let error = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "attribute requires exactly one of type, ref or simpleType"
+    );
+  }
+
+  #[test]
+  fn checked_default_values() {
+    let mut context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+    let unit: SimpleType = hifa_yaserde::de::from_str(
+      r#"
+      <xs:simpleType name="Unit" xmlns:xs="http://www.w3.org/2001/XMLSchema">
+        <xs:restriction base="xs:string">
+          <xs:enumeration value="mm"/>
+          <xs:enumeration value="cm"/>
+        </xs:restriction>
+      </xs:simpleType>
+    "#,
+    )
+    .unwrap();
+    context.add_simple_types([&unit]);
+
+    let attribute = Attribute {
+      name: Some("unit".to_string()),
+      kind: Some("Unit".to_string()),
+      default: Some("cm".to_string()),
+      fixed: None,
+      reference: None,
+      required: Required::Optional,
+      simple_type: None,
+    };
+    assert!(attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .is_ok());
+
+    let attribute = Attribute {
+      default: Some("km".to_string()),
+      ..attribute
+    };
+    let error = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      r#"restriction: the default value "km" is not one of the enumeration values"#
+    );
+
+    // a value which can not be written as a constant is parsed at runtime
+    let attribute = Attribute {
+      kind: Some("xs:date".to_string()),
+      default: Some("2024-01-01".to_string()),
+      ..attribute
+    };
+    let implementation = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap();
+    assert_eq!(
+      implementation.to_string(),
+      "# [yaserde (attribute = true)] # [xml_schema (attribute = \"unit\")] \
+# [xml_schema (default = \"2024-01-01\")] pub unit : hifa_xml_schema :: temporal :: Date ,"
+    );
+
+    let attribute = Attribute {
+      kind: Some("xs:int".to_string()),
+      default: Some("many".to_string()),
+      ..attribute
+    };
+    let error = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      r#"invalid default value "many" for the type i32: invalid digit found in string"#
+    );
+
+    context.set_mode(crate::xsd::Mode::Lenient);
+    let implementation = attribute
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap();
+    assert_eq!(
+      implementation.to_string(),
+      "# [yaserde (attribute = true)] # [xml_schema (attribute = \"unit\")] \
+pub unit : Option < i32 > ,"
+    );
+    assert_eq!(
+      context.skipped()[0].to_string(),
+      r#"attribute "unit": invalid default value "many" for the type i32: invalid digit found in string"#
+    );
   }
 
   #[test]
@@ -299,6 +483,8 @@ mod tests {
     let attribute = Attribute {
       name: None,
       kind: Some("xs:string".to_string()),
+      default: None,
+      fixed: None,
       reference: None,
       required: Required::Optional,
       simple_type: None,
@@ -310,6 +496,7 @@ mod tests {
 
     let implementation = attribute
       .implement(&TokenStream::new(), &None, &context)
+      .unwrap()
       .to_string();
     assert!(implementation.is_empty());
   }
@@ -1,9 +1,9 @@
-use crate::xsd::attribute::Attribute;
+use crate::xsd::{attribute::Attribute, Error, Result, XsdContext};
 
+// without prefix, the root element is not confused with a nested `attributeGroup`
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(
   rename = "attributeGroup",
-  prefix = "xs",
   namespaces = {
     "xs" = "http://www.w3.org/2001/XMLSchema"
   }
@@ -11,10 +11,129 @@ use crate::xsd::attribute::Attribute;
 pub struct AttributeGroup {
   #[yaserde(prefix = "xs", attribute = true)]
   pub name: Option<String>,
-  #[yaserde(rename = "ref", attribute = true)]
-  pub reference: String,
   #[yaserde(rename = "attribute")]
   pub attributes: Vec<Attribute>,
-  // #[yaserde(rename = "attributeGroup")]
-  // pub attribute_group: Vec<AttributeGroup>,
+  #[yaserde(rename = "attributeGroup")]
+  pub attribute_groups: Vec<AttributeGroupReference>,
+}
+
+/// `<xs:attributeGroup ref="..."/>`, in a type or an attribute group.
+#[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
+#[yaserde(
+  rename = "attributeGroup",
+  prefix = "xs",
+  namespaces = {
+    "xs" = "http://www.w3.org/2001/XMLSchema"
+  }
+)]
+pub struct AttributeGroupReference {
+  #[yaserde(rename = "ref", attribute = true)]
+  pub reference: String,
+}
+
+impl AttributeGroup {
+  /// The attributes of the group, followed by the ones of the nested references.
+  fn get_attributes(
+    &self,
+    context: &XsdContext,
+    visited: &mut Vec<String>,
+  ) -> Result<Vec<Attribute>> {
+    let mut attributes = self.attributes.clone();
+    for attribute_group in &self.attribute_groups {
+      attributes.extend(attribute_group.get_attributes(context, visited)?);
+    }
+    Ok(attributes)
+  }
+}
+
+impl AttributeGroupReference {
+  fn get_attributes(
+    &self,
+    context: &XsdContext,
+    visited: &mut Vec<String>,
+  ) -> Result<Vec<Attribute>> {
+    let definition = context
+      .get_attribute_group(&self.reference)
+      .ok_or_else(|| Error::new(format!("attributeGroup {:?} not found", self.reference)))?;
+    let name = definition.name.clone().unwrap_or_default();
+
+    if visited.contains(&name) {
+      return Err(Error::new(format!(
+        "circular reference of attributeGroup {name:?}"
+      )));
+    }
+
+    visited.push(name);
+    let attributes = definition.get_attributes(context, visited)?;
+    visited.pop();
+
+    Ok(attributes)
+  }
+}
+
+/// The attributes of a type, with the ones of its `xs:attributeGroup` references.
+pub fn resolve_attributes(
+  attributes: &[Attribute],
+  attribute_groups: &[AttributeGroupReference],
+  context: &XsdContext,
+) -> Result<Vec<Attribute>> {
+  let mut resolved = attributes.to_vec();
+  for attribute_group in attribute_groups {
+    resolved.extend(
+      attribute_group
+        .get_attributes(context, &mut vec![])
+        .map_err(|error| {
+          error.with_component("attributeGroup", Some(&attribute_group.reference))
+        })?,
+    );
+  }
+  Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hifa_yaserde::de::from_str;
+
+  #[test]
+  fn nested_attribute_groups() {
+    let mut context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    let groups: Vec<AttributeGroup> = [
+      r#"<attributeGroup name="core"><attribute name="id" type="xs:ID"/><attributeGroup ref="i18n"/></attributeGroup>"#,
+      r#"<attributeGroup name="i18n"><attribute name="lang" type="xs:language"/></attributeGroup>"#,
+      r#"<attributeGroup name="loop"><attributeGroup ref="loop"/></attributeGroup>"#,
+    ]
+    .iter()
+    .map(|xml| from_str(xml).unwrap())
+    .collect();
+    context.add_attribute_groups(&groups);
+
+    let reference = |name: &str| AttributeGroupReference {
+      reference: name.to_string(),
+    };
+    let names = |groups: &[AttributeGroupReference]| {
+      resolve_attributes(&[], groups, &context).map(|attributes| {
+        attributes
+          .into_iter()
+          .map(|attribute| attribute.name.unwrap_or_default())
+          .collect::<Vec<_>>()
+      })
+    };
+
+    assert_eq!(
+      names(&[reference("core")]).unwrap(),
+      vec!["id".to_string(), "lang".to_string()]
+    );
+    assert_eq!(
+      names(&[reference("loop")]).unwrap_err().to_string(),
+      r#"attributeGroup "loop": circular reference of attributeGroup "loop""#
+    );
+    assert_eq!(
+      names(&[reference("unknown")]).unwrap_err().to_string(),
+      r#"attributeGroup "unknown": attributeGroup "unknown" not found"#
+    );
+  }
 }
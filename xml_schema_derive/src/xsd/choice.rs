@@ -1,30 +1,40 @@
 use crate::xsd::{
-  annotation::Annotation, attribute::Attribute, element::Element, max_occurences::MaxOccurences,
-  Implementation, XsdContext,
+  element::Element,
+  group::{self, Group},
+  max_occurences::MaxOccurences,
+  rust_types_mapping::RustTypesMapping,
+  sequence::{self, Particle, Sequence},
+  Error, Implementation, Result, XsdContext,
 };
-use proc_macro2::TokenStream;
+use heck::ToUpperCamelCase;
+use hifa_yaserde::{de::Deserializer, YaDeserialize};
+use proc_macro2::{Span, TokenStream};
+use std::io::Read;
+use syn::Ident;
 
-#[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
-#[yaserde(
-    rename = "choice",
-    prefix = "xs",
-    namespaces = {
-    "xs" = "http://www.w3.org/2001/XMLSchema"
-  }
-  )]
+#[derive(Clone, Default, Debug, PartialEq)]
 pub struct Choice {
-  #[yaserde(attribute = true)]
-  pub id: Option<String>,
-  #[yaserde(rename = "attribute")]
-  pub attributes: Vec<Attribute>,
-  #[yaserde(rename = "minOccurs", attribute = true)]
   pub min_occurences: Option<u64>,
-  #[yaserde(rename = "maxOccurs", attribute = true)]
   pub max_occurences: Option<MaxOccurences>,
-  #[yaserde(rename = "annotation")]
-  pub annotation: Option<Annotation>,
-  #[yaserde(rename = "element")]
-  pub elements: Vec<Element>,
+  /// The particles in their declared order, which is the order of the variants.
+  pub particles: Vec<Particle>,
+}
+
+// as for a sequence, the particles of the different kinds are read in a single list
+impl YaDeserialize for Choice {
+  fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> std::result::Result<Self, String> {
+    let Sequence {
+      min_occurences,
+      max_occurences,
+      particles,
+    } = sequence::deserialize_particles(reader, "choice")?;
+
+    Ok(Choice {
+      min_occurences,
+      max_occurences,
+      particles,
+    })
+  }
 }
 
 impl Implementation for Choice {
@@ -33,48 +43,681 @@ impl Implementation for Choice {
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     let elements: TokenStream = self
-      .elements
-      .iter()
-      .map(|element| element.implement(&namespace_definition, prefix, context))
-      .collect();
+      .elements()
+      .map(|element| {
+        element
+          .implement(namespace_definition, prefix, context)
+          .map_err(|error| error.with_component("element", Some(&element.name)))
+      })
+      .collect::<Result<_>>()?;
 
-    quote! {
+    Ok(quote! {
       #elements
-    }
+    })
   }
 }
 
 impl Choice {
+  pub fn elements(&self) -> impl Iterator<Item = &Element> {
+    self.particles.iter().filter_map(|particle| match particle {
+      Particle::Element(element) => Some(element.as_ref()),
+      _ => None,
+    })
+  }
+
+  pub fn choices(&self) -> impl Iterator<Item = &Choice> {
+    self.particles.iter().filter_map(|particle| match particle {
+      Particle::Choice(choice) => Some(choice),
+      _ => None,
+    })
+  }
+
+  pub fn groups_mut(&mut self) -> impl Iterator<Item = &mut Group> {
+    self
+      .particles
+      .iter_mut()
+      .filter_map(|particle| match particle {
+        Particle::Group(group) => Some(group),
+        _ => None,
+      })
+  }
+
+  pub fn sequences(&self) -> impl Iterator<Item = &Sequence> {
+    self.particles.iter().filter_map(|particle| match particle {
+      Particle::Sequence(sequence) => Some(sequence),
+      _ => None,
+    })
+  }
+
   pub fn get_sub_types_implementation(
     &self,
     context: &XsdContext,
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
-  ) -> TokenStream {
-    self
-      .elements
-      .iter()
-      .map(|element| element.get_subtypes_implementation(namespace_definition, prefix, context))
-      .collect()
+    enum_name: &Ident,
+  ) -> Result<TokenStream> {
+    let elements_sub_types: TokenStream = self
+      .elements()
+      .map(|element| {
+        element
+          .get_subtypes_implementation(namespace_definition, prefix, context)
+          .map_err(|error| error.with_component("element", Some(&element.name)))
+      })
+      .collect::<Result<_>>()?;
+
+    let enum_implementation = self.implement_enum(context, prefix, enum_name)?;
+
+    let sequences_sub_types: TokenStream = self
+      .sequences()
+      .enumerate()
+      .map(|(index, sequence)| {
+        let (_variant_name, struct_name) = sequence::sequence_names(enum_name, index);
+        let structure = sequence.get_struct_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &struct_name,
+        )?;
+        let sub_types = sequence.get_sub_types_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &struct_name,
+        )?;
+
+        Ok(quote!(
+          #structure
+          #sub_types
+        ))
+      })
+      .collect::<Result<TokenStream>>()
+      .map_err(|error| error.with_component("sequence", None))?;
+
+    let choices_sub_types: TokenStream = self
+      .choices()
+      .enumerate()
+      .map(|(index, choice)| {
+        let (_variant_name, nested_enum_name) = choice_names(enum_name, index);
+        choice
+          .get_sub_types_implementation(context, namespace_definition, prefix, &nested_enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .collect::<Result<_>>()?;
+
+    Ok(quote!(
+      #elements_sub_types
+      #enum_implementation
+      #sequences_sub_types
+      #choices_sub_types
+    ))
+  }
+
+  /// The enum of the choice, with the types of its nested sequences and choices.
+  pub fn get_choices_implementation(
+    &self,
+    context: &XsdContext,
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
+    enum_name: &Ident,
+  ) -> Result<TokenStream> {
+    let enum_implementation = self.implement_enum(context, prefix, enum_name)?;
+
+    let sequences: TokenStream = self
+      .sequences()
+      .enumerate()
+      .map(|(index, sequence)| {
+        let (_variant_name, struct_name) = sequence::sequence_names(enum_name, index);
+        let structure = sequence.get_struct_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &struct_name,
+        )?;
+        let choices = sequence.get_choices_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &struct_name,
+        )?;
+
+        Ok(quote!(
+          #structure
+          #choices
+        ))
+      })
+      .collect::<Result<TokenStream>>()
+      .map_err(|error| error.with_component("sequence", None))?;
+
+    let choices: TokenStream = self
+      .choices()
+      .enumerate()
+      .map(|(index, choice)| {
+        let (_variant_name, nested_enum_name) = choice_names(enum_name, index);
+        choice
+          .get_choices_implementation(context, namespace_definition, prefix, &nested_enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .collect::<Result<_>>()?;
+
+    Ok(quote!(
+      #enum_implementation
+      #sequences
+      #choices
+    ))
   }
 
   pub fn get_field_implementation(
+    &self,
+    context: &XsdContext,
+    field_name: &str,
+    enum_name: &Ident,
+  ) -> Result<TokenStream> {
+    let elements = group::elements_attribute(&self.get_elements(context, &mut vec![])?);
+
+    let field_type = if self.is_multiple() {
+      quote!(Vec<#enum_name>)
+    } else if self.min_occurences == Some(0) {
+      quote!(Option<#enum_name>)
+    } else {
+      quote!(#enum_name)
+    };
+
+    let field_name = if self.is_multiple() {
+      format_ident!("{}_list", field_name)
+    } else {
+      format_ident!("{}", field_name)
+    };
+
+    Ok(quote!(
+      #[yaserde(flatten = true)]
+      #elements
+      pub #field_name: #field_type,
+    ))
+  }
+
+  /// The choice is generated as an enum with a variant per element, group reference, nested
+  /// sequence (ie. `Sequence` holding `{Enum}Sequence`) and nested choice (ie. `Choice` holding
+  /// `{Enum}Choice`).
+  /// The branches are read from the children of the parent element, so the order is preserved.
+  pub fn implement_enum(
     &self,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
-    let multiple = matches!(self.min_occurences, Some(min_occurences) if min_occurences > 1)
-      || matches!(self.max_occurences, Some(MaxOccurences::Unbounded))
-      || matches!(self.max_occurences, Some(MaxOccurences::Number{value}) if value > 1);
+    enum_name: &Ident,
+  ) -> Result<TokenStream> {
+    // the nested sequences and choices are numbered by kind
+    let mut sequence_index = 0;
+    let mut choice_index = 0;
+    let variants = self
+      .particles
+      .iter()
+      .map(|particle| match particle {
+        Particle::Element(element) => ChoiceVariant::new(element, context, prefix)
+          .map_err(|error| error.with_component("element", Some(&element.name))),
+        Particle::Group(group) => ChoiceVariant::from_group(group, context, prefix)
+          .map_err(|error| error.with_component("group", group.reference.as_deref())),
+        Particle::Sequence(sequence) => {
+          let (variant_name, struct_name) = sequence::sequence_names(enum_name, sequence_index);
+          sequence_index += 1;
+          ChoiceVariant::from_sequence(sequence, context, &variant_name, struct_name)
+            .map_err(|error| error.with_component("sequence", None))
+        }
+        Particle::Choice(choice) => {
+          let (variant_name, nested_enum_name) = choice_names(enum_name, choice_index);
+          choice_index += 1;
+          ChoiceVariant::from_choice(choice, context, &variant_name, nested_enum_name)
+            .map_err(|error| error.with_component("choice", None))
+        }
+      })
+      .collect::<Result<Vec<_>>>()?;
 
-    let optional = !multiple;
+    let Some(first_variant) = variants.first() else {
+      return Err(Error::new(
+        "choice without element or group is not supported",
+      ));
+    };
+    let first_variant_name = &first_variant.name;
 
-    self
-      .elements
+    let definitions = variants.iter().map(|variant| {
+      let name = &variant.name;
+      let rust_type = &variant.rust_type;
+      quote!(#name(#rust_type),)
+    });
+
+    let deserializations: TokenStream = variants
       .iter()
-      .map(|element| element.get_field_implementation(context, prefix, multiple, optional))
-      .collect()
+      .map(|variant| variant.implement_deserialize(enum_name))
+      .collect();
+
+    let serializations: TokenStream = variants
+      .iter()
+      .map(|variant| variant.implement_serialize(enum_name))
+      .collect();
+
+    let list_name = format_ident!("{}List", enum_name);
+
+    Ok(quote!(
+      #[derive(Clone, Debug, PartialEq)]
+      pub enum #enum_name {
+        #(#definitions)*
+      }
+
+      impl Default for #enum_name {
+        fn default() -> Self {
+          #enum_name::#first_variant_name(Default::default())
+        }
+      }
+
+      impl #enum_name {
+        /// Read a choice from the current child element, `None` when it is not an element of the choice.
+        fn deserialize_choice<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Option<Self>, String> {
+          let name = loop {
+            match reader.peek()? {
+              xml::reader::XmlEvent::StartElement{name, ..} => break name.local_name.clone(),
+              xml::reader::XmlEvent::EndElement{..} | xml::reader::XmlEvent::EndDocument => return Ok(None),
+              _ => {
+                reader.next_event()?;
+              }
+            }
+          };
+
+          let choice = match name.as_str() {
+            #deserializations
+            _ => return Ok(None),
+          };
+
+          Ok(Some(choice))
+        }
+
+        fn deserialize_choices<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>, multiple: bool) -> Result<Vec<Self>, String> {
+          if matches!(reader.peek()?, xml::reader::XmlEvent::StartElement{..}) {
+            reader.next_event()?;
+          }
+
+          let mut choices = vec![];
+
+          loop {
+            if let Some(choice) = #enum_name::deserialize_choice(reader)? {
+              choices.push(choice);
+              if !multiple {
+                break;
+              }
+            } else if matches!(reader.peek()?, xml::reader::XmlEvent::StartElement{..}) {
+              reader.next_event()?;
+              reader.skip_element(|_event| {})?;
+            } else {
+              break;
+            }
+          }
+
+          Ok(choices)
+        }
+      }
+
+      impl hifa_yaserde::YaDeserialize for #enum_name {
+        fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
+          #enum_name::deserialize_choices(reader, false)?
+            .pop()
+            .ok_or_else(|| format!("Missing element for {}", stringify!(#enum_name)))
+        }
+      }
+
+      impl hifa_yaserde::YaSerialize for #enum_name {
+        fn serialize<W: std::io::Write>(&self, writer: &mut hifa_yaserde::ser::Serializer<W>) -> Result<(), String> {
+          match self {
+            #serializations
+          }
+
+          Ok(())
+        }
+
+        fn serialize_attributes(&self, source_attributes: Vec<xml::attribute::OwnedAttribute>, source_namespace: xml::namespace::Namespace) -> Result<(Vec<xml::attribute::OwnedAttribute>, xml::namespace::Namespace), String> {
+          Ok((source_attributes, source_namespace))
+        }
+      }
+
+      /// Repeated or optional choices, read at once from the children of the parent element.
+      #[doc(hidden)]
+      #[derive(Default)]
+      pub struct #list_name(Vec<#enum_name>);
+
+      impl hifa_yaserde::YaDeserialize for #list_name {
+        fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
+          #enum_name::deserialize_choices(reader, true).map(#list_name)
+        }
+      }
+
+      impl From<#list_name> for Vec<#enum_name> {
+        fn from(list: #list_name) -> Self {
+          list.0
+        }
+      }
+
+      impl From<#list_name> for Option<#enum_name> {
+        fn from(list: #list_name) -> Self {
+          list.0.into_iter().next()
+        }
+      }
+    ))
+  }
+
+  /// The local names of the elements of the choice, with whether they can be repeated.
+  pub fn get_elements(
+    &self,
+    context: &XsdContext,
+    visited: &mut Vec<String>,
+  ) -> Result<Vec<(String, bool)>> {
+    let mut elements = vec![];
+    for particle in &self.particles {
+      match particle {
+        Particle::Element(element) => elements.push(group::element_entry(element)),
+        Particle::Group(group) => elements.extend(group.get_elements(context, visited)?),
+        Particle::Sequence(sequence) => elements.extend(group::repeat_elements(
+          sequence.get_elements(context, visited)?,
+          sequence.is_multiple(),
+        )),
+        Particle::Choice(choice) => elements.extend(choice.get_elements(context, visited)?),
+      }
+    }
+    Ok(group::repeat_elements(elements, self.is_multiple()))
+  }
+
+  fn is_multiple(&self) -> bool {
+    matches!(self.min_occurences, Some(min_occurences) if min_occurences > 1)
+      || matches!(self.max_occurences, Some(MaxOccurences::Unbounded))
+      || matches!(self.max_occurences, Some(MaxOccurences::Number{value}) if value > 1)
+  }
+}
+
+/// Name of the field and of the enum of a choice, the choices are numbered from the second one.
+pub fn choice_names(struct_name: &Ident, index: usize) -> (String, Ident) {
+  if index == 0 {
+    ("choice".to_string(), format_ident!("{}Choice", struct_name))
+  } else {
+    (
+      format!("choice_{}", index + 1),
+      format_ident!("{}Choice{}", struct_name, index + 1),
+    )
+  }
+}
+
+/// A branch of a choice, ie. a variant of the generated enum.
+struct ChoiceVariant {
+  name: Ident,
+  xml_name: String,
+  rust_type: TokenStream,
+  item_type: TokenStream,
+  multiple: bool,
+  kind: VariantKind,
+}
+
+/// How the value of a variant is read and written.
+enum VariantKind {
+  /// An element, read from its text content when it is `simple`.
+  Element { simple: bool },
+  /// A group reference or a nested sequence, read from the following elements of the group
+  /// with `hifa_xml_schema::group`.
+  Group(Vec<(String, bool)>),
+  /// A nested choice, read with its enum.
+  Choice(Vec<(String, bool)>),
+}
+
+impl ChoiceVariant {
+  fn new(element: &Element, context: &XsdContext, prefix: &Option<String>) -> Result<Self> {
+    let xml_name = match element.get_refers() {
+      Some(refers) if element.name.is_empty() => refers,
+      _ => &element.name,
+    };
+    let local_name = xml_name.split(':').next_back().unwrap_or_default();
+    if local_name.is_empty() {
+      return Err(Error::new("missing name of element"));
+    }
+
+    let name = Ident::new(
+      &local_name.replace('.', "_").to_upper_camel_case(),
+      Span::call_site(),
+    );
+
+    let item_type = element.get_rust_type(context, element.get_refers())?;
+    let multiple = element.is_multiple();
+    let rust_type = if multiple {
+      quote!(Vec<#item_type>)
+    } else {
+      item_type.clone()
+    };
+
+    // a QName is resolved against the namespaces of its element by its own deserialization
+    let simple = element.has_simple_content(context)
+      && !element
+        .kind
+        .as_deref()
+        .is_some_and(|kind| RustTypesMapping::is_xs_qname(context, kind));
+
+    let xml_name = match prefix {
+      Some(prefix) => format!("{prefix}:{local_name}"),
+      None => local_name.to_string(),
+    };
+
+    Ok(ChoiceVariant {
+      name,
+      xml_name,
+      rust_type,
+      item_type,
+      multiple,
+      kind: VariantKind::Element { simple },
+    })
+  }
+
+  /// The variant of a group reference holds the structure of the group.
+  fn from_group(group: &Group, context: &XsdContext, prefix: &Option<String>) -> Result<Self> {
+    let reference = group.reference.as_deref().unwrap_or_default();
+    let elements = group.get_elements(context, &mut vec![])?;
+    let name = group::group_struct_name(reference);
+    let item_type = group.get_rust_type(context, prefix)?;
+
+    Ok(ChoiceVariant::nested(
+      name.to_string(),
+      name,
+      item_type,
+      group.is_multiple(),
+      VariantKind::Group(elements),
+    ))
+  }
+
+  fn from_sequence(
+    sequence: &Sequence,
+    context: &XsdContext,
+    variant_name: &str,
+    struct_name: Ident,
+  ) -> Result<Self> {
+    let elements = sequence.get_elements(context, &mut vec![])?;
+    if elements.is_empty() {
+      return Err(Error::new("sequence without element is not supported"));
+    }
+
+    Ok(ChoiceVariant::nested(
+      struct_name.to_string(),
+      format_ident!("{}", variant_name.to_upper_camel_case()),
+      quote!(#struct_name),
+      sequence.is_multiple(),
+      VariantKind::Group(elements),
+    ))
+  }
+
+  fn from_choice(
+    choice: &Choice,
+    context: &XsdContext,
+    variant_name: &str,
+    enum_name: Ident,
+  ) -> Result<Self> {
+    let elements = choice.get_elements(context, &mut vec![])?;
+
+    Ok(ChoiceVariant::nested(
+      enum_name.to_string(),
+      format_ident!("{}", variant_name.to_upper_camel_case()),
+      quote!(#enum_name),
+      choice.is_multiple(),
+      VariantKind::Choice(elements),
+    ))
+  }
+
+  /// A variant holding a structure or an enum, `xml_name` is its name.
+  fn nested(
+    xml_name: String,
+    name: Ident,
+    item_type: TokenStream,
+    multiple: bool,
+    kind: VariantKind,
+  ) -> Self {
+    let rust_type = if multiple {
+      quote!(Vec<#item_type>)
+    } else {
+      item_type.clone()
+    };
+
+    ChoiceVariant {
+      xml_name,
+      name,
+      rust_type,
+      item_type,
+      multiple,
+      kind,
+    }
+  }
+
+  fn implement_deserialize(&self, enum_name: &Ident) -> TokenStream {
+    let name = &self.name;
+    let item_type = &self.item_type;
+
+    let simple = match &self.kind {
+      VariantKind::Element { simple } => *simple,
+      VariantKind::Group(elements) => {
+        let xml_name = &self.xml_name;
+        let names = elements.iter().map(|(name, _)| name);
+        let elements = group::elements_tokens(elements);
+        let read_group = quote!(
+          hifa_xml_schema::group::read_group::<_, #item_type>(reader, #xml_name, #elements)?
+        );
+
+        return if self.multiple {
+          quote!(
+            #(#names)|* => {
+              let mut values = vec![];
+              while matches!(reader.peek()?, xml::reader::XmlEvent::StartElement{name, ..} if hifa_xml_schema::group::is_group_element(&name.local_name, #elements)) {
+                values.push(#read_group);
+              }
+              #enum_name::#name(values)
+            }
+          )
+        } else {
+          quote!(
+            #(#names)|* => #enum_name::#name(#read_group),
+          )
+        };
+      }
+      VariantKind::Choice(elements) => {
+        let names = elements.iter().map(|(name, _)| name);
+
+        return if self.multiple {
+          quote!(
+            #(#names)|* => {
+              let mut values = vec![];
+              while let Some(value) = <#item_type>::deserialize_choice(reader)? {
+                values.push(value);
+              }
+              #enum_name::#name(values)
+            }
+          )
+        } else {
+          quote!(
+            #(#names)|* => {
+              let value = <#item_type>::deserialize_choice(reader)?
+                .ok_or_else(|| format!("Missing element for {}", stringify!(#item_type)))?;
+              #enum_name::#name(value)
+            }
+          )
+        };
+      }
+    };
+
+    let local_name = self.xml_name.split(':').next_back().unwrap_or_default();
+
+    let read_value = if simple {
+      quote!({
+        reader.next_event()?;
+        let content = match reader.peek()?.clone() {
+          xml::reader::XmlEvent::Characters(content) => {
+            reader.next_event()?;
+            content
+          }
+          _ => String::new(),
+        };
+        content.parse::<#item_type>().map_err(|e| e.to_string())?
+      })
+    } else {
+      quote!(<#item_type as hifa_yaserde::YaDeserialize>::deserialize(reader)?)
+    };
+
+    if self.multiple {
+      quote!(
+        #local_name => {
+          let mut values = vec![];
+          while matches!(reader.peek()?, xml::reader::XmlEvent::StartElement{name, ..} if name.local_name == #local_name) {
+            values.push(#read_value);
+            reader.next_event()?;
+          }
+          #enum_name::#name(values)
+        }
+      )
+    } else {
+      quote!(
+        #local_name => {
+          let value = #read_value;
+          reader.next_event()?;
+          #enum_name::#name(value)
+        }
+      )
+    }
+  }
+
+  fn implement_serialize(&self, enum_name: &Ident) -> TokenStream {
+    let name = &self.name;
+    let xml_name = &self.xml_name;
+
+    let write_value = match self.kind {
+      VariantKind::Group(_) | VariantKind::Choice(_) => quote!(
+        writer.set_start_event_name(None);
+        writer.set_skip_start_end(true);
+        hifa_yaserde::YaSerialize::serialize(value, writer)?;
+      ),
+      VariantKind::Element { simple: true } => quote!(
+        writer.write(xml::writer::XmlEvent::start_element(#xml_name)).map_err(|e| e.to_string())?;
+        writer.write(xml::writer::XmlEvent::characters(&value.to_string())).map_err(|e| e.to_string())?;
+        writer.write(xml::writer::XmlEvent::end_element()).map_err(|e| e.to_string())?;
+      ),
+      VariantKind::Element { simple: false } => quote!(
+        writer.set_start_event_name(Some(#xml_name.to_string()));
+        writer.set_skip_start_end(false);
+        hifa_yaserde::YaSerialize::serialize(value, writer)?;
+      ),
+    };
+
+    if self.multiple {
+      quote!(
+        #enum_name::#name(values) => {
+          for value in values {
+            #write_value
+          }
+        }
+      )
+    } else {
+      quote!(
+        #enum_name::#name(value) => {
+          #write_value
+        }
+      )
+    }
   }
 }
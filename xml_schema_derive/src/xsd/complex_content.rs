@@ -1,5 +1,6 @@
-use crate::xsd::{extension::Extension, xsd_context::XsdContext};
+use crate::xsd::{extension::Extension, xsd_context::XsdContext, Error, Result};
 use proc_macro2::TokenStream;
+use syn::Ident;
 
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(prefix = "xs", namespaces = {
@@ -15,12 +16,14 @@ impl ComplexContent {
     namespace_definition: &TokenStream,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
     self
       .extension
       .as_ref()
-      .unwrap()
-      .get_field_implementation(namespace_definition, context, prefix)
+      .ok_or_else(|| Error::new("only extension is supported in complexContent"))?
+      .get_field_implementation(namespace_definition, context, prefix, struct_name)
+      .map_err(|error| error.with_component("extension", None))
   }
 
   pub fn get_sub_type_implementation(
@@ -28,11 +31,13 @@ impl ComplexContent {
     namespace_definition: &TokenStream,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
     self
       .extension
       .as_ref()
-      .unwrap()
-      .get_sub_type_implementation(namespace_definition, context, prefix)
+      .ok_or_else(|| Error::new("only extension is supported in complexContent"))?
+      .get_sub_type_implementation(namespace_definition, context, prefix, struct_name)
+      .map_err(|error| error.with_component("extension", None))
   }
 }
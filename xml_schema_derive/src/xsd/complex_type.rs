@@ -1,10 +1,22 @@
 use crate::xsd::{
-  annotation::Annotation, attribute::Attribute, choice::Choice, complex_content::ComplexContent,
-  sequence::Sequence, simple_content::SimpleContent, Implementation, XsdContext,
+  all::All,
+  annotation::Annotation,
+  attribute::Attribute,
+  attribute_group::{resolve_attributes, AttributeGroupReference},
+  choice::{self, Choice},
+  complex_content::ComplexContent,
+  group::Group,
+  sequence::Sequence,
+  simple_content::SimpleContent,
+  Error, Implementation, Result, XsdContext,
 };
-use heck::ToUpperCamelCase;
+use heck::{ToShoutySnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
-use syn::Ident;
+use quote::ToTokens;
+use syn::{
+  parse::Parser, punctuated::Punctuated, token::Comma, Field, FieldsNamed, GenericArgument, Ident,
+  PathArguments, Type, TypePath,
+};
 
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(
@@ -19,8 +31,12 @@ pub struct ComplexType {
   pub name: String,
   #[yaserde(rename = "attribute")]
   pub attributes: Vec<Attribute>,
+  #[yaserde(rename = "attributeGroup")]
+  pub attribute_groups: Vec<AttributeGroupReference>,
   #[yaserde(rename = "sequence")]
   pub sequence: Option<Sequence>,
+  #[yaserde(rename = "all")]
+  pub all: Option<All>,
   #[yaserde(rename = "simpleContent")]
   pub simple_content: Option<SimpleContent>,
   #[yaserde(rename = "complexContent")]
@@ -29,6 +45,8 @@ pub struct ComplexType {
   pub annotation: Option<Annotation>,
   #[yaserde(rename = "choice")]
   pub choice: Option<Choice>,
+  #[yaserde(rename = "group")]
+  pub group: Option<Group>,
 }
 
 impl Implementation for ComplexType {
@@ -37,7 +55,7 @@ impl Implementation for ComplexType {
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     let struct_name = Ident::new(
       &self.name.replace('.', "_").to_upper_camel_case(),
       Span::call_site(),
@@ -46,87 +64,158 @@ impl Implementation for ComplexType {
     let sequence = self
       .sequence
       .as_ref()
-      .map(|sequence| sequence.implement(namespace_definition, prefix, context))
+      .map(Sequence::as_content)
+      .map(|sequence| {
+        sequence
+          .get_field_implementation(context, prefix, &struct_name)
+          .map_err(|error| error.with_component("sequence", None))
+      })
+      .transpose()?
+      .unwrap_or_default();
+
+    let all = self
+      .all
+      .as_ref()
+      .map(|all| {
+        all
+          .get_field_implementation(context, prefix)
+          .map_err(|error| error.with_component("all", None))
+      })
+      .transpose()?
       .unwrap_or_default();
 
     log::info!("Generate simple content");
     let simple_content = self
       .simple_content
       .as_ref()
-      .map(|simple_content| simple_content.implement(namespace_definition, prefix, context))
+      .map(|simple_content| {
+        simple_content
+          .implement(namespace_definition, prefix, context)
+          .map_err(|error| error.with_component("simpleContent", None))
+      })
+      .transpose()?
       .unwrap_or_default();
 
     let complex_content = self
       .complex_content
       .as_ref()
       .map(|complex_content| {
-        let complex_content_type =
-          complex_content.get_field_implementation(namespace_definition, context, prefix);
-        quote!(
-          #complex_content_type
-        )
+        complex_content
+          .get_field_implementation(namespace_definition, context, prefix, &struct_name)
+          .map_err(|error| error.with_component("complexContent", None))
       })
+      .transpose()?
       .unwrap_or_default();
 
     let complex_content_sub_types = self
       .complex_content
       .as_ref()
       .map(|complex_content| {
-        let complex_content_type =
-          complex_content.get_sub_type_implementation(namespace_definition, context, prefix);
-        quote!(
-          #complex_content_type
-        )
+        complex_content
+          .get_sub_type_implementation(namespace_definition, context, prefix, &struct_name)
+          .map_err(|error| error.with_component("complexContent", None))
       })
+      .transpose()?
       .unwrap_or_default();
 
-    let attributes: TokenStream = self
-      .attributes
-      .iter()
-      .map(|attribute| attribute.implement(namespace_definition, prefix, context))
-      .collect();
+    let attributes: TokenStream =
+      resolve_attributes(&self.attributes, &self.attribute_groups, context)?
+        .iter()
+        .map(|attribute| {
+          attribute
+            .implement(namespace_definition, prefix, context)
+            .map_err(|error| error.with_component("attribute", attribute.name.as_deref()))
+        })
+        .collect::<Result<_>>()?;
 
     let sequence_sub_types = self
       .sequence
       .as_ref()
-      .map(|sequence| sequence.get_sub_types_implementation(context, namespace_definition, prefix))
+      .map(Sequence::as_content)
+      .map(|sequence| {
+        sequence
+          .get_sub_types_implementation(context, namespace_definition, prefix, &struct_name)
+          .map_err(|error| error.with_component("sequence", None))
+      })
+      .transpose()?
+      .unwrap_or_default();
+
+    let all_sub_types = self
+      .all
+      .as_ref()
+      .map(|all| {
+        all
+          .get_sub_types_implementation(context, namespace_definition, prefix)
+          .map_err(|error| error.with_component("all", None))
+      })
+      .transpose()?
       .unwrap_or_default();
 
     let docs = self
       .annotation
       .as_ref()
       .map(|annotation| annotation.implement(namespace_definition, prefix, context))
+      .transpose()?
       .unwrap_or_default();
 
+    let (choice_field_name, choice_enum_name) = choice::choice_names(&struct_name, 0);
+
     let choice_sub_types = self
       .choice
       .as_ref()
-      .map(|choice| choice.get_sub_types_implementation(context, &namespace_definition, prefix))
-      .unwrap_or_else(TokenStream::new);
+      .map(|choice| {
+        choice
+          .get_sub_types_implementation(context, namespace_definition, prefix, &choice_enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .transpose()?
+      .unwrap_or_default();
 
     let choice_field = self
       .choice
       .as_ref()
-      .map(|choice| choice.get_field_implementation(context, prefix))
-      .unwrap_or_else(TokenStream::new);
+      .map(|choice| {
+        choice
+          .get_field_implementation(context, &choice_field_name, &choice_enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .transpose()?
+      .unwrap_or_default();
 
-    quote! {
-      #docs
+    let group_field = self
+      .group
+      .as_ref()
+      .map(|group| {
+        group
+          .get_field_implementation(context, prefix)
+          .map_err(|error| error.with_component("group", group.reference.as_deref()))
+      })
+      .transpose()?
+      .unwrap_or_default();
 
-      #[derive(Clone, Debug, Default, PartialEq, hifa_yaserde_derive::YaDeserialize, hifa_yaserde_derive::YaSerialize)]
-      #namespace_definition
-      pub struct #struct_name {
+    let structure = implement_struct(
+      namespace_definition,
+      &struct_name,
+      quote!(
         #sequence
+        #all
         #simple_content
         #complex_content
         #choice_field
+        #group_field
         #attributes
-      }
+      ),
+    )?;
+
+    Ok(quote! {
+      #docs
+      #structure
 
       #sequence_sub_types
+      #all_sub_types
       #complex_content_sub_types
       #choice_sub_types
-    }
+    })
   }
 }
 
@@ -136,42 +225,720 @@ impl ComplexType {
     namespace_definition: &TokenStream,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
-    if self.sequence.is_some() {
-      self
-        .sequence
-        .as_ref()
-        .map(|sequence| sequence.get_field_implementation(context, prefix))
-        .unwrap_or_default()
-    } else if self.simple_content.is_some() {
-      self
-        .simple_content
-        .as_ref()
-        .map(|simple_content| {
-          simple_content.get_field_implementation(namespace_definition, context, prefix)
-        })
-        .unwrap_or_default()
-    } else if self.choice.is_some() {
-      self
-        .choice
-        .as_ref()
-        .map(|choice| choice.get_field_implementation(context, prefix))
-        .unwrap_or_else(TokenStream::new)
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    if let Some(sequence) = &self.sequence {
+      sequence
+        .as_content()
+        .get_field_implementation(context, prefix, struct_name)
+        .map_err(|error| error.with_component("sequence", None))
+    } else if let Some(all) = &self.all {
+      all
+        .get_field_implementation(context, prefix)
+        .map_err(|error| error.with_component("all", None))
+    } else if let Some(simple_content) = &self.simple_content {
+      simple_content
+        .get_field_implementation(namespace_definition, context, prefix, struct_name)
+        .map_err(|error| error.with_component("simpleContent", None))
+    } else if let Some(choice) = &self.choice {
+      let (field_name, enum_name) = choice::choice_names(struct_name, 0);
+      choice
+        .get_field_implementation(context, &field_name, &enum_name)
+        .map_err(|error| error.with_component("choice", None))
+    } else if let Some(group) = &self.group {
+      group
+        .get_field_implementation(context, prefix)
+        .map_err(|error| error.with_component("group", group.reference.as_deref()))
+    } else {
+      Ok(TokenStream::new())
+    }
+  }
+
+  /// Enums of the choices and structures of the nested sequences, for a complex type integrated
+  /// in an element.
+  pub fn get_choices_implementation(
+    &self,
+    context: &XsdContext,
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    if let Some(sequence) = &self.sequence {
+      sequence
+        .as_content()
+        .get_choices_implementation(context, namespace_definition, prefix, struct_name)
+        .map_err(|error| error.with_component("sequence", None))
+    } else if let Some(choice) = &self.choice {
+      let (_field_name, enum_name) = choice::choice_names(struct_name, 0);
+      choice
+        .get_choices_implementation(context, namespace_definition, prefix, &enum_name)
+        .map_err(|error| error.with_component("choice", None))
     } else {
-      TokenStream::new()
+      Ok(TokenStream::new())
     }
   }
 
+  /// The type of an element with this complex type integrated: the structure generated for the
+  /// element, named after it, when the type has a content of elements.
   pub fn get_integrated_implementation(&self, parent_name: &str) -> TokenStream {
     if self.simple_content.is_some() {
       return quote!(String);
     }
 
-    if self.sequence.is_some() {
-      let list_wrapper = Ident::new(parent_name, Span::call_site());
-      return quote!(#list_wrapper);
+    if self.sequence.is_some()
+      || self.all.is_some()
+      || self.choice.is_some()
+      || self.group.is_some()
+    {
+      let struct_name = Ident::new(
+        &parent_name.replace('.', "_").to_upper_camel_case(),
+        Span::call_site(),
+      );
+      return quote!(#struct_name);
     }
 
     quote!(String)
   }
 }
+
+/// A `default` or `fixed` value of an attribute or an element.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueConstraint {
+  Default,
+  Fixed,
+}
+
+impl ValueConstraint {
+  fn name(&self) -> &'static str {
+    match self {
+      ValueConstraint::Default => "default",
+      ValueConstraint::Fixed => "fixed",
+    }
+  }
+}
+
+/// Marker of a field with a `default` or `fixed` value and its typed expression, consumed by
+/// `implement_struct`. Without typed expression, the value is parsed at runtime.
+pub fn value_constraint_attribute(
+  default: Option<&str>,
+  fixed: Option<&str>,
+  value: Option<TokenStream>,
+) -> Result<TokenStream> {
+  let value = value.map(|value| quote!(, value = #value));
+
+  match (default, fixed) {
+    (Some(_), Some(_)) => Err(Error::new("default and fixed can not be both present")),
+    (Some(default), None) => Ok(quote!(#[xml_schema(default = #default #value)])),
+    (None, Some(fixed)) => Ok(quote!(#[xml_schema(fixed = #fixed #value)])),
+    (None, None) => Ok(TokenStream::new()),
+  }
+}
+
+/// Generate the structure with its fields.
+/// The repeated choices and groups can not be deserialized by the derive, as flattened vectors
+/// (nor the optional ones, read even when missing),
+/// the `default` and `fixed` values are applied after reading the fields,
+/// and several compositors are read one after the other from the remaining children:
+/// the structure is then deserialized through a private one.
+pub fn implement_struct(
+  namespace_definition: &TokenStream,
+  struct_name: &Ident,
+  fields: TokenStream,
+) -> Result<TokenStream> {
+  let mut fields: FieldsNamed =
+    syn::parse2(quote!({ #fields })).map_err(|error| Error::new(error.to_string()))?;
+
+  let markers = fields
+    .named
+    .iter_mut()
+    .map(take_field_markers)
+    .collect::<Result<Vec<_>>>()?;
+  let constraints: Vec<_> = markers
+    .iter()
+    .map(|markers| markers.constraint.clone())
+    .collect();
+  let has_constraint = constraints.iter().any(Option::is_some);
+
+  // several compositors are read one after the other, from the children left by the other fields
+  let read_children = markers
+    .iter()
+    .filter(|markers| markers.elements.is_some())
+    .count()
+    > 1;
+
+  // the QName attributes are resolved against the namespaces of their element, and written with
+  // the declarations of their namespaces
+  let qname_attributes: Vec<bool> = fields
+    .named
+    .iter()
+    .zip(&markers)
+    .map(|(field, markers)| markers.attribute.is_some() && is_qname(&field.ty))
+    .collect();
+  let has_qname_attribute = qname_attributes.contains(&true);
+
+  let mut deserializer_fields = fields.clone();
+  let mut has_flattened_list = false;
+  let mut read_types = vec![];
+
+  for ((field, markers), is_qname_attribute) in deserializer_fields
+    .named
+    .iter_mut()
+    .zip(&markers)
+    .zip(&qname_attributes)
+  {
+    // the QName attributes are read as text, and resolved against the namespaces of the element
+    if *is_qname_attribute {
+      field.ty = match wrapped_type(&field.ty) {
+        Some(_) => syn::parse_quote!(Option<String>),
+        None => syn::parse_quote!(String),
+      };
+    }
+    if let Some(list_type) = flattened_list_type(field, markers) {
+      field.ty = list_type;
+      has_flattened_list = true;
+    }
+    read_types.push(field.ty.clone());
+    if markers.constraint.is_some() {
+      field.ty = if markers.attribute.is_some() {
+        let rust_type = &field.ty;
+        syn::parse_quote!(Option<#rust_type>)
+      } else {
+        text_type(&field.ty)
+      };
+    }
+  }
+
+  if read_children {
+    deserializer_fields.named = deserializer_fields
+      .named
+      .into_iter()
+      .zip(&markers)
+      .filter(|(_field, markers)| markers.elements.is_none())
+      .map(|(field, _markers)| field)
+      .collect();
+    deserializer_fields.named.push(syn::parse_quote!(
+      #[yaserde(flatten = true)]
+      xml_schema_children: hifa_xml_schema::group::Children
+    ));
+  }
+
+  let serializer_fields: Vec<_> = fields
+    .named
+    .iter()
+    .zip(&qname_attributes)
+    .filter(|(_field, is_qname_attribute)| !**is_qname_attribute)
+    .map(|(field, _)| field.clone())
+    .collect();
+
+  if has_qname_attribute {
+    for field in fields.named.iter_mut() {
+      field
+        .attrs
+        .retain(|attribute| !attribute.path().is_ident("yaserde"));
+    }
+  }
+
+  let fields = &fields.named;
+
+  if !has_flattened_list && !has_constraint && !read_children && !has_qname_attribute {
+    return Ok(quote!(
+      #[derive(Clone, Debug, Default, PartialEq, hifa_yaserde_derive::YaDeserialize, hifa_yaserde_derive::YaSerialize)]
+      #namespace_definition
+      pub struct #struct_name {
+        #fields
+      }
+    ));
+  }
+
+  let deserializer_name = format_ident!("{}Deserializer", struct_name);
+  let xml_name = struct_name.to_string();
+  let field_names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+  let deserializer_fields = &deserializer_fields.named;
+
+  let values = fields
+    .iter()
+    .zip(&markers)
+    .zip(&read_types)
+    .zip(&qname_attributes)
+    .map(|(((field, markers), read_type), is_qname_attribute)| {
+      let name = &field.ident;
+      let rust_type = field.ty.to_token_stream();
+
+      let value = match (&markers.constraint, &markers.elements) {
+        (None, Some(elements)) if read_children => quote!(value
+          .xml_schema_children
+          .read::<#read_type>(#xml_name, #elements)?
+          .into()),
+        (None, _) => quote!(value.#name.into()),
+        (Some((constraint, lexical, typed_value)), _) if markers.attribute.is_none() => {
+          let value_type = value_type(&field.ty).to_token_stream();
+          let invalid_message = format!(
+            "{xml_name}: invalid value {{:?}} of {}: {{}}",
+            name.to_token_stream()
+          );
+          let parse_value = quote!(text
+            .parse::<#value_type>()
+            .map_err(|e| format!(#invalid_message, text.as_str(), e)));
+          let (typed_value, parsed_value) = match typed_value {
+            Some(typed_value) => (quote!(#typed_value), quote!(Ok(#typed_value))),
+            None => {
+              let parsed_value = quote!(#lexical
+                .parse::<#value_type>()
+                .map_err(|e| format!(#invalid_message, #lexical, e)));
+              (quote!(#parsed_value?), parsed_value)
+            }
+          };
+
+          let parse = match constraint {
+            ValueConstraint::Default => quote!(
+              if text.is_empty() {
+                return #parsed_value;
+              }
+              #parse_value
+            ),
+            ValueConstraint::Fixed => {
+              let message = format!(
+                "{xml_name}: the value {{}} of {} is not the fixed value {lexical}",
+                name.to_token_stream()
+              );
+              quote!(
+                let fixed: #value_type = #typed_value;
+                if text.is_empty() {
+                  return Ok(fixed);
+                }
+                let found = #parse_value?;
+                if found != fixed {
+                  return Err(format!(#message, found));
+                }
+                Ok(found)
+              )
+            }
+          };
+          let conversion = text_conversion(&field.ty, quote!(value.#name));
+
+          quote!({
+            let parse = |text: hifa_xml_schema::string::Text| -> Result<#value_type, String> { #parse };
+            #conversion?
+          })
+        }
+        (Some((constraint, lexical, typed_value)), _) => {
+          let attribute = markers.attribute.as_deref().unwrap_or_default();
+          let invalid_message = format!("{xml_name}: invalid value {{:?}} of {attribute}: {{}}");
+          // a QName is read as text, and resolved as the value found
+          let (rust_type, typed_value) = match typed_value {
+            _ if *is_qname_attribute => (quote!(String), quote!(#lexical.to_string())),
+            Some(typed_value) => (rust_type, typed_value.to_token_stream()),
+            None => (
+              rust_type.clone(),
+              quote!(#lexical
+                .parse::<#rust_type>()
+                .map_err(|e| format!(#invalid_message, #lexical, e))?),
+            ),
+          };
+
+          match constraint {
+            ValueConstraint::Default => quote!(match value.#name {
+              Some(found) => found,
+              None => #typed_value,
+            }),
+            ValueConstraint::Fixed => {
+              let message = format!(
+                "{xml_name}: the value {{}} of {attribute} is not the fixed value {lexical}"
+              );
+              quote!({
+                let fixed: #rust_type = #typed_value;
+                match value.#name {
+                  Some(found) if found != fixed => return Err(format!(#message, found)),
+                  _ => fixed,
+                }
+              })
+            }
+          }
+        }
+      };
+
+      let resolve = quote!(|found: String| {
+        found
+          .parse::<hifa_xml_schema::qname::QName>()
+          .and_then(|found| found.resolve(&namespace))
+      });
+      Ok(match wrapped_type(&field.ty) {
+        _ if !is_qname_attribute => value,
+        _ if markers.constraint.is_some() => quote!((#resolve)(#value)?),
+        Some(_) => quote!(value.#name.map(#resolve).transpose()?),
+        None => quote!((#resolve)(value.#name)?),
+      })
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let (default_derive, default_implementation) = if has_constraint {
+    let defaults = fields
+      .iter()
+      .zip(&constraints)
+      .map(|(field, constraint)| match constraint {
+        Some((_, _, Some(typed_value))) if wrapped_type(&field.ty).is_none() => {
+          typed_value.to_token_stream()
+        }
+        // the value parsed at runtime, the default one of the type when it is not valid
+        Some((_, lexical, None)) if wrapped_type(&field.ty).is_none() => {
+          quote!(#lexical.parse().unwrap_or_default())
+        }
+        _ => quote!(Default::default()),
+      })
+      .collect::<Vec<_>>();
+
+    let constants = fields
+      .iter()
+      .zip(&constraints)
+      .filter_map(|(field, constraint)| {
+        let (constraint, lexical, _) = constraint.as_ref()?;
+        let name = field.ident.as_ref()?.to_string();
+        let constant_name = format_ident!(
+          "{}_{}",
+          name.to_shouty_snake_case(),
+          constraint.name().to_shouty_snake_case()
+        );
+        let docs = format!(
+          "The {} value of `{name}`, in its lexical form.",
+          constraint.name()
+        );
+
+        Some(quote!(
+          #[doc = #docs]
+          pub const #constant_name: &str = #lexical;
+        ))
+      });
+
+    (
+      TokenStream::new(),
+      quote!(
+        impl Default for #struct_name {
+          fn default() -> Self {
+            #struct_name {
+              #(#field_names: #defaults,)*
+            }
+          }
+        }
+
+        impl #struct_name {
+          #(#constants)*
+        }
+      ),
+    )
+  } else {
+    (quote!(Default,), TokenStream::new())
+  };
+
+  let mutability = read_children.then_some(quote!(mut));
+  let namespace = has_qname_attribute
+    .then_some(quote!(let namespace = hifa_xml_schema::qname::in_scope_namespace(reader)?;));
+
+  let (serialize_derive, structure_namespace_definition, serializer) = if has_qname_attribute {
+    (
+      TokenStream::new(),
+      TokenStream::new(),
+      implement_qname_serializer(
+        namespace_definition,
+        struct_name,
+        fields,
+        &markers,
+        &qname_attributes,
+        &serializer_fields,
+      ),
+    )
+  } else {
+    (
+      quote!(hifa_yaserde_derive::YaSerialize),
+      namespace_definition.clone(),
+      TokenStream::new(),
+    )
+  };
+
+  Ok(quote!(
+    #[derive(Clone, Debug, #default_derive PartialEq, #serialize_derive)]
+    #structure_namespace_definition
+    pub struct #struct_name {
+      #fields
+    }
+
+    #default_implementation
+
+    #serializer
+
+    #[derive(Default, hifa_yaserde_derive::YaDeserialize)]
+    #namespace_definition
+    #[yaserde(rename = #xml_name)]
+    struct #deserializer_name {
+      #deserializer_fields
+    }
+
+    impl hifa_yaserde::YaDeserialize for #struct_name {
+      fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        #namespace
+        let #mutability value = <#deserializer_name as hifa_yaserde::YaDeserialize>::deserialize(reader)?;
+        Ok(#struct_name {
+          #(#field_names: #values,)*
+        })
+      }
+    }
+  ))
+}
+
+/// The serialization of a structure with QName attributes, through a private structure where
+/// they are written by `QNameAttributes` with the declarations of their namespaces.
+fn implement_qname_serializer(
+  namespace_definition: &TokenStream,
+  struct_name: &Ident,
+  fields: &Punctuated<Field, Comma>,
+  markers: &[FieldMarkers],
+  qname_attributes: &[bool],
+  serializer_fields: &[Field],
+) -> TokenStream {
+  let serializer_name = format_ident!("{}Serializer", struct_name);
+  let xml_name = struct_name.to_string();
+  let element_name = match namespace_prefix(namespace_definition) {
+    Some(prefix) => format!("{prefix}:{xml_name}"),
+    None => xml_name.clone(),
+  };
+
+  let field_names = serializer_fields.iter().map(|field| &field.ident);
+  let pushes = fields
+    .iter()
+    .zip(markers)
+    .zip(qname_attributes)
+    .filter(|(_, is_qname_attribute)| **is_qname_attribute)
+    .map(|((field, markers), _)| {
+      let name = &field.ident;
+      let attribute_name = markers.attribute.as_deref().unwrap_or_default();
+      let value = match wrapped_type(&field.ty) {
+        Some(_) => quote!(self.#name.as_ref()),
+        None => quote!(Some(&self.#name)),
+      };
+      quote!(qnames.push(#attribute_name, #value);)
+    });
+
+  quote!(
+    #[derive(hifa_yaserde_derive::YaSerialize)]
+    #namespace_definition
+    #[yaserde(rename = #xml_name)]
+    struct #serializer_name {
+      #(#serializer_fields,)*
+      #[yaserde(flatten = true)]
+      xml_schema_qnames: hifa_xml_schema::qname::QNameAttributes,
+    }
+
+    impl #struct_name {
+      fn xml_schema_serializer(&self, element_name: Option<&str>) -> #serializer_name {
+        let mut qnames = hifa_xml_schema::qname::QNameAttributes::new(element_name);
+        #(#pushes)*
+
+        #serializer_name {
+          #(#field_names: self.#field_names.clone(),)*
+          xml_schema_qnames: qnames,
+        }
+      }
+    }
+
+    impl hifa_yaserde::YaSerialize for #struct_name {
+      fn serialize<W: std::io::Write>(&self, writer: &mut hifa_yaserde::ser::Serializer<W>) -> Result<(), String> {
+        let element_name = writer.get_start_event_name().unwrap_or_else(|| #element_name.to_string());
+        hifa_yaserde::YaSerialize::serialize(&self.xml_schema_serializer(Some(&element_name)), writer)
+      }
+
+      fn serialize_attributes(&self, source_attributes: Vec<xml::attribute::OwnedAttribute>, source_namespace: xml::namespace::Namespace) -> Result<(Vec<xml::attribute::OwnedAttribute>, xml::namespace::Namespace), String> {
+        hifa_yaserde::YaSerialize::serialize_attributes(&self.xml_schema_serializer(None), source_attributes, source_namespace)
+      }
+    }
+  )
+}
+
+/// The prefix of the elements declared by a namespace definition, ie. `#[yaserde(prefix = "ex", ...)]`.
+fn namespace_prefix(namespace_definition: &TokenStream) -> Option<String> {
+  let attributes = syn::Attribute::parse_outer
+    .parse2(namespace_definition.clone())
+    .ok()?;
+
+  let mut prefix = None;
+  for attribute in attributes {
+    let _ = attribute.parse_nested_meta(|meta| {
+      let value = meta.value()?;
+      if meta.path.is_ident("prefix") {
+        prefix = Some(value.parse::<syn::LitStr>()?.value());
+      } else {
+        value.parse::<proc_macro2::TokenTree>()?;
+      }
+      Ok(())
+    });
+  }
+  prefix
+}
+
+/// A `QName` or an optional one.
+fn is_qname(rust_type: &Type) -> bool {
+  let value_type = match wrapped_type(rust_type) {
+    Some((wrapper, inner_type)) if wrapper == "Option" => inner_type,
+    Some(_) => return false,
+    None => rust_type,
+  };
+  value_type.to_token_stream().to_string() == "hifa_xml_schema :: qname :: QName"
+}
+
+/// The markers of a field, consumed by `implement_struct`.
+#[derive(Default)]
+struct FieldMarkers {
+  /// The name of the XML attribute read in the field.
+  attribute: Option<String>,
+  /// The `default` or `fixed` value, in its lexical form and as a typed expression, if any.
+  constraint: Option<(ValueConstraint, String, Option<syn::Expr>)>,
+  /// The elements of a flattened compositor, as the argument of `Children::read`.
+  elements: Option<syn::Expr>,
+}
+
+/// Remove the `attribute`, `default`, `fixed`, `value` and `elements` markers of the field, and
+/// return them.
+fn take_field_markers(field: &mut Field) -> Result<FieldMarkers> {
+  let mut markers = FieldMarkers::default();
+  let mut constraint = None;
+  let mut typed_value = None;
+
+  for attribute in &field.attrs {
+    if !attribute.path().is_ident("xml_schema") {
+      continue;
+    }
+
+    attribute
+      .parse_nested_meta(|meta| {
+        if meta.path.is_ident("attribute") {
+          let name: syn::LitStr = meta.value()?.parse()?;
+          markers.attribute = Some(name.value());
+          return Ok(());
+        }
+        if meta.path.is_ident("elements") {
+          markers.elements = Some(meta.value()?.parse()?);
+          return Ok(());
+        }
+        if meta.path.is_ident("value") {
+          typed_value = Some(meta.value()?.parse()?);
+          return Ok(());
+        }
+
+        let kind = if meta.path.is_ident("default") {
+          ValueConstraint::Default
+        } else if meta.path.is_ident("fixed") {
+          ValueConstraint::Fixed
+        } else {
+          return Err(meta.error("expected attribute, default, fixed, value or elements"));
+        };
+        let lexical: syn::LitStr = meta.value()?.parse()?;
+        constraint = Some((kind, lexical.value()));
+        Ok(())
+      })
+      .map_err(|error| Error::new(error.to_string()))?;
+  }
+
+  markers.constraint = match (constraint, typed_value) {
+    (Some((kind, lexical)), typed_value) => Some((kind, lexical, typed_value)),
+    (None, None) => None,
+    (None, Some(_)) => {
+      return Err(Error::new(
+        "a typed value goes with a default or fixed value",
+      ))
+    }
+  };
+
+  field
+    .attrs
+    .retain(|attribute| !attribute.path().is_ident("xml_schema"));
+
+  Ok(markers)
+}
+
+/// The `Vec`, `Option` or `Nillable` wrapper of a type, and the wrapped type.
+fn wrapped_type(rust_type: &Type) -> Option<(String, &Type)> {
+  let Type::Path(TypePath { path, .. }) = rust_type else {
+    return None;
+  };
+  let segment = path.segments.last()?;
+  if !["Vec", "Option", "Nillable"].contains(&segment.ident.to_string().as_str()) {
+    return None;
+  }
+
+  let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+    return None;
+  };
+  match arguments.args.first() {
+    Some(GenericArgument::Type(inner_type)) => Some((segment.ident.to_string(), inner_type)),
+    _ => None,
+  }
+}
+
+/// The type of the values, inside the wrappers.
+fn value_type(rust_type: &Type) -> &Type {
+  match wrapped_type(rust_type) {
+    Some((_, inner_type)) => value_type(inner_type),
+    None => rust_type,
+  }
+}
+
+/// The type of an element with a `default` or `fixed` value read as text, ie. `Option<Text>`,
+/// as the value of an empty element is applied after reading it.
+fn text_type(rust_type: &Type) -> Type {
+  let Some((_, inner_type)) = wrapped_type(rust_type) else {
+    return syn::parse_quote!(hifa_xml_schema::string::Text);
+  };
+
+  let mut wrapper = rust_type.clone();
+  if let Type::Path(TypePath { path, .. }) = &mut wrapper {
+    if let Some(PathArguments::AngleBracketed(arguments)) = path
+      .segments
+      .last_mut()
+      .map(|segment| &mut segment.arguments)
+    {
+      arguments.args[0] = GenericArgument::Type(text_type(inner_type));
+    }
+  }
+  wrapper
+}
+
+/// Convert the text read with `text_type` with the `parse` closure, into a `Result`.
+fn text_conversion(rust_type: &Type, value: TokenStream) -> TokenStream {
+  let Some((wrapper, inner_type)) = wrapped_type(rust_type) else {
+    return quote!(parse(#value));
+  };
+  let item = text_conversion(inner_type, quote!(item));
+
+  match wrapper.as_str() {
+    "Vec" => quote!(#value.into_iter().map(|item| #item).collect::<Result<Vec<_>, String>>()),
+    "Option" => quote!(#value.map(|item| #item).transpose()),
+    _ => quote!(match #value {
+      hifa_xml_schema::nillable::Nillable::Nil => Ok(hifa_xml_schema::nillable::Nillable::Nil),
+      hifa_xml_schema::nillable::Nillable::Value(item) => #item.map(hifa_xml_schema::nillable::Nillable::Value),
+    }),
+  }
+}
+
+/// The `Vec<Choice>` or `Option<Choice>` type of a flattened compositor, marked with its
+/// `elements`, is read with the `ChoiceList` type, as for the groups.
+fn flattened_list_type(field: &Field, markers: &FieldMarkers) -> Option<Type> {
+  markers.elements.as_ref()?;
+
+  let Type::Path(TypePath { path, .. }) = &field.ty else {
+    return None;
+  };
+  let segment = path.segments.last()?;
+  if segment.ident != "Vec" && segment.ident != "Option" {
+    return None;
+  }
+
+  let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+    return None;
+  };
+  let Some(GenericArgument::Type(Type::Path(item_type))) = arguments.args.first() else {
+    return None;
+  };
+
+  let mut list_type = item_type.clone();
+  let item_segment = list_type.path.segments.last_mut()?;
+  item_segment.ident = format_ident!("{}List", item_segment.ident);
+  Some(Type::Path(list_type))
+}
@@ -0,0 +1,788 @@
+//! Value of a restricted simple type returned by its `Default` implementation: the first sample
+//! of its facets which satisfies all of them, ie. its lower bound or a text matching its pattern.
+//! Without such a value known to be valid, the type does not implement `Default`.
+
+use crate::xsd::{
+  facet::Facet,
+  restriction::{primitive_literal, Restriction},
+  rust_types_mapping::RustTypesMapping,
+  Error, Result, XsdContext,
+};
+use proc_macro2::TokenStream;
+use regex::Regex;
+use regex_syntax::hir::{Class, ClassUnicode, Hir, HirKind};
+use std::cmp::Ordering;
+
+/// Number of additional repetitions tried to reach the minimum length with a pattern.
+const MAX_EXTRA_REPETITIONS: usize = 16;
+
+/// Number of fraction digits tried to find a decimal value between exclusive bounds.
+const MAX_STEP_SCALE: u32 = 6;
+
+/// How the values of the base type are compared with the facets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Domain {
+  Integer,
+  Decimal,
+  Float,
+  Text,
+  Binary,
+  Other,
+}
+
+/// Expression of the default value of the restriction, in its Rust type, with its lexical form
+/// when it is computed from the facets, `None` when no value is known to satisfy them.
+pub fn implement(
+  restriction: &Restriction,
+  context: &XsdContext,
+  base: &str,
+  rust_type: &TokenStream,
+) -> Result<Option<(TokenStream, Option<String>)>> {
+  // without other facet than `whiteSpace`, any value of the base type is valid
+  if !(Restriction {
+    white_space: None,
+    ..restriction.clone()
+  })
+  .has_facets()
+  {
+    return Ok(Some((quote!(Default::default()), None)));
+  }
+
+  let domain = domain(context, base);
+  let base_name = base.rsplit(':').next().unwrap_or(base);
+  let patterns = restriction
+    .patterns
+    .iter()
+    .map(|facet| {
+      let expression = translate_expression(&facet.value);
+      Regex::new(&format!("^(?:{expression})$"))
+        .map(|regex| (expression, regex))
+        .map_err(|e| Error::new(format!("invalid pattern {:?}: {e}", facet.value)))
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let mut candidates = Vec::new();
+  for (expression, _) in &patterns {
+    if let Ok(hir) = regex_syntax::parse(expression) {
+      candidates
+        .extend((0..=MAX_EXTRA_REPETITIONS).filter_map(|extra| sample(&hir, &mut { extra })));
+    }
+  }
+  candidates.extend(bound_candidates(restriction, domain));
+
+  let found = candidates.into_iter().find(|candidate| {
+    (patterns.is_empty() || patterns.iter().any(|(_, regex)| regex.is_match(candidate)))
+      && is_collapsed(candidate)
+      && satisfies_lengths(restriction, domain, candidate)
+      && satisfies_value(restriction, domain, base_name, rust_type, candidate)
+      && is_lexical_value(domain, base_name, rust_type, candidate)
+  });
+
+  let Some(value) = found else {
+    return Ok(None);
+  };
+
+  let expression = if let Some(literal) = primitive_literal("default", &value, rust_type)? {
+    literal
+  } else if rust_type.to_string() == "String" || domain == Domain::Text {
+    quote!(#value.into())
+  } else {
+    // the value is checked above as a lexical value of the base type
+    quote!(#value.parse().unwrap_or_default())
+  };
+  Ok(Some((expression, Some(value))))
+}
+
+fn domain(context: &XsdContext, base: &str) -> Domain {
+  if !RustTypesMapping::is_xs_type(context, base) {
+    return Domain::Other;
+  }
+  if RustTypesMapping::is_xs_text(context, base) {
+    return Domain::Text;
+  }
+  if RustTypesMapping::is_xs_binary(context, base) {
+    return Domain::Binary;
+  }
+  if RustTypesMapping::is_xs_decimal(context, base) {
+    return Domain::Decimal;
+  }
+
+  match base.rsplit(':').next() {
+    Some("float" | "double") => Domain::Float,
+    Some(
+      "byte" | "unsignedByte" | "short" | "unsignedShort" | "int" | "unsignedInt" | "long"
+      | "unsignedLong" | "integer" | "nonPositiveInteger" | "negativeInteger"
+      | "nonNegativeInteger" | "positiveInteger",
+    ) => Domain::Integer,
+    _ => Domain::Other,
+  }
+}
+
+/// Values from the bounds and the lengths, the lowest ones first.
+fn bound_candidates(restriction: &Restriction, domain: Domain) -> Vec<String> {
+  let mut candidates = Vec::new();
+  let bound = |facet: &Option<Facet>| facet.as_ref().map(|facet| facet.value.trim().to_string());
+
+  match domain {
+    Domain::Integer | Domain::Decimal | Domain::Float => {
+      let max_scale = if domain == Domain::Integer {
+        0
+      } else {
+        MAX_STEP_SCALE
+      };
+      let steps = |facet: &Option<Facet>, up: bool| {
+        bound(facet)
+          .and_then(|value| Dec::parse(&value))
+          .map(|value| {
+            (0..=max_scale)
+              .filter_map(|scale| value.step(scale, up))
+              .map(|value| value.to_string())
+              .collect::<Vec<_>>()
+          })
+          .unwrap_or_default()
+      };
+
+      candidates.extend(bound(&restriction.min_inclusive));
+      candidates.extend(steps(&restriction.min_exclusive, true));
+      candidates.extend(["0", "1", "-1"].map(String::from));
+      candidates.extend(bound(&restriction.max_inclusive));
+      candidates.extend(steps(&restriction.max_exclusive, false));
+
+      // the leading zeros reach the length of the lexical form
+      let padded = [&restriction.length, &restriction.min_length]
+        .iter()
+        .filter_map(|facet| bound(facet)?.parse::<usize>().ok())
+        .flat_map(|length| {
+          candidates.iter().filter_map(move |candidate| {
+            let (sign, digits) = match candidate.strip_prefix('-') {
+              Some(digits) => ("-", digits),
+              None => ("", candidate.as_str()),
+            };
+            let width = length.checked_sub(sign.len())?;
+            (digits.len() < width).then(|| format!("{sign}{digits:0>width$}"))
+          })
+        })
+        .collect::<Vec<_>>();
+      candidates.extend(padded);
+    }
+    Domain::Text => {
+      candidates.push(String::new());
+      candidates.extend(
+        [&restriction.length, &restriction.min_length]
+          .iter()
+          .filter_map(|facet| bound(facet)?.parse::<usize>().ok())
+          .map(|length| "a".repeat(length)),
+      );
+    }
+    Domain::Binary => {
+      candidates.push(String::new());
+      candidates.extend(
+        [&restriction.length, &restriction.min_length]
+          .iter()
+          .filter_map(|facet| bound(facet)?.parse::<usize>().ok())
+          .map(|octets| zero_octets(restriction, octets)),
+      );
+    }
+    Domain::Other => {
+      // the values of a valid schema satisfy the other bounds, without being compared
+      candidates.extend(bound(&restriction.min_inclusive).or_else(|| {
+        bound(&restriction.max_inclusive).filter(|_| restriction.min_exclusive.is_none())
+      }));
+    }
+  }
+
+  candidates
+}
+
+/// The lexical form of `octets` zero octets: `00` in hexadecimal, `A` in base 64.
+fn zero_octets(restriction: &Restriction, octets: usize) -> String {
+  let is_hex = restriction
+    .base
+    .as_deref()
+    .is_some_and(|base| base.ends_with("hexBinary"));
+  if is_hex {
+    return "00".repeat(octets);
+  }
+
+  let mut encoded = "A".repeat((octets * 4).div_ceil(3));
+  while !encoded.len().is_multiple_of(4) {
+    encoded.push('=');
+  }
+  encoded
+}
+
+/// The value is not changed by any `whiteSpace` normalization.
+fn is_collapsed(value: &str) -> bool {
+  !value.contains(['\t', '\n', '\r'])
+    && !value.starts_with(' ')
+    && !value.ends_with(' ')
+    && !value.contains("  ")
+}
+
+fn satisfies_lengths(restriction: &Restriction, domain: Domain, value: &str) -> bool {
+  let length = if domain == Domain::Binary {
+    match octets(restriction, value) {
+      Some(length) => length,
+      None => return false,
+    }
+  } else {
+    value.chars().count()
+  };
+
+  let count = |facet: &Option<Facet>| {
+    facet
+      .as_ref()
+      .map(|facet| facet.value.trim().parse::<usize>())
+  };
+  let check = |facet: &Option<Facet>, valid: fn(usize, usize) -> bool| match count(facet) {
+    Some(Ok(bound)) => valid(length, bound),
+    Some(Err(_)) => false,
+    None => true,
+  };
+
+  check(&restriction.length, |length, bound| length == bound)
+    && check(&restriction.min_length, |length, bound| length >= bound)
+    && check(&restriction.max_length, |length, bound| length <= bound)
+}
+
+/// Number of octets of binary data, `None` when the value is not valid.
+fn octets(restriction: &Restriction, value: &str) -> Option<usize> {
+  let is_hex = restriction
+    .base
+    .as_deref()
+    .is_some_and(|base| base.ends_with("hexBinary"));
+  if is_hex {
+    return (value.len().is_multiple_of(2) && value.bytes().all(|c| c.is_ascii_hexdigit()))
+      .then_some(value.len() / 2);
+  }
+
+  let data = value.trim_end_matches('=');
+  let is_valid = value.len().is_multiple_of(4)
+    && value.len() - data.len() <= 2
+    && data
+      .bytes()
+      .all(|c| c.is_ascii_alphanumeric() || c == b'+' || c == b'/');
+  is_valid.then_some(value.len() / 4 * 3 - (value.len() - data.len()))
+}
+
+fn satisfies_value(
+  restriction: &Restriction,
+  domain: Domain,
+  base_name: &str,
+  rust_type: &TokenStream,
+  value: &str,
+) -> bool {
+  if primitive_literal("default", value, rust_type).is_err() {
+    return false;
+  }
+
+  let bounds = [
+    (
+      &restriction.min_inclusive,
+      [Ordering::Greater, Ordering::Equal],
+    ),
+    (
+      &restriction.max_inclusive,
+      [Ordering::Less, Ordering::Equal],
+    ),
+    (
+      &restriction.min_exclusive,
+      [Ordering::Greater, Ordering::Greater],
+    ),
+    (&restriction.max_exclusive, [Ordering::Less, Ordering::Less]),
+  ];
+
+  match domain {
+    Domain::Integer | Domain::Decimal => {
+      let Some(number) = Dec::parse(value) else {
+        return false;
+      };
+      if domain == Domain::Integer && value.contains('.') {
+        return false;
+      }
+
+      let sign = number.mantissa.cmp(&0);
+      let sign_valid = match base_name {
+        "nonPositiveInteger" => sign != Ordering::Greater,
+        "negativeInteger" => sign == Ordering::Less,
+        "nonNegativeInteger" => sign != Ordering::Less,
+        "positiveInteger" => sign == Ordering::Greater,
+        _ => true,
+      };
+
+      sign_valid
+        && bounds.iter().all(|(facet, valid)| match facet {
+          Some(facet) => Dec::parse(&facet.value)
+            .and_then(|bound| number.compare(&bound))
+            .is_some_and(|ordering| valid.contains(&ordering)),
+          None => true,
+        })
+        && digits_within(&restriction.total_digits, total_digits(value))
+        && digits_within(&restriction.fraction_digits, fraction_digits(value))
+    }
+    Domain::Float => {
+      let Some(number) = parse_float(value).filter(|number| !number.is_nan()) else {
+        return false;
+      };
+
+      bounds.iter().all(|(facet, valid)| match facet {
+        Some(facet) => parse_float(&facet.value)
+          .and_then(|bound| number.partial_cmp(&bound))
+          .is_some_and(|ordering| valid.contains(&ordering)),
+        None => true,
+      })
+    }
+    Domain::Text | Domain::Binary => true,
+    Domain::Other => {
+      let inclusive_bound = match (&restriction.min_inclusive, &restriction.max_inclusive) {
+        (Some(bound), _) | (None, Some(bound)) => bound.value.trim() == value,
+        (None, None) => true,
+      };
+
+      restriction.min_exclusive.is_none() && restriction.max_exclusive.is_none() && inclusive_bound
+    }
+  }
+}
+
+/// The value is read by the Rust type of the base type, which is not available when generating
+/// the code: its lexical form is checked instead.
+fn is_lexical_value(domain: Domain, base_name: &str, rust_type: &TokenStream, value: &str) -> bool {
+  match domain {
+    Domain::Integer => match rust_type.to_string().as_str() {
+      "hifa_xml_schema :: numeric :: NonPositiveI64"
+      | "hifa_xml_schema :: numeric :: NegativeI64" => value.parse::<i64>().is_ok(),
+      "hifa_xml_schema :: numeric :: PositiveU64" => value.parse::<u64>().is_ok(),
+      _ => true,
+    },
+    Domain::Decimal | Domain::Float | Domain::Text | Domain::Binary => true,
+    Domain::Other => {
+      rust_type.to_string() == "String"
+        || matches!(primitive_literal("default", value, rust_type), Ok(Some(_)))
+        || is_temporal_value(base_name, value)
+    }
+  }
+}
+
+/// The value is in the lexical space of the temporal built-in type.
+fn is_temporal_value(base_name: &str, value: &str) -> bool {
+  const YEAR: &str = r"-?(?:[1-9][0-9]{4,}|[0-9]{4})";
+  const MONTH: &str = r"(?:0[1-9]|1[0-2])";
+  const DAY: &str = r"(?:0[1-9]|[12][0-9]|3[01])";
+  const TIME: &str =
+    r"(?:(?:[01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9](?:\.[0-9]+)?|24:00:00(?:\.0+)?)";
+  const TIMEZONE: &str = r"(?:Z|[+-](?:(?:0[0-9]|1[0-3]):[0-5][0-9]|14:00))";
+
+  let expression = match base_name {
+    "date" => format!("{YEAR}-{MONTH}-{DAY}{TIMEZONE}?"),
+    "time" => format!("{TIME}{TIMEZONE}?"),
+    "dateTime" => format!("{YEAR}-{MONTH}-{DAY}T{TIME}{TIMEZONE}?"),
+    "dateTimeStamp" => format!("{YEAR}-{MONTH}-{DAY}T{TIME}{TIMEZONE}"),
+    "gYearMonth" => format!("{YEAR}-{MONTH}{TIMEZONE}?"),
+    "gYear" => format!("{YEAR}{TIMEZONE}?"),
+    "gMonthDay" => format!("--{MONTH}-{DAY}{TIMEZONE}?"),
+    "gDay" => format!("---{DAY}{TIMEZONE}?"),
+    "gMonth" => format!("--{MONTH}{TIMEZONE}?"),
+    "duration" => r"-?P(?:[0-9]+Y)?(?:[0-9]+M)?(?:[0-9]+D)?(?:T(?:[0-9]+H)?(?:[0-9]+M)?(?:[0-9]+(?:\.[0-9]+)?S)?)?".to_string(),
+    "dayTimeDuration" => r"-?P(?:[0-9]+D)?(?:T(?:[0-9]+H)?(?:[0-9]+M)?(?:[0-9]+(?:\.[0-9]+)?S)?)?".to_string(),
+    "yearMonthDuration" => r"-?P(?:[0-9]+Y)?(?:[0-9]+M)?".to_string(),
+    _ => return false,
+  };
+  let is_match =
+    Regex::new(&format!("^(?:{expression})$")).is_ok_and(|regex| regex.is_match(value));
+
+  // a duration has at least a component, and a time designator is followed by one
+  let has_components =
+    !base_name.ends_with("uration") || (!value.ends_with('P') && !value.ends_with('T'));
+
+  is_match && has_components && has_valid_day(value)
+}
+
+/// The day of a date is in its month, the year being a leap year or not.
+fn has_valid_day(value: &str) -> bool {
+  let Some(captures) = Regex::new(r"^(?:(-?[0-9]{4,})|-)-([0-9]{2})-([0-9]{2})")
+    .ok()
+    .and_then(|regex| regex.captures(value))
+  else {
+    return true;
+  };
+
+  let number = |index: usize| {
+    captures
+      .get(index)
+      .and_then(|found| found.as_str().parse::<i64>().ok())
+  };
+  let (Some(month), Some(day)) = (number(2), number(3)) else {
+    return false;
+  };
+  let is_leap = match number(1) {
+    Some(year) => year % 4 == 0 && (year % 100 != 0 || year % 400 == 0),
+    // the month and day of a `gMonthDay` are in any year
+    None => true,
+  };
+
+  let days = match month {
+    2 if is_leap => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  };
+  day <= days
+}
+
+fn digits_within(facet: &Option<Facet>, digits: usize) -> bool {
+  match facet {
+    Some(facet) => facet
+      .value
+      .trim()
+      .parse::<usize>()
+      .is_ok_and(|limit| digits <= limit),
+    None => true,
+  }
+}
+
+/// As `total_digits` of the `facets` module of hifa XML-Schema.
+fn total_digits(value: &str) -> usize {
+  let (integer, fraction) = split_decimal(value);
+  integer.len() + fraction.len()
+}
+
+/// As `fraction_digits` of the `facets` module of hifa XML-Schema.
+fn fraction_digits(value: &str) -> usize {
+  split_decimal(value).1.len()
+}
+
+fn split_decimal(value: &str) -> (&str, &str) {
+  let value = value.trim().trim_start_matches(['+', '-']);
+  let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+  (
+    integer.trim_start_matches('0'),
+    fraction.trim_end_matches('0'),
+  )
+}
+
+fn parse_float(value: &str) -> Option<f64> {
+  match value.trim() {
+    "INF" | "+INF" => Some(f64::INFINITY),
+    "-INF" => Some(f64::NEG_INFINITY),
+    "NaN" => Some(f64::NAN),
+    value
+      if value
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E')) =>
+    {
+      value.parse().ok()
+    }
+    _ => None,
+  }
+}
+
+/// A decimal number, `mantissa × 10^-scale`, to compare the bounds exactly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Dec {
+  mantissa: i128,
+  scale: u32,
+}
+
+impl Dec {
+  fn parse(value: &str) -> Option<Dec> {
+    let value = value.trim();
+    let (negative, digits) = match value.strip_prefix('-') {
+      Some(digits) => (true, digits),
+      None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if (integer.is_empty() && fraction.is_empty())
+      || !integer.bytes().all(|c| c.is_ascii_digit())
+      || !fraction.bytes().all(|c| c.is_ascii_digit())
+    {
+      return None;
+    }
+
+    let magnitude: i128 = format!("{integer}{fraction}").parse().ok()?;
+    Some(Dec {
+      mantissa: if negative { -magnitude } else { magnitude },
+      scale: std::convert::TryFrom::try_from(fraction.len()).ok()?,
+    })
+  }
+
+  fn rescale(&self, scale: u32) -> Option<i128> {
+    self
+      .mantissa
+      .checked_mul(10i128.checked_pow(scale.checked_sub(self.scale)?)?)
+  }
+
+  fn compare(&self, other: &Dec) -> Option<Ordering> {
+    let scale = self.scale.max(other.scale);
+    Some(self.rescale(scale)?.cmp(&other.rescale(scale)?))
+  }
+
+  /// The closest value with `scale` fraction digits above (or below) this one.
+  fn step(&self, scale: u32, up: bool) -> Option<Dec> {
+    let mantissa = if scale >= self.scale {
+      self.rescale(scale)?
+    } else {
+      let unit = 10i128.checked_pow(self.scale - scale)?;
+      if up {
+        self.mantissa.div_euclid(unit)
+      } else {
+        -(-self.mantissa).div_euclid(unit)
+      }
+    };
+
+    Some(Dec {
+      mantissa: if up {
+        mantissa.checked_add(1)?
+      } else {
+        mantissa.checked_sub(1)?
+      },
+      scale,
+    })
+  }
+}
+
+impl std::fmt::Display for Dec {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.mantissa < 0 {
+      f.write_str("-")?;
+    }
+
+    let digits = self.mantissa.unsigned_abs().to_string();
+    let scale = self.scale as usize;
+    if scale == 0 {
+      return f.write_str(&digits);
+    }
+
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    write!(f, "{integer}.{fraction}")
+  }
+}
+
+/// A text matching the expression, `extra` repetitions are added to the first repeated items.
+fn sample(hir: &Hir, extra: &mut usize) -> Option<String> {
+  match hir.kind() {
+    HirKind::Empty | HirKind::Look(_) => Some(String::new()),
+    HirKind::Literal(literal) => String::from_utf8(literal.0.to_vec()).ok(),
+    HirKind::Class(Class::Unicode(class)) => sample_char(class).map(String::from),
+    HirKind::Class(Class::Bytes(_)) => None,
+    HirKind::Repetition(repetition) => {
+      let item = sample(&repetition.sub, extra)?;
+      let more = match repetition.max {
+        Some(max) => (*extra).min((max - repetition.min) as usize),
+        None => *extra,
+      };
+      *extra -= more;
+      Some(item.repeat(repetition.min as usize + more))
+    }
+    HirKind::Capture(capture) => sample(&capture.sub, extra),
+    HirKind::Concat(items) => items.iter().map(|item| sample(item, extra)).collect(),
+    HirKind::Alternation(items) => items.iter().find_map(|item| sample(item, extra)),
+  }
+}
+
+/// A character of the class, a digit or a letter when possible.
+fn sample_char(class: &ClassUnicode) -> Option<char> {
+  let contains = |c: char| {
+    class
+      .ranges()
+      .iter()
+      .any(|range| range.start() <= c && c <= range.end())
+  };
+
+  ['0', 'a', 'A', '_', '-', '.']
+    .iter()
+    .copied()
+    .find(|c| contains(*c))
+    .or_else(|| {
+      class
+        .ranges()
+        .iter()
+        .find(|range| range.end() > ' ')
+        .map(|range| range.start().max('!'))
+    })
+}
+
+/// Translate the XSD specific syntax of a pattern to the `regex` one, as the `Pattern` of the
+/// `facets` module of hifa XML-Schema does at runtime.
+fn translate_expression(expression: &str) -> String {
+  const NAME_START: &str = r"_:A-Za-z\u{C0}-\u{D6}\u{D8}-\u{F6}\u{F8}-\u{2FF}\u{370}-\u{37D}\u{37F}-\u{1FFF}\u{200C}-\u{200D}\u{2070}-\u{218F}\u{2C00}-\u{2FEF}\u{3001}-\u{D7FF}\u{F900}-\u{FDCF}\u{FDF0}-\u{FFFD}";
+  const NAME: &str = r"-._:A-Za-z0-9\u{B7}\u{C0}-\u{D6}\u{D8}-\u{F6}\u{F8}-\u{37D}\u{37F}-\u{1FFF}\u{200C}-\u{200D}\u{203F}-\u{2040}\u{2070}-\u{218F}\u{2C00}-\u{2FEF}\u{3001}-\u{D7FF}\u{F900}-\u{FDCF}\u{FDF0}-\u{FFFD}";
+
+  let mut translated = String::with_capacity(expression.len());
+  let mut chars = expression.chars().peekable();
+  let mut class_depth = 0;
+
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' => match chars.next() {
+        Some('i') => translated.push_str(&format!("[{NAME_START}]")),
+        Some('I') => translated.push_str(&format!("[^{NAME_START}]")),
+        Some('c') => translated.push_str(&format!("[{NAME}]")),
+        Some('C') => translated.push_str(&format!("[^{NAME}]")),
+        Some(escaped) => {
+          translated.push('\\');
+          translated.push(escaped);
+        }
+        None => translated.push_str(r"\\"),
+      },
+      '[' => {
+        class_depth += 1;
+        translated.push('[');
+        if chars.peek() == Some(&'^') {
+          translated.push(chars.next().unwrap_or_default());
+        }
+      }
+      ']' if class_depth > 0 => {
+        class_depth -= 1;
+        translated.push(']');
+      }
+      '-' if class_depth > 0 && chars.peek() == Some(&'[') => translated.push_str("--"),
+      '^' | '$' if class_depth == 0 => {
+        translated.push('\\');
+        translated.push(c);
+      }
+      _ => translated.push(c),
+    }
+  }
+
+  translated
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::xsd::simple_type::SimpleType;
+  use hifa_yaserde::de::from_str;
+
+  fn default_value(restriction: &str) -> Option<String> {
+    let simple_type: SimpleType = from_str(&format!(
+      r#"<xs:simpleType name="Value" xmlns:xs="http://www.w3.org/2001/XMLSchema">{restriction}</xs:simpleType>"#
+    ))
+    .unwrap();
+    let restriction = simple_type.restriction.unwrap();
+
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+    let base = restriction.base.clone().unwrap();
+    let rust_type = RustTypesMapping::get(&context, &base).unwrap();
+
+    implement(&restriction, &context, &base, &rust_type)
+      .unwrap()
+      .map(|(value, _)| value.to_string())
+  }
+
+  #[test]
+  fn lower_bound() {
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:int"><xs:minInclusive value="1"/></xs:restriction>"#
+      ),
+      Some("1i32".to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:int"><xs:minExclusive value="5"/></xs:restriction>"#
+      ),
+      Some("6i32".to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:double"><xs:minExclusive value="0"/><xs:maxExclusive value="1"/></xs:restriction>"#
+      ),
+      Some("0.1f64".to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:int"><xs:maxExclusive value="-5"/></xs:restriction>"#
+      ),
+      Some("- 6i32".to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:long"><xs:maxExclusive value="0"/><xs:totalDigits value="3"/></xs:restriction>"#
+      ),
+      Some("- 1i64".to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:negativeInteger"><xs:totalDigits value="3"/></xs:restriction>"#
+      ),
+      Some(r#""-1" . parse () . unwrap_or_default ()"#.to_string())
+    );
+  }
+
+  #[test]
+  fn lexical_sample() {
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:string"><xs:pattern value="[A-Z]{2}\d+"/><xs:minLength value="4"/></xs:restriction>"#
+      ),
+      Some(r#""AA00" . into ()"#.to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:integer"><xs:pattern value="[1-9][0-9]{4}"/></xs:restriction>"#
+      ),
+      Some("10000i64".to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:hexBinary"><xs:length value="2"/></xs:restriction>"#
+      ),
+      Some(r#""0000" . parse () . unwrap_or_default ()"#.to_string())
+    );
+  }
+
+  #[test]
+  fn no_default_value() {
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:int"><xs:minExclusive value="1"/><xs:maxExclusive value="2"/></xs:restriction>"#
+      ),
+      None
+    );
+    // the value of a pattern is not a date time
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:dateTime"><xs:pattern value=".+Z"/></xs:restriction>"#
+      ),
+      None
+    );
+    // an exclusive bound is not compared with the values of the other types
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:date"><xs:minExclusive value="2000-01-01"/></xs:restriction>"#
+      ),
+      None
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:gMonthDay"><xs:minInclusive value="--02-30"/></xs:restriction>"#
+      ),
+      None
+    );
+  }
+
+  #[test]
+  fn temporal_bound() {
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:date"><xs:minInclusive value="2000-02-29"/></xs:restriction>"#
+      ),
+      Some(r#""2000-02-29" . parse () . unwrap_or_default ()"#.to_string())
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:dateTime"><xs:pattern value="\d{4}-\d{2}-\d{2}T00:00:00Z"/></xs:restriction>"#
+      ),
+      None
+    );
+    assert_eq!(
+      default_value(
+        r#"<xs:restriction base="xs:duration"><xs:maxInclusive value="PT1H"/></xs:restriction>"#
+      ),
+      Some(r#""PT1H" . parse () . unwrap_or_default ()"#.to_string())
+    );
+  }
+}
@@ -1,6 +1,10 @@
 use crate::xsd::{
-  annotation::Annotation, complex_type::ComplexType, max_occurences::MaxOccurences,
-  rust_types_mapping::RustTypesMapping, simple_type::SimpleType, Implementation, XsdContext,
+  annotation::Annotation,
+  complex_type::{implement_struct, value_constraint_attribute, ComplexType},
+  max_occurences::MaxOccurences,
+  rust_types_mapping::RustTypesMapping,
+  simple_type::{self, SimpleType},
+  Error, Implementation, Result, XsdContext,
 };
 use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
@@ -21,6 +25,12 @@ pub struct Element {
   pub min_occurences: Option<u64>,
   #[yaserde(rename = "maxOccurs", attribute = true)]
   pub max_occurences: Option<MaxOccurences>,
+  #[yaserde(attribute = true)]
+  pub default: Option<String>,
+  #[yaserde(attribute = true)]
+  pub fixed: Option<String>,
+  #[yaserde(attribute = true)]
+  pub nillable: Option<String>,
   #[yaserde(rename = "complexType")]
   pub complex_type: Option<ComplexType>,
   #[yaserde(rename = "simpleType")]
@@ -35,7 +45,7 @@ impl Implementation for Element {
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     let struct_name = Ident::new(
       &self.name.replace('.', "_").to_upper_camel_case(),
       Span::call_site(),
@@ -43,7 +53,7 @@ impl Implementation for Element {
     let (fields, extra_structs) = if let Some(kind) = &self.kind {
       let subtype_mode = RustTypesMapping::subtype_mode(context, kind);
 
-      let extern_type = RustTypesMapping::get(context, kind);
+      let extern_type = RustTypesMapping::get(context, kind)?;
 
       (
         quote!(
@@ -57,29 +67,41 @@ impl Implementation for Element {
         .complex_type
         .iter()
         .map(|complex_type| {
-          complex_type.get_field_implementation(namespace_definition, context, prefix)
+          complex_type.get_field_implementation(namespace_definition, context, prefix, &struct_name)
         })
-        .collect();
+        .collect::<Result<_>>()?;
 
-      (fields_definition, quote!())
+      let choices = self
+        .complex_type
+        .iter()
+        .map(|complex_type| {
+          complex_type.get_choices_implementation(
+            context,
+            namespace_definition,
+            prefix,
+            &struct_name,
+          )
+        })
+        .collect::<Result<_>>()?;
+
+      (fields_definition, choices)
     };
 
     let docs = self
       .annotation
       .as_ref()
       .map(|annotation| annotation.implement(namespace_definition, prefix, context))
+      .transpose()?
       .unwrap_or_default();
 
-    quote! {
+    let structure = implement_struct(namespace_definition, &struct_name, fields)?;
+
+    Ok(quote! {
       #docs
-      #[derive(Clone, Debug, Default, PartialEq, hifa_yaserde_derive::YaDeserialize, hifa_yaserde_derive::YaSerialize)]
-      #namespace_definition
-      pub struct #struct_name {
-        #fields
-      }
+      #structure
 
       #extra_structs
-    }
+    })
   }
 }
 
@@ -89,9 +111,9 @@ impl Element {
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     if self.complex_type.is_none() {
-      return quote!();
+      return Ok(quote!());
     }
 
     self.implement(namespace_definition, prefix, context)
@@ -103,24 +125,19 @@ impl Element {
     prefix: &Option<String>,
     inheritable_multiple: bool,
     optional: bool,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     let refers = self.get_refers();
     if self.name.is_empty() && refers.is_none() {
-      return quote!();
+      return Ok(quote!());
     }
 
-    let multiple = inheritable_multiple
-      || (self.max_occurences.is_some()
-        && self.max_occurences != Some(MaxOccurences::Number { value: 1 }));
+    let multiple = inheritable_multiple || self.is_multiple();
 
-    let name = if self.name.to_lowercase() == "type" {
-      "kind".to_string()
-    } else if !self.name.is_empty() {
-      self.name.to_snake_case()
-    } else {
-      refers
-        .expect("[Element] refers should be defined")
-        .to_snake_case()
+    let name = match refers {
+      _ if self.name.to_lowercase() == "type" => "kind".to_string(),
+      _ if !self.name.is_empty() => self.name.to_snake_case(),
+      Some(refers) => refers.to_snake_case(),
+      None => unreachable!(),
     };
 
     log::info!("Generate element {:?}", name);
@@ -131,18 +148,115 @@ impl Element {
       name
     };
     let attribute_name = Ident::new(&name, Span::call_site());
-    let yaserde_rename = if !self.name.is_empty() {
-      &self.name
+    let yaserde_rename = match refers {
+      Some(refers) if self.name.is_empty() => refers,
+      _ => &self.name,
+    };
+
+    let rust_type = context.fallback(
+      "element",
+      Some(yaserde_rename),
+      self.get_rust_type(context, refers),
+      || quote!(String),
+    )?;
+
+    let has_simple_content = self.has_simple_content(context);
+    if !has_simple_content && (self.default.is_some() || self.fixed.is_some()) {
+      // in lenient mode, the declarations are ignored
+      context.fallback(
+        "element",
+        Some(yaserde_rename),
+        Err(Error::new(
+          "default and fixed are only supported on an element of a simple type",
+        )),
+        TokenStream::new,
+      )?;
+    }
+
+    let value_constraint = if has_simple_content {
+      let value_constraint = simple_type::typed_value_constraint(
+        context,
+        self.kind.as_deref(),
+        self.simple_type.as_ref(),
+        &rust_type,
+        self.default.as_deref(),
+        self.fixed.as_deref(),
+      )
+      .and_then(|value| {
+        value_constraint_attribute(self.default.as_deref(), self.fixed.as_deref(), value)
+      });
+      context.fallback(
+        "element",
+        Some(yaserde_rename),
+        value_constraint,
+        TokenStream::new,
+      )?
     } else {
-      refers.expect("[Element] refers should be defined")
+      TokenStream::new()
+    };
+
+    let rust_type = match (self.is_nillable(), has_simple_content) {
+      (false, _) => rust_type,
+      (true, true) => quote!(hifa_xml_schema::nillable::Nillable<#rust_type>),
+      (true, false) => quote!(hifa_xml_schema::nillable::NillableComplex<#rust_type>),
     };
 
+    let rust_type = if multiple {
+      quote!(Vec<#rust_type>)
+    } else {
+      rust_type
+    };
+
+    let optional_enumeration = self
+      .kind
+      .as_deref()
+      .map(|kind| context.is_optional_enumeration(kind))
+      .unwrap_or_default();
+
+    let rust_type =
+      if optional || (!multiple && (self.min_occurences == Some(0) || optional_enumeration)) {
+        quote!(Option<#rust_type>)
+      } else {
+        rust_type
+      };
+
+    let prefix_attribute = prefix
+      .as_ref()
+      .map(|prefix| quote!(, prefix=#prefix))
+      .unwrap_or_default();
+
+    Ok(quote! {
+      #[yaserde(rename=#yaserde_rename #prefix_attribute)]
+      #value_constraint
+      pub #attribute_name: #rust_type,
+    })
+  }
+
+  pub fn is_nillable(&self) -> bool {
+    matches!(self.nillable.as_deref().map(str::trim), Some("true" | "1"))
+  }
+
+  /// The value of the element is text: a built-in type, a simple type of the schema or an inline
+  /// simple type.
+  pub fn has_simple_content(&self, context: &XsdContext) -> bool {
+    self.simple_type.is_some()
+      || self
+        .kind
+        .as_deref()
+        .is_some_and(|kind| context.is_simple_type(kind))
+  }
+
+  pub fn is_multiple(&self) -> bool {
+    self.max_occurences.is_some() && self.max_occurences != Some(MaxOccurences::Number { value: 1 })
+  }
+
+  pub fn get_rust_type(&self, context: &XsdContext, refers: Option<&str>) -> Result<TokenStream> {
     let rust_type = if let Some(complex_type) = &self.complex_type {
       complex_type.get_integrated_implementation(&self.name)
     } else if let Some(simple_type) = &self.simple_type {
-      simple_type.get_type_implementation(context, &Some(self.name.to_owned()), None)
+      simple_type.get_type_implementation(context, &Some(self.name.to_owned()), None)?
     } else if let Some(kind) = &self.kind {
-      RustTypesMapping::get(context, kind)
+      RustTypesMapping::get(context, kind)?
     } else if let Some(refers) = refers {
       let module = (!context.is_in_sub_module()
         && !self
@@ -155,52 +269,26 @@ impl Element {
       .then_some(quote!(xml_schema_types::))
       .unwrap_or_default();
 
-      let rust_type = RustTypesMapping::get(context, refers);
+      let rust_type = RustTypesMapping::get(context, refers)?;
       quote!( hifa_yaserde :: ext :: Boxed <#module #rust_type>)
     } else {
-      panic!(
-        "[Element] {:?} unimplemented type: {:?}",
-        self.name, self.kind,
-      );
+      return Err(Error::new("missing type of element"));
     };
 
     let module = (!context.is_in_sub_module()
       && !self
         .kind
         .as_ref()
-        .map(|kind| {
-          RustTypesMapping::is_xs_string(context, kind)
-            || RustTypesMapping::is_xs_int(context, kind)
-        })
+        .map(|kind| RustTypesMapping::is_xs_type(context, kind))
         .unwrap_or_default()
-      && !self.get_refers().is_some())
+      && self.get_refers().is_none())
     .then_some(quote!(xml_schema_types::))
     .unwrap_or_default();
 
-    let rust_type = if multiple {
-      quote!(Vec<#module #rust_type>)
-    } else {
-      quote!(#module #rust_type)
-    };
-
-    let rust_type = if optional || (!multiple && self.min_occurences == Some(0)) {
-      quote!(Option<#rust_type>)
-    } else {
-      rust_type
-    };
-
-    let prefix_attribute = prefix
-      .as_ref()
-      .map(|prefix| quote!(, prefix=#prefix))
-      .unwrap_or_default();
-
-    quote! {
-      #[yaserde(rename=#yaserde_rename #prefix_attribute)]
-      pub #attribute_name: #rust_type,
-    }
+    Ok(quote!(#module #rust_type))
   }
 
-  fn get_refers(&self) -> Option<&str> {
+  pub fn get_refers(&self) -> Option<&str> {
     self.refers.as_ref().and_then(|refers| {
       if refers.is_empty() {
         None
@@ -229,6 +317,9 @@ mod tests {
       refers: None,
       min_occurences: None,
       max_occurences: None,
+      default: None,
+      fixed: None,
+      nillable: None,
       complex_type: None,
       simple_type: None,
       annotation: Some(Annotation {
@@ -242,7 +333,7 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = element.implement(&quote!(), &None, &context);
+    let implementation = element.implement(&quote!(), &None, &context).unwrap();
 
     let expected = TokenStream::from_str(&format!(
       r#"
@@ -266,6 +357,9 @@ mod tests {
       refers: None,
       min_occurences: None,
       max_occurences: None,
+      default: None,
+      fixed: None,
+      nillable: None,
       complex_type: None,
       simple_type: None,
       annotation: Some(Annotation {
@@ -279,7 +373,7 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = element.implement(&quote!(), &None, &context);
+    let implementation = element.implement(&quote!(), &None, &context).unwrap();
 
     let expected = TokenStream::from_str(&format!(
       r#"
@@ -304,6 +398,9 @@ mod tests {
       refers: Some("OwnedType".to_string()),
       min_occurences: None,
       max_occurences: None,
+      default: None,
+      fixed: None,
+      nillable: None,
       complex_type: None,
       simple_type: None,
       annotation: None,
@@ -313,7 +410,9 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = element.get_field_implementation(&context, &None, false, false);
+    let implementation = element
+      .get_field_implementation(&context, &None, false, false)
+      .unwrap();
 
     let expected = TokenStream::from_str(
       r#"#[yaserde(rename = "OwnedType")] pub owned_type : hifa_yaserde :: ext :: Boxed < xml_schema_types :: OwnedType > ,"#,
@@ -329,12 +428,17 @@ mod tests {
       refers: Some("OwnedType".to_string()),
       min_occurences: Some(0),
       max_occurences: Some(MaxOccurences::Unbounded),
+      default: None,
+      fixed: None,
+      nillable: None,
       complex_type: None,
       simple_type: None,
       annotation: None,
     };
 
-    let implementation = element.get_field_implementation(&context, &None, false, false);
+    let implementation = element
+      .get_field_implementation(&context, &None, false, false)
+      .unwrap();
 
     let expected = TokenStream::from_str(
       r#"#[yaserde(rename = "OwnedType")] pub owned_type_list : Vec < hifa_yaserde :: ext :: Boxed < xml_schema_types :: OwnedType > > ,"#
@@ -343,4 +447,78 @@ mod tests {
 
     assert_eq!(implementation.to_string(), expected.to_string());
   }
+
+  #[test]
+  fn nillable_element_with_default() {
+    let element = Element {
+      name: "rate".to_string(),
+      kind: Some("xs:int".to_string()),
+      refers: None,
+      min_occurences: Some(0),
+      max_occurences: None,
+      default: Some("5".to_string()),
+      fixed: None,
+      nillable: Some("true".to_string()),
+      complex_type: None,
+      simple_type: None,
+      annotation: None,
+    };
+
+    let mut context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    let implementation = element
+      .get_field_implementation(&context, &None, false, false)
+      .unwrap();
+
+    let expected = TokenStream::from_str(
+      r#"#[yaserde(rename = "rate")] #[xml_schema(default = "5", value = 5i32)] pub rate : Option < hifa_xml_schema :: nillable :: Nillable < i32 > > ,"#,
+    )
+    .unwrap();
+
+    assert_eq!(implementation.to_string(), expected.to_string());
+
+    // the values of a complex type are not text
+    let element = Element {
+      kind: Some("Person".to_string()),
+      ..element
+    };
+
+    let error = element
+      .get_field_implementation(&context, &None, false, false)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "default and fixed are only supported on an element of a simple type"
+    );
+
+    // ignored in lenient mode
+    context.set_mode(crate::xsd::Mode::Lenient);
+    let implementation = element
+      .get_field_implementation(&context, &None, false, false)
+      .unwrap();
+    assert_eq!(
+      context.skipped()[0].to_string(),
+      r#"element "rate": default and fixed are only supported on an element of a simple type"#
+    );
+
+    let expected = TokenStream::from_str(
+      r#"#[yaserde(rename = "rate")] pub rate : Option < hifa_xml_schema :: nillable :: NillableComplex < xml_schema_types :: Person > > ,"#,
+    )
+    .unwrap();
+
+    assert_eq!(implementation.to_string(), expected.to_string());
+
+    // a nillable element of a complex type is supported in strict mode
+    context.set_mode(crate::xsd::Mode::Strict);
+    let element = Element {
+      default: None,
+      ..element
+    };
+    let implementation = element
+      .get_field_implementation(&context, &None, false, false)
+      .unwrap();
+    assert_eq!(implementation.to_string(), expected.to_string());
+  }
 }
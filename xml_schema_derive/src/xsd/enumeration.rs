@@ -1,8 +1,127 @@
+use heck::ToUpperCamelCase;
+use proc_macro2::{Ident, Span};
+
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(prefix = "xs", namespaces = {
     "xs" = "http://www.w3.org/2001/XMLSchema"
   })]
 pub struct Enumeration {
- #[yaserde(attribute = true)]
+  #[yaserde(attribute = true)]
   pub value: String,
 }
+
+/// Names of the variants of an enum, one per value.
+/// A name colliding with a previous one, or with a reserved one, is suffixed with a number (ie. `Value2`).
+pub fn variant_names(enumerations: &[Enumeration], reserved: &[&str]) -> Vec<Ident> {
+  let mut names: Vec<String> = reserved.iter().map(|name| name.to_string()).collect();
+
+  for enumeration in enumerations {
+    let base_name = variant_name(&enumeration.value);
+    let mut name = base_name.clone();
+    let mut index = 2;
+    while names.contains(&name) {
+      name = format!("{base_name}{index}");
+      index += 1;
+    }
+    names.push(name);
+  }
+
+  names
+    .iter()
+    .skip(reserved.len())
+    .map(|name| Ident::new(name, Span::call_site()))
+    .collect()
+}
+
+/// UpperCamelCase identifier of a value, ie. `application/json` is named `ApplicationJson`.
+/// The digits of different words are separated (`1.0` is `_1_0`), a leading digit is prefixed
+/// with `_`, and a keyword suffixed with `_` (`self` is `Self_`).
+fn variant_name(value: &str) -> String {
+  let mut name = match value.trim_start().chars().next() {
+    Some('-') => "Minus".to_string(),
+    Some('+') => "Plus".to_string(),
+    _ => String::new(),
+  };
+
+  for word in value
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|word| !word.is_empty())
+  {
+    let word = word.to_upper_camel_case();
+    if name.ends_with(|c: char| c.is_ascii_digit())
+      && word.starts_with(|c: char| c.is_ascii_digit())
+    {
+      name.push('_');
+    }
+    name.push_str(&word);
+  }
+
+  if name.is_empty() {
+    return if value.is_empty() { "Empty" } else { "Value" }.to_string();
+  }
+
+  if name.starts_with(|c: char| c.is_ascii_digit()) {
+    name.insert(0, '_');
+  }
+
+  if syn::parse_str::<Ident>(&name).is_err() {
+    name.push('_');
+  }
+
+  if syn::parse_str::<Ident>(&name).is_err() {
+    return "Value".to_string();
+  }
+
+  name
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sanitized_variant_names() {
+    let values = [
+      "1.0",
+      "10",
+      "application/json",
+      "en-US",
+      "self",
+      "",
+      "-1",
+      "r",
+      "R",
+      "*",
+      "other",
+    ];
+
+    let enumerations: Vec<Enumeration> = values
+      .iter()
+      .map(|value| Enumeration {
+        value: value.to_string(),
+      })
+      .collect();
+
+    let names: Vec<String> = variant_names(&enumerations, &["Other"])
+      .iter()
+      .map(|name| name.to_string())
+      .collect();
+
+    assert_eq!(
+      names,
+      vec![
+        "_1_0",
+        "_10",
+        "ApplicationJson",
+        "EnUs",
+        "Self_",
+        "Empty",
+        "Minus1",
+        "R",
+        "R2",
+        "Value",
+        "Other2",
+      ]
+    );
+  }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+use std::io::Cursor;
+use xml::reader::{EventReader, XmlEvent};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error raised when a XSD can not be loaded or generated.
+/// It is located in the XSD file with the path of the component which fails.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Error {
+  message: String,
+  file: Option<String>,
+  position: Option<(u64, u64)>,
+  components: Vec<Component>,
+}
+
+/// A XSD component, ie. `complexType "MyType"`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component {
+  kind: String,
+  name: Option<String>,
+}
+
+impl Error {
+  pub fn new<S: Into<String>>(message: S) -> Self {
+    Error {
+      message: message.into(),
+      ..Default::default()
+    }
+  }
+
+  /// Add the parent component, errors are raised from the inner component.
+  pub fn with_component(mut self, kind: &str, name: Option<&str>) -> Self {
+    self.components.insert(
+      0,
+      Component {
+        kind: kind.to_string(),
+        name: name
+          .filter(|name| !name.is_empty())
+          .map(|name| name.to_string()),
+      },
+    );
+    self
+  }
+
+  /// Set the file and the position of the component, if it is found in the XSD content.
+  pub fn locate(self, file: &str, content: &str) -> Self {
+    if self.file.is_some() {
+      return self;
+    }
+
+    match find_position(content, &self.components) {
+      Some(position) => Error {
+        file: Some(file.to_string()),
+        position: Some(position),
+        ..self
+      },
+      None => self,
+    }
+  }
+
+  pub fn with_file(self, file: &str) -> Self {
+    if self.file.is_some() {
+      return self;
+    }
+
+    Error {
+      file: Some(file.to_string()),
+      ..self
+    }
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(file) = &self.file {
+      write!(f, "{file}")?;
+      if let Some((line, column)) = self.position {
+        write!(f, ":{line}:{column}")?;
+      }
+      write!(f, ": ")?;
+    }
+
+    if !self.components.is_empty() {
+      let path = self
+        .components
+        .iter()
+        .map(|component| component.to_string())
+        .collect::<Vec<String>>()
+        .join(" / ");
+      write!(f, "{path}: ")?;
+    }
+
+    write!(f, "{}", self.message)
+  }
+}
+
+impl fmt::Display for Component {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.name {
+      Some(name) => write!(f, "{} {:?}", self.kind, name),
+      None => write!(f, "{}", self.kind),
+    }
+  }
+}
+
+impl From<String> for Error {
+  fn from(message: String) -> Self {
+    Error::new(message)
+  }
+}
+
+impl From<&str> for Error {
+  fn from(message: &str) -> Self {
+    Error::new(message)
+  }
+}
+
+/// Find the line and column (starting at 1) of the deepest component of the path.
+fn find_position(content: &str, components: &[Component]) -> Option<(u64, u64)> {
+  if components.is_empty() {
+    return None;
+  }
+
+  let mut parser = EventReader::new(Cursor::new(content));
+  let mut depth = 0;
+  let mut matched_depths: Vec<usize> = vec![];
+  let mut position = None;
+
+  loop {
+    match parser.next() {
+      Ok(XmlEvent::StartElement {
+        name, attributes, ..
+      }) => {
+        depth += 1;
+
+        let Some(component) = components.get(matched_depths.len()) else {
+          continue;
+        };
+
+        let name_matches = match &component.name {
+          Some(component_name) => attributes.iter().any(|attribute| {
+            (attribute.name.local_name == "name" || attribute.name.local_name == "ref")
+              && &attribute.value == component_name
+          }),
+          None => true,
+        };
+
+        if name.local_name == component.kind && name_matches {
+          matched_depths.push(depth);
+          let text_position = xml::common::Position::position(&parser);
+          position = Some((text_position.row + 1, text_position.column + 1));
+
+          if matched_depths.len() == components.len() {
+            return position;
+          }
+        }
+      }
+      Ok(XmlEvent::EndElement { .. }) => {
+        if matched_depths.last() == Some(&depth) {
+          matched_depths.pop();
+        }
+        depth -= 1;
+      }
+      Ok(XmlEvent::EndDocument) | Err(_) => break,
+      Ok(_) => {}
+    }
+  }
+
+  position
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  static XSD: &str = r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+  <xs:complexType name="First">
+    <xs:attribute name="value" type="xs:string"/>
+  </xs:complexType>
+  <xs:complexType name="Second">
+    <xs:attribute name="value" type="xs:unknown"/>
+  </xs:complexType>
+</xs:schema>"#;
+
+  #[test]
+  fn error_display() {
+    let error = Error::new("Type \"unknown\" not implemented")
+      .with_component("attribute", Some("value"))
+      .with_component("complexType", Some("Second"));
+
+    assert_eq!(
+      error.to_string(),
+      r#"complexType "Second" / attribute "value": Type "unknown" not implemented"#
+    );
+
+    let error = error.locate("schema.xsd", XSD);
+
+    assert_eq!(
+      error.to_string(),
+      r#"schema.xsd:6:5: complexType "Second" / attribute "value": Type "unknown" not implemented"#
+    );
+  }
+
+  #[test]
+  fn error_without_component() {
+    let error = Error::new("Bad XML Schema").locate("schema.xsd", XSD);
+    assert_eq!(error.to_string(), "Bad XML Schema");
+
+    let error = error.with_file("schema.xsd");
+    assert_eq!(error.to_string(), "schema.xsd: Bad XML Schema");
+  }
+}
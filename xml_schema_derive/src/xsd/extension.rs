@@ -1,8 +1,14 @@
 use crate::xsd::{
-  attribute::Attribute, choice::Choice, group::Group, rust_types_mapping::RustTypesMapping,
-  sequence::Sequence, Implementation, XsdContext,
+  attribute::Attribute,
+  attribute_group::{resolve_attributes, AttributeGroupReference},
+  choice::{self, Choice},
+  group::{self, Group},
+  rust_types_mapping::RustTypesMapping,
+  sequence::Sequence,
+  Implementation, Result, XsdContext,
 };
 use proc_macro2::TokenStream;
+use syn::Ident;
 
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(
@@ -17,6 +23,8 @@ pub struct Extension {
   pub base: String,
   #[yaserde(rename = "attribute")]
   pub attributes: Vec<Attribute>,
+  #[yaserde(rename = "attributeGroup")]
+  pub attribute_groups: Vec<AttributeGroupReference>,
   #[yaserde(rename = "sequence")]
   pub sequences: Vec<Sequence>,
   #[yaserde(rename = "group")]
@@ -31,8 +39,8 @@ impl Implementation for Extension {
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
-    let rust_type = RustTypesMapping::get(context, &self.base);
+  ) -> Result<TokenStream> {
+    let rust_type = RustTypesMapping::get(context, &self.base)?;
 
     // TODO: implement sequences
     // let sequences: TokenStream = self
@@ -41,11 +49,15 @@ impl Implementation for Extension {
     //   .map(|sequence| sequence.implement(namespace_definition, prefix, context))
     //   .collect();
 
-    let attributes: TokenStream = self
-      .attributes
-      .iter()
-      .map(|attribute| attribute.implement(namespace_definition, prefix, context))
-      .collect();
+    let attributes: TokenStream =
+      resolve_attributes(&self.attributes, &self.attribute_groups, context)?
+        .iter()
+        .map(|attribute| {
+          attribute
+            .implement(namespace_definition, prefix, context)
+            .map_err(|error| error.with_component("attribute", attribute.name.as_deref()))
+        })
+        .collect::<Result<_>>()?;
 
     let inner_attribute = if format!("{rust_type}") == "String" {
       quote!(#[yaserde(text = true)])
@@ -53,63 +65,88 @@ impl Implementation for Extension {
       TokenStream::new()
     };
 
-    quote!(
+    Ok(quote!(
       #inner_attribute
       pub base: #rust_type,
       #attributes
-    )
+    ))
   }
 }
 
 impl Extension {
   pub fn get_field_implementation(
     &self,
-    namespace_definition: &TokenStream,
+    _namespace_definition: &TokenStream,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
-    let rust_type = RustTypesMapping::get(context, &self.base);
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    let rust_type = RustTypesMapping::get(context, &self.base)?;
 
     let sequences: TokenStream = self
       .sequences
       .iter()
-      .map(|sequence| sequence.implement(namespace_definition, prefix, context))
-      .collect();
+      .map(|sequence| {
+        sequence
+          .as_content()
+          .get_field_implementation(context, prefix, struct_name)
+          .map_err(|error| error.with_component("sequence", None))
+      })
+      .collect::<Result<_>>()?;
 
-    let attributes: TokenStream = self
-      .attributes
-      .iter()
-      .map(|attribute| attribute.implement(&TokenStream::new(), prefix, context))
-      .collect();
+    let attributes: TokenStream =
+      resolve_attributes(&self.attributes, &self.attribute_groups, context)?
+        .iter()
+        .map(|attribute| {
+          attribute
+            .implement(&TokenStream::new(), prefix, context)
+            .map_err(|error| error.with_component("attribute", attribute.name.as_deref()))
+        })
+        .collect::<Result<_>>()?;
 
     let choices: TokenStream = self
       .choices
       .iter()
-      .map(|choice| choice.get_field_implementation(context, prefix))
-      .collect();
+      .enumerate()
+      .map(|(index, choice)| {
+        let (field_name, enum_name) = choice::choice_names(struct_name, index);
+        choice
+          .get_field_implementation(context, &field_name, &enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .collect::<Result<_>>()?;
 
     let group_content = self
       .group
       .as_ref()
       .map(|group| {
-        let group_type = group.get_type_implementation(context, prefix);
-
+        group
+          .get_type_implementation(context, prefix)
+          .and_then(|group_type| {
+            let elements = group.get_elements(context, &mut vec![])?;
+            Ok((group_type, group::elements_attribute(&elements)))
+          })
+          .map_err(|error| error.with_component("group", group.reference.as_deref()))
+      })
+      .transpose()?
+      .map(|(group_type, elements)| {
         quote!(
           #[yaserde(flatten = true)]
+          #elements
           pub extension : #group_type ,
         )
       })
       .unwrap_or_default();
 
     let subtype_mode = RustTypesMapping::subtype_mode(context, &self.base);
-    quote!(
+    Ok(quote!(
       #[yaserde(#subtype_mode)]
       pub base : #rust_type ,
       #sequences
       #attributes
       #choices
       #group_content
-    )
+    ))
   }
 
   pub fn get_sub_type_implementation(
@@ -117,23 +154,46 @@ impl Extension {
     namespace_definition: &TokenStream,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
     let attributes_sub_types: TokenStream = self
       .attributes
       .iter()
-      .map(|attribute| attribute.get_sub_type_implementation(namespace_definition, context, prefix))
-      .collect();
+      .map(|attribute| {
+        attribute
+          .get_sub_type_implementation(namespace_definition, context, prefix)
+          .map_err(|error| error.with_component("attribute", attribute.name.as_deref()))
+      })
+      .collect::<Result<_>>()?;
 
     let sequence_sub_types: TokenStream = self
       .sequences
       .iter()
-      .map(|sequence| sequence.get_sub_types_implementation(context, namespace_definition, prefix))
-      .collect();
+      .map(|sequence| {
+        sequence
+          .as_content()
+          .get_sub_types_implementation(context, namespace_definition, prefix, struct_name)
+          .map_err(|error| error.with_component("sequence", None))
+      })
+      .collect::<Result<_>>()?;
 
-    quote!(
+    let choice_sub_types: TokenStream = self
+      .choices
+      .iter()
+      .enumerate()
+      .map(|(index, choice)| {
+        let (_field_name, enum_name) = choice::choice_names(struct_name, index);
+        choice
+          .get_sub_types_implementation(context, namespace_definition, prefix, &enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .collect::<Result<_>>()?;
+
+    Ok(quote!(
       #attributes_sub_types
       #sequence_sub_types
-    )
+      #choice_sub_types
+    ))
   }
 }
 
@@ -147,6 +207,7 @@ mod tests {
     let st = Extension {
       base: "xs:string".to_string(),
       attributes: vec![],
+      attribute_groups: vec![],
       sequences: vec![],
       group: None,
       choices: vec![],
@@ -156,7 +217,7 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = st.implement(&TokenStream::new(), &None, &context);
+    let implementation = st.implement(&TokenStream::new(), &None, &context).unwrap();
 
     let expected = TokenStream::from_str(
       r#"
@@ -179,6 +240,8 @@ mod tests {
         Attribute {
           name: Some("attribute_1".to_string()),
           kind: Some("xs:string".to_string()),
+          default: None,
+          fixed: None,
           reference: None,
           required: Required::Required,
           simple_type: None,
@@ -186,11 +249,14 @@ mod tests {
         Attribute {
           name: Some("attribute_2".to_string()),
           kind: Some("xs:boolean".to_string()),
+          default: None,
+          fixed: None,
           reference: None,
           required: Required::Optional,
           simple_type: None,
         },
       ],
+      attribute_groups: vec![],
       sequences: vec![],
       group: None,
       choices: vec![],
@@ -200,15 +266,17 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = st.implement(&TokenStream::new(), &None, &context);
+    let implementation = st.implement(&TokenStream::new(), &None, &context).unwrap();
 
     let expected = TokenStream::from_str(
       r#"
        #[yaserde(text= true)]
         pub base: String,
        #[yaserde(attribute = true)]
+       #[xml_schema(attribute = "attribute_1")]
         pub attribute_1: String,
        #[yaserde(attribute = true)]
+       #[xml_schema(attribute = "attribute_2")]
         pub attribute_2: Option<bool> ,
       "#,
     )
@@ -0,0 +1,9 @@
+/// A constraining facet of a restriction, ie. `<xs:maxLength value="8"/>`.
+#[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
+#[yaserde(prefix = "xs", namespaces = {
+    "xs" = "http://www.w3.org/2001/XMLSchema"
+  })]
+pub struct Facet {
+  #[yaserde(attribute = true)]
+  pub value: String,
+}
@@ -1,7 +1,14 @@
 use crate::xsd::{
-  rust_types_mapping::RustTypesMapping, sequence::Sequence, Implementation, XsdContext,
+  all::All,
+  choice::{self, Choice},
+  complex_type::implement_struct,
+  element::Element,
+  max_occurences::MaxOccurences,
+  rust_types_mapping::RustTypesMapping,
+  sequence::Sequence,
+  Error, Implementation, Result, XsdContext,
 };
-use heck::ToUpperCamelCase;
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
 use syn::Ident;
 
@@ -14,37 +21,67 @@ pub struct Group {
   pub name: Option<String>,
   #[yaserde(attribute = true, rename = "ref")]
   pub reference: Option<String>,
+  #[yaserde(rename = "minOccurs", attribute = true)]
+  pub min_occurences: Option<u64>,
+  #[yaserde(rename = "maxOccurs", attribute = true)]
+  pub max_occurences: Option<MaxOccurences>,
   #[yaserde()]
   pub sequence: Option<Sequence>,
+  #[yaserde()]
+  pub choice: Option<Choice>,
+  #[yaserde()]
+  pub all: Option<All>,
 }
 
 impl Implementation for Group {
+  /// The named group is generated as a structure, flattened in the structures referencing it.
   fn implement(
     &self,
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
-    if self.name.is_none() {
-      return quote!();
-    }
-    let raw_name = self.name.clone().unwrap();
+  ) -> Result<TokenStream> {
+    let Some(raw_name) = &self.name else {
+      return Ok(quote!());
+    };
 
-    let struct_name = Ident::new(&raw_name.to_upper_camel_case(), Span::call_site());
+    let struct_name = group_struct_name(raw_name);
+    let (choice_field_name, choice_enum_name) = choice::choice_names(&struct_name, 0);
 
-    let fields = self
-      .sequence
-      .as_ref()
-      .map(|sequence| sequence.get_field_implementation(context, prefix))
-      .unwrap_or_default();
+    let (fields, sub_types) = if let Some(sequence) = &self.sequence {
+      (
+        sequence.get_field_implementation(context, prefix, &struct_name),
+        sequence.get_sub_types_implementation(context, namespace_definition, prefix, &struct_name),
+      )
+    } else if let Some(choice) = &self.choice {
+      (
+        choice.get_field_implementation(context, &choice_field_name, &choice_enum_name),
+        choice.get_sub_types_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &choice_enum_name,
+        ),
+      )
+    } else if let Some(all) = &self.all {
+      (
+        all.get_field_implementation(context, prefix),
+        all.get_sub_types_implementation(context, namespace_definition, prefix),
+      )
+    } else {
+      (Ok(TokenStream::new()), Ok(TokenStream::new()))
+    };
+    let compositor = self.compositor_name();
+    let fields = fields.map_err(|error| error.with_component(compositor, None))?;
+    let sub_types = sub_types.map_err(|error| error.with_component(compositor, None))?;
 
-    quote!(
-      #[derive(Clone, Debug, Default, PartialEq, yaserde_derive::YaDeserialize, yaserde_derive::YaSerialize)]
-      #namespace_definition
-      pub struct #struct_name {
-        #fields
-      }
-    )
+    let elements = self.get_elements(context, &mut vec![raw_name.clone()])?;
+    let structure = implement_group_struct(namespace_definition, &struct_name, fields, &elements)?;
+
+    Ok(quote!(
+      #structure
+      #sub_types
+    ))
   }
 }
 
@@ -53,15 +90,188 @@ impl Group {
     &self,
     context: &XsdContext,
     _prefix: &Option<String>,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
+    match &self.reference {
+      Some(reference) => RustTypesMapping::get(context, reference),
+      None => Err(Error::new("missing reference for group")),
+    }
+  }
+
+  /// The field of a group reference, flattened in the parent structure.
+  pub fn get_field_implementation(
+    &self,
+    context: &XsdContext,
+    prefix: &Option<String>,
+  ) -> Result<TokenStream> {
+    let rust_type = self.get_rust_type(context, prefix)?;
+    let name = local_name(self.reference.as_deref().unwrap_or_default()).to_snake_case();
+    let elements = elements_attribute(&self.get_elements(context, &mut vec![])?);
+
+    let (field_name, field_type) = if self.is_multiple() {
+      (format_ident!("{}_list", name), quote!(Vec<#rust_type>))
+    } else if self.min_occurences == Some(0) {
+      (format_ident!("{}", name), quote!(Option<#rust_type>))
+    } else {
+      (format_ident!("{}", name), rust_type)
+    };
+
+    Ok(quote!(
+      #[yaserde(flatten = true)]
+      #elements
+      pub #field_name: #field_type,
+    ))
+  }
+
+  /// The type of a group reference, with its module outside of `xml_schema_types`.
+  pub fn get_rust_type(
+    &self,
+    context: &XsdContext,
+    prefix: &Option<String>,
+  ) -> Result<TokenStream> {
+    let rust_type = self.get_type_implementation(context, prefix)?;
+    let module = (!context.is_in_sub_module())
+      .then_some(quote!(xml_schema_types::))
+      .unwrap_or_default();
+
+    Ok(quote!(#module #rust_type))
+  }
+
+  /// The local names of the elements of the group, with whether they can be repeated.
+  /// `visited` holds the groups being resolved, to detect the circular references.
+  pub fn get_elements(
+    &self,
+    context: &XsdContext,
+    visited: &mut Vec<String>,
+  ) -> Result<Vec<(String, bool)>> {
     if let Some(reference) = &self.reference {
-      RustTypesMapping::get(context, reference)
+      let definition = context
+        .get_group(reference)
+        .ok_or_else(|| Error::new(format!("group {reference:?} not found")))?;
+      let name = definition.name.clone().unwrap_or_default();
+
+      if visited.contains(&name) {
+        return Err(Error::new(format!("circular reference of group {name:?}")));
+      }
+
+      visited.push(name);
+      let elements = definition.get_elements(context, visited)?;
+      visited.pop();
+
+      return Ok(repeat_elements(elements, self.is_multiple()));
+    }
+
+    if let Some(sequence) = &self.sequence {
+      sequence.get_elements(context, visited)
+    } else if let Some(choice) = &self.choice {
+      choice.get_elements(context, visited)
+    } else if let Some(all) = &self.all {
+      Ok(all.elements.iter().map(element_entry).collect())
+    } else {
+      Ok(vec![])
+    }
+  }
+
+  pub fn is_multiple(&self) -> bool {
+    matches!(self.min_occurences, Some(min_occurences) if min_occurences > 1)
+      || matches!(self.max_occurences, Some(MaxOccurences::Unbounded))
+      || matches!(self.max_occurences, Some(MaxOccurences::Number{value}) if value > 1)
+  }
+
+  fn compositor_name(&self) -> &'static str {
+    if self.choice.is_some() {
+      "choice"
+    } else if self.all.is_some() {
+      "all"
     } else {
-      panic!("Missing reference for group");
+      "sequence"
     }
   }
 }
 
+/// Name of the structure of a named group, or of a group reference.
+pub fn group_struct_name(name: &str) -> Ident {
+  Ident::new(
+    &local_name(name).replace('.', "_").to_upper_camel_case(),
+    Span::call_site(),
+  )
+}
+
+/// The structure of a group, and the `{Group}List` type reading its repeated or optional
+/// instances from the children of the parent element.
+pub fn implement_group_struct(
+  namespace_definition: &TokenStream,
+  struct_name: &Ident,
+  fields: TokenStream,
+  elements: &[(String, bool)],
+) -> Result<TokenStream> {
+  let structure = implement_struct(namespace_definition, struct_name, fields)?;
+  let list_name = format_ident!("{}List", struct_name);
+  let xml_name = struct_name.to_string();
+  let elements = elements_tokens(elements);
+
+  Ok(quote!(
+    #structure
+
+    /// Instances of the group, read at once from the children of the parent element.
+    #[doc(hidden)]
+    #[derive(Default)]
+    pub struct #list_name(Vec<#struct_name>);
+
+    impl hifa_yaserde::YaDeserialize for #list_name {
+      fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        hifa_xml_schema::group::read_groups(reader, #xml_name, #elements, true).map(#list_name)
+      }
+    }
+
+    impl From<#list_name> for Vec<#struct_name> {
+      fn from(list: #list_name) -> Self {
+        list.0
+      }
+    }
+
+    impl From<#list_name> for Option<#struct_name> {
+      fn from(list: #list_name) -> Self {
+        list.0.into_iter().next()
+      }
+    }
+  ))
+}
+
+/// The local name and whether the element can be repeated.
+pub fn element_entry(element: &Element) -> (String, bool) {
+  let name = match element.get_refers() {
+    Some(refers) if element.name.is_empty() => refers,
+    _ => &element.name,
+  };
+  (local_name(name).to_string(), element.is_multiple())
+}
+
+/// The elements of a repeated particle can all be repeated.
+pub fn repeat_elements(elements: Vec<(String, bool)>, multiple: bool) -> Vec<(String, bool)> {
+  elements
+    .into_iter()
+    .map(|(name, repeated)| (name, repeated || multiple))
+    .collect()
+}
+
+/// Marker of a flattened compositor with its elements, consumed by `implement_struct`.
+pub fn elements_attribute(elements: &[(String, bool)]) -> TokenStream {
+  let elements = elements_tokens(elements);
+  quote!(#[xml_schema(elements = #elements)])
+}
+
+/// The elements of a group, as the argument of the functions of `hifa_xml_schema::group`.
+pub fn elements_tokens(elements: &[(String, bool)]) -> TokenStream {
+  let elements = elements
+    .iter()
+    .map(|(name, repeated)| quote!((#name, #repeated)));
+  quote!(&[#(#elements),*])
+}
+
+fn local_name(name: &str) -> &str {
+  name.split(':').next_back().unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -85,12 +295,24 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = format!("{}", group.implement(&TokenStream::new(), &None, &context));
+    let implementation = format!(
+      "{}",
+      group
+        .implement(&TokenStream::new(), &None, &context)
+        .unwrap()
+    );
 
-    assert_eq!(implementation, "# [derive (Clone , Debug , Default , PartialEq , yaserde_derive :: YaDeserialize , yaserde_derive :: YaSerialize)] \
+    assert_eq!(implementation, "# [derive (Clone , Debug , Default , PartialEq , hifa_yaserde_derive :: YaDeserialize , hifa_yaserde_derive :: YaSerialize)] \
 pub struct Groupthing { \
 # [yaserde (rename = \"CX_X\")] pub cx_x : xml_schema_types :: Asdfg , \
-# [yaserde (rename = \"CY_X\")] pub cy_x : xml_schema_types :: Asdfg , }");
+# [yaserde (rename = \"CY_X\")] pub cy_x : xml_schema_types :: Asdfg , } \
+# [doc = r\" Instances of the group, read at once from the children of the parent element.\"] \
+# [doc (hidden)] # [derive (Default)] pub struct GroupthingList (Vec < Groupthing >) ; \
+impl hifa_yaserde :: YaDeserialize for GroupthingList { \
+fn deserialize < R : std :: io :: Read > (reader : & mut hifa_yaserde :: de :: Deserializer < R >) -> Result < Self , String > { \
+hifa_xml_schema :: group :: read_groups (reader , \"Groupthing\" , & [(\"CX_X\" , false) , (\"CY_X\" , false)] , true) . map (GroupthingList) } } \
+impl From < GroupthingList > for Vec < Groupthing > { fn from (list : GroupthingList) -> Self { list . 0 } } \
+impl From < GroupthingList > for Option < Groupthing > { fn from (list : GroupthingList) -> Self { list . 0 . into_iter () . next () } }");
   }
 
   #[test]
@@ -103,8 +325,95 @@ pub struct Groupthing { \
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let type_implementation = format!("{}", group.get_type_implementation(&context, &None));
+    let type_implementation = format!(
+      "{}",
+      group.get_type_implementation(&context, &None).unwrap()
+    );
 
     assert_eq!(type_implementation, "Groupthing");
   }
+
+  #[test]
+  fn group_references() {
+    let mut context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    let groups: Vec<Group> = [
+      r#"<group name="address"><sequence><element name="street" type="xs:string"/><group ref="city"/></sequence></group>"#,
+      r#"<group name="city"><choice><element name="town" type="xs:string"/><element name="zip" type="xs:string" maxOccurs="2"/></choice></group>"#,
+      r#"<group name="note"><all><element name="text" type="xs:string"/></all></group>"#,
+      r#"<group name="cycle"><sequence><group ref="cycle"/></sequence></group>"#,
+      r#"<group name="location"><sequence><group ref="city"/><element name="street" type="xs:string"/></sequence></group>"#,
+    ]
+    .iter()
+    .map(|xml| from_str(xml).unwrap())
+    .collect();
+    context.add_groups(&groups);
+
+    let elements = |xml: &str| {
+      let group: Group = from_str(xml).unwrap();
+      group
+        .get_elements(&context, &mut vec![])
+        .map_err(|error| error.to_string())
+    };
+
+    assert_eq!(
+      elements(r#"<group ref="address" maxOccurs="unbounded"/>"#),
+      Ok(vec![
+        ("street".to_string(), true),
+        ("town".to_string(), true),
+        ("zip".to_string(), true)
+      ])
+    );
+    assert_eq!(
+      elements(r#"<group ref="address"/>"#),
+      Ok(vec![
+        ("street".to_string(), false),
+        ("town".to_string(), false),
+        ("zip".to_string(), true)
+      ])
+    );
+    assert_eq!(
+      elements(r#"<group ref="location"/>"#),
+      Ok(vec![
+        ("town".to_string(), false),
+        ("zip".to_string(), true),
+        ("street".to_string(), false)
+      ])
+    );
+    assert_eq!(
+      elements(r#"<group ref="note"/>"#),
+      Ok(vec![("text".to_string(), false)])
+    );
+    assert_eq!(
+      elements(r#"<group ref="cycle"/>"#),
+      Err(r#"circular reference of group "cycle""#.to_string())
+    );
+    assert_eq!(
+      elements(r#"<group ref="unknown"/>"#),
+      Err(r#"group "unknown" not found"#.to_string())
+    );
+
+    let field = |xml: &str| {
+      let group: Group = from_str(xml).unwrap();
+      group
+        .get_field_implementation(&context, &None)
+        .unwrap()
+        .to_string()
+    };
+
+    assert_eq!(
+      field(r#"<group ref="address" minOccurs="0"/>"#),
+      "# [yaserde (flatten = true)] \
+# [xml_schema (elements = & [(\"street\" , false) , (\"town\" , false) , (\"zip\" , true)])] \
+pub address : Option < xml_schema_types :: Address > ,"
+    );
+    assert_eq!(
+      field(r#"<group ref="address" maxOccurs="3"/>"#),
+      "# [yaserde (flatten = true)] \
+# [xml_schema (elements = & [(\"street\" , true) , (\"town\" , true) , (\"zip\" , true)])] \
+pub address_list : Vec < xml_schema_types :: Address > ,"
+    );
+  }
 }
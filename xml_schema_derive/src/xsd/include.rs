@@ -0,0 +1,14 @@
+#[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
+#[yaserde(
+  rename = "include",
+  prefix = "xs",
+  namespaces = {
+    "xs" = "http://www.w3.org/2001/XMLSchema"
+  }
+)]
+pub struct Include {
+  #[yaserde(attribute = true)]
+  pub id: Option<String>,
+  #[yaserde(rename = "schemaLocation", attribute = true)]
+  pub schema_location: String,
+}
@@ -1,4 +1,8 @@
-use crate::xsd::{rust_types_mapping::RustTypesMapping, Implementation, XsdContext};
+use crate::xsd::{
+  rust_types_mapping::RustTypesMapping,
+  simple_type::{self, SimpleType},
+  Error, Implementation, Result, XsdContext,
+};
 use proc_macro2::{Ident, TokenStream};
 
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
@@ -8,63 +12,60 @@ use proc_macro2::{Ident, TokenStream};
 pub struct List {
   #[yaserde(rename = "itemType", attribute = true)]
   pub item_type: String,
+  #[yaserde(rename = "simpleType")]
+  pub simple_types: Vec<SimpleType>,
 }
 
 impl Implementation for List {
+  /// The list is generated as a structure with the `items`, separated by whitespaces in the
+  /// attribute value or the text content.
   fn implement_childs(
     &self,
-    _namespace_definition: &TokenStream,
-    _prefix: &Option<String>,
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
     context: &XsdContext,
     struct_name: &Ident,
-  ) -> TokenStream {
-    let list_type = RustTypesMapping::get(context, &self.item_type);
+  ) -> Result<TokenStream> {
+    let (list_type, item_implementation) =
+      self.get_item_type(namespace_definition, prefix, context, struct_name)?;
+    let xml_name = struct_name.to_string();
+    let text_content = simple_type::implement_text_content(struct_name);
 
-    quote!(
+    Ok(quote!(
       #[derive(Clone, Debug, Default, PartialEq)]
       pub struct #struct_name {
         pub items: Vec<#list_type>
       }
 
-      impl hifa_yaserde::YaDeserialize for #struct_name {
-        fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
-          loop {
-            match reader.next_event()? {
-              xml::reader::XmlEvent::StartElement{..} => {}
-              xml::reader::XmlEvent::Characters(ref text_content) => {
-                let items: Vec<#list_type> =
-                  text_content
-                    .split(' ')
-                    .map(|item| item.to_owned())
-                    .map(|item| item.parse().unwrap())
-                    .collect();
-
-                return Ok(#struct_name {items});
-              }
-              _ => {break;}
-            }
-          }
+      impl std::str::FromStr for #struct_name {
+        type Err = String;
 
-          Err("Unable to parse attribute".to_string())
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          let items = hifa_xml_schema::list::items(s)
+            .map(|item| {
+              item
+                .parse::<#list_type>()
+                .map_err(|e| format!("{}: invalid list item {:?}: {}", #xml_name, item, e))
+            })
+            .collect::<Result<Vec<#list_type>, String>>()?;
+
+          Ok(#struct_name {items})
         }
       }
 
-      impl hifa_yaserde::YaSerialize for #struct_name {
-        fn serialize<W: std::io::Write>(&self, writer: &mut hifa_yaserde::ser::Serializer<W>) -> Result<(), String> {
+      impl std::fmt::Display for #struct_name {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
           let content =
             self.items.iter().map(|item| item.to_string()).collect::<Vec<String>>().join(" ");
 
-          let data_event = xml::writer::XmlEvent::characters(&content);
-          writer.write(data_event).map_err(|e| e.to_string())?;
-
-          Ok(())
-        }
-
-        fn serialize_attributes(&self, mut source_attributes: Vec<xml::attribute::OwnedAttribute>, mut source_namespace: xml::namespace::Namespace) -> Result<(Vec<xml::attribute::OwnedAttribute>, xml::namespace::Namespace), String> {
-          Ok((source_attributes, source_namespace))
+          f.write_str(&content)
         }
       }
-    )
+
+      #text_content
+
+      #item_implementation
+    ))
   }
 }
 
@@ -79,11 +80,62 @@ impl List {
       #struct_name
     }
   }
+
+  /// The type of the items, from the `itemType` attribute or the inline simple type.
+  /// An inline simple type is generated as `{List}Item` when it is not just a base type.
+  fn get_item_type(
+    &self,
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
+    context: &XsdContext,
+    struct_name: &Ident,
+  ) -> Result<(TokenStream, TokenStream)> {
+    let simple_type = match (self.item_type.as_str(), self.simple_types.as_slice()) {
+      (item_type, []) if !item_type.is_empty() => {
+        return Ok((
+          RustTypesMapping::get(context, item_type)?,
+          TokenStream::new(),
+        ))
+      }
+      ("", [simple_type]) => simple_type,
+      _ => {
+        return Err(Error::new(
+          "list requires exactly one of itemType or simpleType",
+        ))
+      }
+    };
+
+    let is_base_type = simple_type.list.is_none()
+      && simple_type.union.is_none()
+      && simple_type
+        .restriction
+        .as_ref()
+        .map(|restriction| restriction.enumerations.is_empty() && !restriction.has_facets())
+        .unwrap_or_default();
+
+    if is_base_type {
+      let rust_type = simple_type
+        .get_type_implementation(context, prefix, None)
+        .map_err(|error| error.with_component("simpleType", None))?;
+      return Ok((rust_type, TokenStream::new()));
+    }
+
+    let type_name = format_ident!("{}Item", struct_name);
+    let implementation = SimpleType {
+      name: type_name.to_string(),
+      ..simple_type.clone()
+    }
+    .implement(namespace_definition, prefix, context)
+    .map_err(|error| error.with_component("simpleType", None))?;
+
+    Ok((quote!(#type_name), implementation))
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use hifa_yaserde::de::from_str;
   use proc_macro2::Span;
   use std::str::FromStr;
   use syn::Ident;
@@ -96,66 +148,89 @@ mod tests {
 
     let list_type = List {
       item_type: "xs:string".to_string(),
+      simple_types: vec![],
     };
 
     let struct_name = Ident::new("Parent", Span::call_site());
 
-    let implementation =
-      list_type.implement_childs(&TokenStream::new(), &None, &context, &struct_name);
+    let implementation = list_type
+      .implement_childs(&TokenStream::new(), &None, &context, &struct_name)
+      .unwrap();
 
-    let expected =
-      TokenStream::from_str(r#"
+    let mut expected = TokenStream::from_str(
+      r#"
         #[derive(Clone, Debug, Default, PartialEq)]
         pub struct Parent {
           pub items: Vec <String>
         }
 
-        impl hifa_yaserde::YaDeserialize for Parent {
-          fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
-            loop {
-              match reader.next_event()? {
-                xml::reader::XmlEvent::StartElement{..} => { }
-                xml::reader::XmlEvent::Characters(ref text_content) => {
-                  let items: Vec<String> = text_content.split(' ')
-                    .map(|item| item.to_owned())
-                    .map(|item| item.parse().unwrap())
-                    .collect();
-
-                  return Ok(Parent{items});
-                }
-                _ => { break; }
-              }
-            }
-
-            Err ("Unable to parse attribute" . to_string ())
+        impl std::str::FromStr for Parent {
+          type Err = String;
+
+          fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let items = hifa_xml_schema::list::items(s)
+              .map(|item| {
+                item
+                  .parse :: <String>()
+                  .map_err(|e| format!("{}: invalid list item {:?}: {}", "Parent", item, e))
+              })
+              .collect :: <Result<Vec<String> , String>>() ? ;
+
+            Ok(Parent{items})
           }
         }
 
-        impl hifa_yaserde::YaSerialize for Parent {
-          fn serialize<W: std::io::Write> (&self, writer: &mut hifa_yaserde::ser::Serializer<W>) -> Result<(), String> {
+        impl std::fmt::Display for Parent {
+          fn fmt(&self, f: &mut std::fmt::Formatter< '_>) -> std::fmt::Result {
             let content = self
               .items
               .iter()
               .map(|item| item.to_string())
-              .collect:: <Vec<String>>().join(" ");
-
-            let data_event = xml::writer::XmlEvent::characters(&content);
-
-            writer.write(data_event).map_err(|e| e.to_string())? ;
-
-            Ok (())
-          }
+              .collect :: <Vec<String>>().join(" ");
 
-          fn serialize_attributes(
-            &self,
-            mut source_attributes: Vec<xml::attribute::OwnedAttribute> ,
-            mut source_namespace: xml::namespace::Namespace
-            ) -> Result<(Vec<xml::attribute::OwnedAttribute> , xml::namespace::Namespace), String> {
-            Ok((source_attributes , source_namespace))
+            f.write_str(&content)
           }
         }
-      "#).unwrap();
+      "#,
+    )
+    .unwrap();
+    expected.extend(simple_type::implement_text_content(&struct_name));
 
     assert_eq!(implementation.to_string(), expected.to_string());
   }
+
+  #[test]
+  fn list_item_types() {
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+    let struct_name = Ident::new("Sizes", Span::call_site());
+
+    let item_type = |xml: &str| {
+      let list: List = from_str(xml).unwrap();
+      list
+        .get_item_type(&TokenStream::new(), &None, &context, &struct_name)
+        .map(|(rust_type, _)| rust_type.to_string())
+        .map_err(|error| error.to_string())
+    };
+
+    assert_eq!(
+      item_type(r#"<list itemType="Size"/>"#),
+      Ok("Size".to_string())
+    );
+    assert_eq!(
+      item_type(r#"<list><simpleType><restriction base="xs:int"/></simpleType></list>"#),
+      Ok("i32".to_string())
+    );
+    assert_eq!(
+      item_type(
+        r#"<list><simpleType><restriction base="xs:string"><enumeration value="small"/></restriction></simpleType></list>"#
+      ),
+      Ok("SizesItem".to_string())
+    );
+    assert_eq!(
+      item_type(r#"<list/>"#),
+      Err("list requires exactly one of itemType or simpleType".to_string())
+    );
+  }
 }
@@ -1,5 +1,6 @@
 use hifa_yaserde::YaDeserialize;
 use std::io::Read;
+use std::str::FromStr;
 use xml::reader::XmlEvent;
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -11,6 +12,19 @@ pub enum MaxOccurences {
   },
 }
 
+impl FromStr for MaxOccurences {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    if value == "unbounded" {
+      Ok(MaxOccurences::Unbounded)
+    } else {
+      let number = value.parse::<u32>().map_err(|e| e.to_string())?;
+      Ok(MaxOccurences::Number { value: number })
+    }
+  }
+}
+
 impl YaDeserialize for MaxOccurences {
   fn deserialize<R: Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
     if let XmlEvent::StartElement { name, .. } = reader.peek()? {
@@ -22,14 +36,7 @@ impl YaDeserialize for MaxOccurences {
       let content = reader.next_event()?;
 
       match content {
-        XmlEvent::Characters(value) => {
-          if value == "unbounded" {
-            Ok(MaxOccurences::Unbounded)
-          } else {
-            let number = value.parse::<u32>().map_err(|e| e.to_string())?;
-            Ok(MaxOccurences::Number { value: number })
-          }
-        }
+        XmlEvent::Characters(value) => value.parse(),
         _ => Err("bad content for Max Occurences field".to_string()),
       }
     } else {
@@ -1,17 +1,24 @@
+mod all;
 mod annotation;
 mod attribute;
 mod attribute_group;
 mod choice;
 mod complex_content;
 mod complex_type;
+mod default_value;
 mod element;
 mod enumeration;
+mod error;
 mod extension;
+mod facet;
 mod group;
 mod import;
+mod include;
 mod list;
 mod max_occurences;
+mod opaque;
 mod qualification;
+mod redefine;
 mod restriction;
 mod rust_types_mapping;
 mod schema;
@@ -24,19 +31,23 @@ mod xsd_context;
 use heck::ToSnakeCase;
 use hifa_yaserde::de::from_str;
 use proc_macro2::{Ident, TokenStream};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::path::Path;
 use syn::Visibility;
 use xsd_context::XsdContext;
 
+pub use error::{Error, Result};
+pub use xsd_context::{EnumDefault, Mode, Precision};
+
 trait Implementation {
   fn implement(
     &self,
     _namespace_definition: &TokenStream,
     _prefix: &Option<String>,
     _context: &XsdContext,
-  ) -> TokenStream {
-    unimplemented!()
+  ) -> Result<TokenStream> {
+    Err(Error::new("implementation is not supported"))
   }
 
   fn implement_childs(
@@ -45,8 +56,8 @@ trait Implementation {
     _prefix: &Option<String>,
     _context: &XsdContext,
     _struct_name: &Ident,
-  ) -> TokenStream {
-    unimplemented!()
+  ) -> Result<TokenStream> {
+    Err(Error::new("implementation of childs is not supported"))
   }
 }
 
@@ -56,6 +67,10 @@ pub struct Xsd {
   vis: Visibility,
   context: XsdContext,
   schema: schema::Schema,
+  target_prefix: Option<String>,
+  imported_modules: Vec<String>,
+  imports: Vec<Xsd>,
+  sources: Vec<(String, String)>,
 }
 
 impl Xsd {
@@ -64,7 +79,7 @@ impl Xsd {
     vis: Visibility,
     content: &str,
     module_namespace_mappings: &BTreeMap<String, String>,
-  ) -> Result<Self, String> {
+  ) -> Result<Self> {
     let context = XsdContext::new(content)?;
     let context = context.with_module_namespace_mappings(module_namespace_mappings);
     let schema: schema::Schema = from_str(content)?;
@@ -74,6 +89,10 @@ impl Xsd {
       vis,
       context,
       schema,
+      target_prefix: None,
+      imported_modules: vec![],
+      imports: vec![],
+      sources: vec![],
     })
   }
 
@@ -82,44 +101,582 @@ impl Xsd {
     vis: Visibility,
     source: &str,
     module_namespace_mappings: &BTreeMap<String, String>,
-  ) -> Result<Self, String> {
-    let content = if source.starts_with("http://") || source.starts_with("https://") {
-      log::info!("Load HTTP schema {}", source);
-      reqwest::blocking::get(source)
-        .map_err(|e| e.to_string())?
-        .text()
-        .map_err(|e| e.to_string())?
+  ) -> Result<Self> {
+    let location = if is_url(source) {
+      source.to_string()
     } else {
       let root: String = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
       log::info!("CARGO_MANIFEST_DIR is {}", &root);
-      let full_path = std::path::Path::new(&root).join(source);
-      fs::read_to_string(full_path).map_err(|e| e.to_string())?
+      Path::new(&root).join(source).to_string_lossy().to_string()
     };
 
-    // skip BOM header, can be present on some files
-    let content = if content.as_bytes()[0..3] == [0xef, 0xbb, 0xbf] {
-      content[3..].to_owned()
-    } else {
-      content
-    };
+    let mut included_locations = BTreeSet::new();
+    let mut xsd = Xsd::load(
+      name,
+      vis,
+      &location,
+      module_namespace_mappings,
+      &mut included_locations,
+    )?;
+
+    let root_module = xsd.name.to_snake_case();
+    let mut loaded = LoadedModules::default();
+    loaded
+      .locations
+      .insert(root_module.clone(), included_locations);
+    if let Some(target_namespace) = &xsd.schema.target_namespace {
+      loaded
+        .namespaces
+        .insert(target_namespace.clone(), root_module.clone());
+    }
+
+    let mut imports = vec![];
+    xsd.load_imports(&location, &mut loaded, &mut imports)?;
+
+    // the schemas imported from other locations of a namespace are merged in its module
+    for import in imports {
+      if import.name == root_module {
+        xsd.merge(import)?;
+      } else if let Some(module) = xsd.imports.iter_mut().find(|xsd| xsd.name == import.name) {
+        module.merge(import)?;
+      } else {
+        xsd.imports.push(import);
+      }
+    }
+
+    Ok(xsd)
+  }
+
+  /// Load a schema and merge the schemas it includes.
+  /// `included_locations` are the locations already loaded in the same module, they are skipped.
+  fn load(
+    name: String,
+    vis: Visibility,
+    location: &str,
+    module_namespace_mappings: &BTreeMap<String, String>,
+    included_locations: &mut BTreeSet<String>,
+  ) -> Result<Self> {
+    let content = load_content(location)?;
+    let mut xsd = Xsd::new(name, vis, &content, module_namespace_mappings)
+      .map_err(|e| e.with_file(location))?;
+    xsd.sources.push((location.to_string(), content));
+
+    included_locations.insert(canonical_location(location));
+    xsd.load_includes(location, included_locations)?;
+
+    Ok(xsd)
+  }
+
+  /// Load recursively the schemas referenced by `xs:include`, `xs:redefine` and `xs:override`,
+  /// and merge them into this schema.
+  /// Each location is included once, to break inclusion cycles.
+  fn load_includes(
+    &mut self,
+    location: &str,
+    included_locations: &mut BTreeSet<String>,
+  ) -> Result<()> {
+    for include in std::mem::take(&mut self.schema.includes) {
+      self.include(location, &include.schema_location, included_locations)?;
+    }
+
+    for redefine in std::mem::take(&mut self.schema.redefines) {
+      self.include(location, &redefine.schema_location, included_locations)?;
+      self.schema.redefine(&redefine, true);
+    }
+
+    for redefine in std::mem::take(&mut self.schema.overrides) {
+      self.include(location, &redefine.schema_location, included_locations)?;
+      self.schema.redefine(&redefine, false);
+    }
+
+    Ok(())
+  }
+
+  fn include(
+    &mut self,
+    location: &str,
+    schema_location: &str,
+    included_locations: &mut BTreeSet<String>,
+  ) -> Result<()> {
+    let include_location = resolve_location(location, schema_location);
+    if !included_locations.insert(canonical_location(&include_location)) {
+      log::info!("Schema {} is already included", include_location);
+      return Ok(());
+    }
+
+    log::info!("Include schema {}", include_location);
+    let content = load_content(&include_location)?;
+    let mut included = Xsd::new(
+      self.name.clone(),
+      self.vis.clone(),
+      &content,
+      &BTreeMap::default(),
+    )
+    .map_err(|e| e.with_file(&include_location))?;
+    included
+      .sources
+      .push((include_location.to_string(), content));
+
+    // chameleon include: the included schema adopts the namespace of the including schema
+    if included.schema.target_namespace.is_none() {
+      included.schema.target_namespace = self.schema.target_namespace.clone();
+    }
+
+    // locations in the included schema are relative to its own location
+    for import in included.schema.imports.iter_mut() {
+      if let Some(schema_location) = &import.schema_location {
+        import.schema_location = Some(resolve_location(&include_location, schema_location));
+      }
+    }
+    included.load_includes(&include_location, included_locations)?;
+
+    self.context.merge_namespaces(&included.context);
+    self.sources.extend(included.sources);
+    self
+      .schema
+      .include(included.schema)
+      .map_err(|e| e.with_file(&include_location))
+  }
+
+  /// Load recursively the schemas referenced by `xs:import`.
+  /// Each namespace is loaded in one module, all imported schemas are stored flat in `imports`.
+  /// The other locations of an imported namespace are loaded once, to be merged in its module.
+  fn load_imports(
+    &mut self,
+    location: &str,
+    loaded: &mut LoadedModules,
+    imports: &mut Vec<Xsd>,
+  ) -> Result<()> {
+    for import in self.schema.imports.clone() {
+      let namespace = import.namespace.clone().unwrap_or_default();
+      let import_location = import
+        .schema_location
+        .as_ref()
+        .map(|schema_location| resolve_location(location, schema_location));
+
+      let module = if let Some(module) = loaded.namespaces.get(&namespace).cloned() {
+        let included_locations = loaded.locations.entry(module.clone()).or_default();
+        match import_location {
+          Some(import_location)
+            if !included_locations.contains(&canonical_location(&import_location)) =>
+          {
+            log::info!(
+              "Import namespace {:?} from {} in the module {}",
+              namespace,
+              import_location,
+              module
+            );
+            let xsd =
+              self.load_import(&module, &namespace, &import_location, included_locations)?;
+            self.load_import_imports(xsd, &import_location, loaded, imports)?;
+          }
+          _ => {}
+        }
+        module
+      } else if self.context.has_module_namespace_mapping(&namespace) {
+        log::info!("Namespace {:?} is already mapped to a module", namespace);
+        continue;
+      } else {
+        let Some(import_location) = import_location else {
+          log::warn!(
+            "Missing schemaLocation to import namespace {:?}, skip it",
+            namespace
+          );
+          continue;
+        };
+
+        let used_names: BTreeSet<String> = loaded
+          .locations
+          .keys()
+          .cloned()
+          .chain(["xml_schema_types".to_string()])
+          .collect();
+        let module = module_name(&namespace, &import_location, &used_names);
+        loaded.namespaces.insert(namespace.clone(), module.clone());
+
+        log::info!("Import namespace {:?} from {}", namespace, import_location);
+        let included_locations = loaded.locations.entry(module.clone()).or_default();
+        let xsd = self.load_import(&module, &namespace, &import_location, included_locations)?;
+        self.load_import_imports(xsd, &import_location, loaded, imports)?;
+        module
+      };
+
+      self
+        .context
+        .add_module_namespace_mapping(&namespace, &format!("{module}::xml_schema_types"));
+      if !self.imported_modules.contains(&module) {
+        self.imported_modules.push(module);
+      }
+    }
+
+    Ok(())
+  }
+
+  fn load_import(
+    &self,
+    module: &str,
+    namespace: &str,
+    import_location: &str,
+    included_locations: &mut BTreeSet<String>,
+  ) -> Result<Xsd> {
+    let mut xsd = Xsd::load(
+      module.to_string(),
+      self.vis.clone(),
+      import_location,
+      &BTreeMap::default(),
+      included_locations,
+    )?;
+    xsd.target_prefix = self
+      .context
+      .get_prefix(namespace)
+      .or_else(|| xsd.context.get_prefix(namespace))
+      .or_else(|| Some(module.to_string()))
+      .filter(|_| xsd.schema.target_namespace.is_some());
+
+    Ok(xsd)
+  }
+
+  fn load_import_imports(
+    &self,
+    mut xsd: Xsd,
+    import_location: &str,
+    loaded: &mut LoadedModules,
+    imports: &mut Vec<Xsd>,
+  ) -> Result<()> {
+    xsd.load_imports(import_location, loaded, imports)?;
+    imports.push(xsd);
+    Ok(())
+  }
+
+  /// Merge a schema of the same namespace, imported from another location, into this one.
+  fn merge(&mut self, other: Xsd) -> Result<()> {
+    let location = other
+      .sources
+      .first()
+      .map(|(location, _content)| location.clone())
+      .unwrap_or_default();
+
+    self.context.merge_namespaces(&other.context);
+    self.context.merge_module_namespace_mappings(&other.context);
+    for module in other.imported_modules {
+      if !self.imported_modules.contains(&module) {
+        self.imported_modules.push(module);
+      }
+    }
+    self.sources.extend(other.sources);
+    self
+      .schema
+      .include(other.schema)
+      .map_err(|e| e.with_file(&location))
+  }
+
+  pub fn set_mode(&mut self, mode: Mode) {
+    self.context.set_mode(mode);
+    for xsd in self.imports.iter_mut() {
+      xsd.set_mode(mode);
+    }
+  }
+
+  pub fn set_enum_default(&mut self, enum_default: EnumDefault) {
+    self.context.set_enum_default(enum_default);
+    for xsd in self.imports.iter_mut() {
+      xsd.set_enum_default(enum_default);
+    }
+  }
+
+  pub fn set_open_enums(&mut self, open_enums: bool) {
+    self.context.set_open_enums(open_enums);
+    for xsd in self.imports.iter_mut() {
+      xsd.set_open_enums(open_enums);
+    }
+  }
 
-    Xsd::new(name, vis, &content, module_namespace_mappings)
+  pub fn set_precision(&mut self, precision: Precision) {
+    self.context.set_precision(precision);
+    for xsd in self.imports.iter_mut() {
+      xsd.set_precision(precision);
+    }
   }
 
-  pub fn implement(&self, target_prefix: &Option<String>) -> TokenStream {
+  pub fn implement(&self, target_prefix: &Option<String>) -> Result<TokenStream> {
+    let imports: TokenStream = self
+      .imports
+      .iter()
+      .map(|xsd| xsd.implement_imported_module(&self.name.to_snake_case()))
+      .collect::<Result<_>>()?;
+
     let schema = self
       .schema
-      .implement(&TokenStream::new(), target_prefix, &self.context);
+      .implement_with_modules(target_prefix, &self.context, &imports)
+      .map_err(|e| self.locate(e))?;
+
+    let skipped = self.report_skipped();
 
     let mod_name = format_ident!("{}", self.name.to_snake_case());
     let vis = &self.vis;
 
-    quote! {
+    Ok(quote! {
         mod #mod_name {
             #schema
+            #skipped
         }
 
         #vis use #mod_name::*;
+    })
+  }
+
+  /// Imported schemas are generated as sub-modules of the root `xml_schema_types` module.
+  /// They re-export the modules of their own imports, to share the same paths.
+  fn implement_imported_module(&self, root_module: &str) -> Result<TokenStream> {
+    let reexports: TokenStream = self
+      .imported_modules
+      .iter()
+      .map(|module| {
+        let module_ident = format_ident!("{}", module);
+        if module == root_module {
+          quote!(pub(super) use super::super::super as #module_ident;)
+        } else {
+          quote!(pub(super) use super::super::#module_ident;)
+        }
+      })
+      .collect();
+
+    let schema = self
+      .schema
+      .implement_with_modules(&self.target_prefix, &self.context, &reexports)
+      .map_err(|e| self.locate(e))?;
+
+    let mod_name = format_ident!("{}", self.name);
+
+    Ok(quote! {
+      pub mod #mod_name {
+        #schema
+      }
+    })
+  }
+
+  /// Warn about the components replaced by a fallback in lenient mode,
+  /// and list them in the `SKIPPED_COMPONENTS` constant.
+  fn report_skipped(&self) -> TokenStream {
+    if !self.context.is_lenient() {
+      return quote!();
     }
+
+    let skipped: Vec<String> = std::iter::once(self)
+      .chain(self.imports.iter())
+      .flat_map(|xsd| {
+        xsd
+          .context
+          .skipped()
+          .into_iter()
+          .map(move |error| xsd.locate(error).to_string())
+      })
+      .collect();
+
+    for component in &skipped {
+      log::warn!("Skip unsupported component: {}", component);
+    }
+
+    quote!(
+      /// Unsupported components of the schema, replaced by a fallback.
+      pub const SKIPPED_COMPONENTS: &[&str] = &[#(#skipped),*];
+    )
+  }
+
+  /// Locate the error in the schema, or in the schemas it includes.
+  fn locate(&self, error: Error) -> Error {
+    let error = self
+      .sources
+      .iter()
+      .fold(error, |error, (location, content)| {
+        error.locate(location, content)
+      });
+
+    match self.sources.first() {
+      Some((location, _content)) => error.with_file(location),
+      None => error,
+    }
+  }
+}
+
+/// Modules of the loaded namespaces, and the locations loaded in each module.
+#[derive(Default)]
+struct LoadedModules {
+  namespaces: BTreeMap<String, String>,
+  locations: BTreeMap<String, BTreeSet<String>>,
+}
+
+fn is_url(location: &str) -> bool {
+  location.starts_with("http://") || location.starts_with("https://")
+}
+
+fn load_content(location: &str) -> Result<String> {
+  let content = if is_url(location) {
+    log::info!("Load HTTP schema {}", location);
+    reqwest::blocking::get(location)
+      .map_err(|e| Error::new(e.to_string()).with_file(location))?
+      .text()
+      .map_err(|e| Error::new(e.to_string()).with_file(location))?
+  } else {
+    log::info!("Load schema {}", location);
+    fs::read_to_string(location).map_err(|e| Error::new(e.to_string()).with_file(location))?
+  };
+
+  // skip BOM header, can be present on some files
+  let content = if content.as_bytes().starts_with(&[0xef, 0xbb, 0xbf]) {
+    content[3..].to_owned()
+  } else {
+    content
+  };
+
+  Ok(content)
+}
+
+/// Normalized location, used to detect a schema loaded many times.
+fn canonical_location(location: &str) -> String {
+  if is_url(location) {
+    return location.to_string();
+  }
+
+  fs::canonicalize(location)
+    .map(|path| path.to_string_lossy().to_string())
+    .unwrap_or_else(|_| location.to_string())
+}
+
+/// Resolve a `schemaLocation` relatively to the location of the schema which references it.
+fn resolve_location(base: &str, location: &str) -> String {
+  if is_url(location) || Path::new(location).is_absolute() {
+    return location.to_string();
+  }
+
+  if is_url(base) {
+    return reqwest::Url::parse(base)
+      .and_then(|url| url.join(location))
+      .map(|url| url.to_string())
+      .unwrap_or_else(|_| location.to_string());
+  }
+
+  Path::new(base)
+    .parent()
+    .unwrap_or_else(|| Path::new(""))
+    .join(location)
+    .to_string_lossy()
+    .to_string()
+}
+
+/// Rust module name used to generate the schema of a namespace.
+/// A keyword is suffixed with `_`, and a name already in `used_names` is numbered (`example_com_2`).
+fn module_name(namespace: &str, location: &str, used_names: &BTreeSet<String>) -> String {
+  let source = if namespace.is_empty() {
+    location
+      .rsplit(['/', '\\'])
+      .next()
+      .unwrap_or_default()
+      .trim_end_matches(".xsd")
+  } else {
+    namespace
+      .trim_start_matches("http://")
+      .trim_start_matches("https://")
+      .trim_start_matches("urn:")
+  };
+
+  let name = source
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+    .collect::<String>()
+    .to_snake_case();
+
+  let mut name = if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+    format!("ns_{name}")
+  } else {
+    name
+  };
+
+  if syn::parse_str::<Ident>(&name).is_err() {
+    name.push('_');
+  }
+
+  if !used_names.contains(&name) {
+    return name;
+  }
+
+  (2..)
+    .map(|index| format!("{name}_{index}"))
+    .find(|numbered| !used_names.contains(numbered))
+    .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn resolve_local_location() {
+    assert_eq!(
+      resolve_location("/schemas/main.xsd", "common/types.xsd"),
+      "/schemas/common/types.xsd"
+    );
+    assert_eq!(
+      resolve_location("/schemas/main.xsd", "http://example.com/types.xsd"),
+      "http://example.com/types.xsd"
+    );
+  }
+
+  #[test]
+  fn resolve_url_location() {
+    assert_eq!(
+      resolve_location("http://example.com/schemas/main.xsd", "../types.xsd"),
+      "http://example.com/types.xsd"
+    );
+  }
+
+  #[test]
+  fn namespace_module_name() {
+    let used_names = BTreeSet::new();
+    assert_eq!(
+      module_name("http://example.com/book", "book.xsd", &used_names),
+      "example_com_book"
+    );
+    assert_eq!(
+      module_name("urn:oasis:names:tc:ubl", "ubl.xsd", &used_names),
+      "oasis_names_tc_ubl"
+    );
+    assert_eq!(
+      module_name("", "/schemas/common-types.xsd", &used_names),
+      "common_types"
+    );
+    assert_eq!(module_name("urn:type", "type.xsd", &used_names), "type_");
+    assert_eq!(module_name("", "/schemas/self.xsd", &used_names), "self_");
+  }
+
+  #[test]
+  fn colliding_module_names() {
+    let mut used_names = BTreeSet::from(["xml_schema_types".to_string(), "my_schema".to_string()]);
+
+    let mut names = vec![];
+    for (namespace, location) in [
+      ("http://a/b", "b.xsd"),
+      ("https://a/b", "b.xsd"),
+      ("urn:a-b", "ab.xsd"),
+      ("urn:a_b", "ab.xsd"),
+      ("urn:xml:schema:types", "types.xsd"),
+      ("urn:my-schema", "my.xsd"),
+    ] {
+      let name = module_name(namespace, location, &used_names);
+      used_names.insert(name.clone());
+      names.push(name);
+    }
+
+    assert_eq!(
+      names,
+      vec![
+        "a_b",
+        "a_b_2",
+        "a_b_3",
+        "a_b_4",
+        "xml_schema_types_2",
+        "my_schema_2"
+      ]
+    );
   }
 }
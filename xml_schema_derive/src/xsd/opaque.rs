@@ -0,0 +1,91 @@
+use proc_macro2::{Ident, TokenStream};
+
+/// Fallback of an unsupported component in lenient mode.
+/// The structure captures the raw XML content, and writes it back as it is.
+pub fn implement_opaque(struct_name: &Ident, element_name: &str) -> TokenStream {
+  quote!(
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct #struct_name {
+      pub content: String,
+    }
+
+    impl hifa_yaserde::YaDeserialize for #struct_name {
+      fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        let start_depth = reader.depth();
+        if matches!(reader.peek()?, xml::reader::XmlEvent::StartElement{..}) {
+          reader.next_event()?;
+        }
+
+        let config = xml::writer::EmitterConfig::new().write_document_declaration(false);
+        let mut writer = xml::writer::EventWriter::new_with_config(Vec::new(), config);
+
+        loop {
+          let is_end = match reader.peek()? {
+            xml::reader::XmlEvent::EndElement{..} => reader.depth() == start_depth + 1,
+            xml::reader::XmlEvent::EndDocument => true,
+            _ => false,
+          };
+          if is_end {
+            break;
+          }
+
+          let event = reader.next_event()?;
+          if let Some(event) = event.as_writer_event() {
+            writer.write(event).map_err(|e| e.to_string())?;
+          }
+        }
+
+        let content = String::from_utf8(writer.into_inner()).map_err(|e| e.to_string())?;
+        Ok(#struct_name {content})
+      }
+    }
+
+    impl hifa_yaserde::YaSerialize for #struct_name {
+      fn serialize<W: std::io::Write>(&self, writer: &mut hifa_yaserde::ser::Serializer<W>) -> Result<(), String> {
+        let skip = writer.skip_start_end();
+
+        if !skip {
+          let name = writer.get_start_event_name().unwrap_or_else(|| #element_name.to_string());
+          writer.write(xml::writer::XmlEvent::start_element(name.as_str())).map_err(|e| e.to_string())?;
+        }
+
+        let content = format!("<content>{}</content>", self.content);
+        let mut reader = xml::reader::EventReader::new(content.as_bytes());
+        let mut depth = 0;
+
+        loop {
+          let event = reader.next().map_err(|e| e.to_string())?;
+          match event {
+            xml::reader::XmlEvent::StartElement{..} => depth += 1,
+            xml::reader::XmlEvent::EndElement{..} => depth -= 1,
+            xml::reader::XmlEvent::EndDocument => break,
+            _ => {}
+          }
+
+          let is_content = match event {
+            xml::reader::XmlEvent::StartElement{..} => depth > 1,
+            xml::reader::XmlEvent::EndElement{..} => depth > 0,
+            xml::reader::XmlEvent::StartDocument{..} => false,
+            _ => true,
+          };
+
+          if is_content {
+            if let Some(event) = event.as_writer_event() {
+              writer.write(event).map_err(|e| e.to_string())?;
+            }
+          }
+        }
+
+        if !skip {
+          writer.write(xml::writer::XmlEvent::end_element()).map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+      }
+
+      fn serialize_attributes(&self, source_attributes: Vec<xml::attribute::OwnedAttribute>, source_namespace: xml::namespace::Namespace) -> Result<(Vec<xml::attribute::OwnedAttribute>, xml::namespace::Namespace), String> {
+        Ok((source_attributes, source_namespace))
+      }
+    }
+  )
+}
@@ -0,0 +1,34 @@
+use crate::xsd::{
+  annotation::Annotation, attribute::Attribute, attribute_group::AttributeGroup,
+  complex_type::ComplexType, element::Element, group::Group, simple_type::SimpleType,
+};
+
+/// Components of a `xs:redefine`, or of a XSD 1.1 `xs:override` which can also contain elements and attributes.
+#[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
+#[yaserde(
+  rename = "redefine",
+  prefix = "xs",
+  namespaces = {
+    "xs" = "http://www.w3.org/2001/XMLSchema"
+  }
+)]
+pub struct Redefine {
+  #[yaserde(attribute = true)]
+  pub id: Option<String>,
+  #[yaserde(rename = "schemaLocation", attribute = true)]
+  pub schema_location: String,
+  #[yaserde(rename = "annotation")]
+  pub annotation: Option<Annotation>,
+  #[yaserde(rename = "simpleType")]
+  pub simple_types: Vec<SimpleType>,
+  #[yaserde(rename = "complexType")]
+  pub complex_types: Vec<ComplexType>,
+  #[yaserde(rename = "group")]
+  pub groups: Vec<Group>,
+  #[yaserde(rename = "attributeGroup")]
+  pub attribute_groups: Vec<AttributeGroup>,
+  #[yaserde(rename = "element")]
+  pub elements: Vec<Element>,
+  #[yaserde(rename = "attribute")]
+  pub attributes: Vec<Attribute>,
+}
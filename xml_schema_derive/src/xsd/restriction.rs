@@ -1,7 +1,14 @@
-use crate::xsd::{enumeration::Enumeration, rust_types_mapping::RustTypesMapping, XsdContext};
+use crate::xsd::{
+  default_value,
+  enumeration::{self, Enumeration},
+  facet::Facet,
+  rust_types_mapping::RustTypesMapping,
+  simple_type, Error, Result, XsdContext,
+};
 use proc_macro2::Ident;
-use proc_macro2::Span;
+use proc_macro2::Literal;
 use proc_macro2::TokenStream;
+use std::str::FromStr;
 
 use super::Implementation;
 
@@ -14,6 +21,28 @@ pub struct Restriction {
   pub base: Option<String>,
   #[yaserde(rename = "enumeration")]
   pub enumerations: Vec<Enumeration>,
+  #[yaserde(rename = "pattern")]
+  pub patterns: Vec<Facet>,
+  #[yaserde(rename = "minInclusive")]
+  pub min_inclusive: Option<Facet>,
+  #[yaserde(rename = "maxInclusive")]
+  pub max_inclusive: Option<Facet>,
+  #[yaserde(rename = "minExclusive")]
+  pub min_exclusive: Option<Facet>,
+  #[yaserde(rename = "maxExclusive")]
+  pub max_exclusive: Option<Facet>,
+  #[yaserde(rename = "length")]
+  pub length: Option<Facet>,
+  #[yaserde(rename = "minLength")]
+  pub min_length: Option<Facet>,
+  #[yaserde(rename = "maxLength")]
+  pub max_length: Option<Facet>,
+  #[yaserde(rename = "totalDigits")]
+  pub total_digits: Option<Facet>,
+  #[yaserde(rename = "fractionDigits")]
+  pub fraction_digits: Option<Facet>,
+  #[yaserde(rename = "whiteSpace")]
+  pub white_space: Option<Facet>,
 }
 
 impl Implementation for Restriction {
@@ -23,63 +52,42 @@ impl Implementation for Restriction {
     _prefix: &Option<String>,
     context: &XsdContext,
     struct_name: &syn::Ident,
-  ) -> TokenStream {
-    if let Some(base) = self.base.as_ref() {
-      if RustTypesMapping::is_xs_string(context, base) {
-        if self.enumerations.len() > 0 {
-          let enum_values_string: Vec<String> = self
-            .enumerations
-            .iter()
-            .map(|enumeration| enumeration.value.clone())
-            .collect::<Vec<String>>();
-          let enum_values: Vec<Ident> = enum_values_string
-            .iter()
-            .map(|enumeration| Ident::new(enumeration, Span::call_site()))
-            .collect::<Vec<Ident>>();
-
-          return quote!(
-            #[derive(Clone, Debug, Default, PartialEq, hifa_yaserde_derive::YaDeserialize, hifa_yaserde_derive::YaSerialize)]
-            pub enum #struct_name {
-              #[default]
-              _DEFAULT,
-              #(#enum_values),*
+  ) -> Result<TokenStream> {
+    let base = self
+      .base
+      .as_ref()
+      .ok_or_else(|| Error::new("missing base for restriction"))?;
 
-            }
+    if !self.enumerations.is_empty() {
+      return self.implement_enum(context, struct_name, base);
+    }
 
-            impl std::str::FromStr for #struct_name {
-              type Err = String;
+    if RustTypesMapping::is_xs_string(context, base) && !self.has_facets() {
+      return Ok(quote!(
+        #[derive(Clone, Debug, Default, PartialEq, hifa_yaserde_derive::YaDeserialize, hifa_yaserde_derive::YaSerialize)]
+        #namespace_definition
+        pub struct #struct_name {
+         #[yaserde(text= true)]
+          pub content: std::string::String,
+        }
 
-              fn from_str(s: &str) -> Result<Self, Self::Err> {
-                match s {
-                  #(#enum_values_string => Ok(#struct_name::#enum_values),)*
-                  _ => Err(format!("Invalid value: {}", s)),
-                }
-              }
-            }
+        impl std::str::FromStr for #struct_name {
+          type Err = String;
 
-            impl std::string::ToString for #struct_name {
-              fn to_string(&self) -> String {
-                match self {
-                  #struct_name::_DEFAULT => panic!("Cannot convert _DEFAULT to string"),
-                  #(#struct_name::#enum_values => #enum_values_string.to_string(),)*
-                }
-              }
-            }
-          );
-        } else {
-          return quote!(
-            #[derive(Clone, Debug, Default, PartialEq, hifa_yaserde_derive::YaDeserialize, hifa_yaserde_derive::YaSerialize)]
-            #namespace_definition
-            pub struct #struct_name {
-             #[yaserde(text= true)]
-              pub content: std::string::String,
-            }
-          );
+          fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(#struct_name { content: s.to_string() })
+          }
         }
-      }
+
+        impl std::fmt::Display for #struct_name {
+          fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.content)
+          }
+        }
+      ));
     }
 
-    unimplemented!("unimplemented Restriction: {:?}", self);
+    self.implement_validated_type(context, struct_name, base)
   }
 }
 
@@ -88,14 +96,654 @@ impl Restriction {
     &self,
     context: &XsdContext,
     _prefix: &Option<String>,
-  ) -> TokenStream {
-    if let Some(base) = &self.base {
-      RustTypesMapping::get(context, base)
+  ) -> Result<TokenStream> {
+    match &self.base {
+      Some(base) => RustTypesMapping::get(context, base),
+      None => Err(Error::new("missing base for restriction")),
+    }
+  }
+
+  pub fn has_facets(&self) -> bool {
+    !self.patterns.is_empty()
+      || self.min_inclusive.is_some()
+      || self.max_inclusive.is_some()
+      || self.min_exclusive.is_some()
+      || self.max_exclusive.is_some()
+      || self.length.is_some()
+      || self.min_length.is_some()
+      || self.max_length.is_some()
+      || self.total_digits.is_some()
+      || self.fraction_digits.is_some()
+      || self.white_space.is_some()
+  }
+
+  /// Expression of a value of the restriction in `rust_type`: the variant of one of the
+  /// enumeration values, or a value of a built-in base type without other facets than
+  /// `whiteSpace`. `None` for the other values, checked against the facets at runtime.
+  pub fn typed_value(
+    &self,
+    context: &XsdContext,
+    rust_type: &TokenStream,
+    constraint: &str,
+    value: &str,
+  ) -> Result<Option<TokenStream>> {
+    let base = self
+      .base
+      .as_ref()
+      .ok_or_else(|| Error::new("missing base for restriction"))?;
+    let base_type = RustTypesMapping::get(context, base)?;
+
+    if !self.enumerations.is_empty() {
+      let is_string = RustTypesMapping::is_xs_string(context, base);
+      let found = primitive_literal(constraint, value, &base_type)
+        .ok()
+        .flatten()
+        .map(|literal| literal.to_string());
+      let reserved: &[&str] = if context.has_open_enums() {
+        &["Other"]
+      } else {
+        &[]
+      };
+      let variants = enumeration::variant_names(&self.enumerations, reserved);
+
+      for (enumeration, variant) in self.enumerations.iter().zip(variants) {
+        let matches = if is_string {
+          enumeration.value == value
+        } else {
+          let literal = primitive_literal("enumeration", &enumeration.value, &base_type)?
+            .map(|literal| literal.to_string());
+          match (&found, literal) {
+            (Some(found), Some(literal)) => *found == literal,
+            _ => enumeration.value.trim() == value.trim(),
+          }
+        };
+        if matches {
+          return Ok(Some(quote!(#rust_type::#variant)));
+        }
+      }
+
+      return Err(Error::new(format!(
+        "the {constraint} value {value:?} is not one of the enumeration values"
+      )));
+    }
+
+    let has_facets = (Restriction {
+      white_space: None,
+      ..self.clone()
+    })
+    .has_facets();
+    if has_facets || !RustTypesMapping::is_xs_type(context, base) {
+      return Ok(None);
+    }
+
+    let value = match self.white_space.as_ref().map(|facet| facet.value.as_str()) {
+      Some("replace") => value.replace(['\t', '\n', '\r'], " "),
+      Some("collapse") => value
+        .split([' ', '\t', '\n', '\r'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" "),
+      _ => value.to_string(),
+    };
+    let Some(content) = simple_type::typed_value(context, base, &base_type, constraint, &value)?
+    else {
+      return Ok(None);
+    };
+
+    if RustTypesMapping::is_xs_string(context, base) && !self.has_facets() {
+      Ok(Some(quote!(#rust_type { content: #content })))
     } else {
-      panic!("Missing base for restriction");
+      Ok(Some(quote!(<#rust_type>::from(#content))))
     }
   }
 
+  /// Enum with a variant per enumeration value, the typed value is returned by `value()`.
+  fn implement_enum(
+    &self,
+    context: &XsdContext,
+    struct_name: &Ident,
+    base: &str,
+  ) -> Result<TokenStream> {
+    let rust_type = RustTypesMapping::get(context, base)?;
+    let compared_type = compared_type(context, base, &rust_type);
+    let xml_name = struct_name.to_string();
+    let is_string = RustTypesMapping::is_xs_string(context, base);
+    let is_text = is_string || compared_type.to_string() != rust_type.to_string();
+
+    let literals: Vec<&String> = self
+      .enumerations
+      .iter()
+      .map(|enumeration| &enumeration.value)
+      .collect();
+    let open = context.has_open_enums();
+    let reserved: &[&str] = if open { &["Other"] } else { &[] };
+    let variants = enumeration::variant_names(&self.enumerations, reserved);
+    let first_variant = &variants[0];
+    let values = literals
+      .iter()
+      .map(|literal| {
+        if is_string {
+          Ok(quote!(#literal.to_string()))
+        } else {
+          facet_value("enumeration", literal, &compared_type)
+        }
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    // an unknown value of a text base type is kept as text, the others as their value and text
+    let is_text_other = is_text || RustTypesMapping::is_xs_text(context, base);
+    let unknown_value = if !open {
+      quote!(Err(format!("{}: invalid value {:?}", #xml_name, s)))
+    } else if is_text_other {
+      quote!(Ok(#struct_name::Other(s.to_string())))
+    } else {
+      quote!(Ok(#struct_name::Other {
+        value,
+        lexical: s.to_string(),
+      }))
+    };
+
+    let parse = if is_string {
+      quote!(
+        match s {
+          #(#literals => Ok(#struct_name::#variants),)*
+          _ => #unknown_value,
+        }
+      )
+    } else {
+      let white_space = RustTypesMapping::white_space(context, base);
+      quote!(
+        let s = hifa_xml_schema::facets::WhiteSpace::#white_space.normalize(s);
+        let value = s
+          .parse::<#compared_type>()
+          .map_err(|e| format!("{}: invalid value {:?}: {}", #xml_name, s, e))?;
+
+        #(
+          if value == #values {
+            return Ok(#struct_name::#variants);
+          }
+        )*
+
+        #unknown_value
+      )
+    };
+
+    let typed_values = literals
+      .iter()
+      .map(|literal| {
+        if is_text {
+          return Ok(quote!(#literal.to_string()));
+        }
+
+        Ok(
+          primitive_literal("enumeration", literal, &rust_type)?.unwrap_or_else(
+            || quote!(#literal.parse::<#rust_type>().expect("valid enumeration value")),
+          ),
+        )
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    let text_content = simple_type::implement_text_content(struct_name);
+
+    let (other_definition, other_value, other_display) = if !open {
+      (quote!(), quote!(), quote!())
+    } else if is_text_other {
+      (
+        quote!(
+          /// A value of the base type which is not one of the enumeration values.
+          Other(String),
+        ),
+        quote!(#struct_name::Other(value) => value.as_str().into(),),
+        quote!(#struct_name::Other(value) => f.write_str(value),),
+      )
+    } else {
+      (
+        quote!(
+          /// A value of the base type which is not one of the enumeration values.
+          Other {
+            value: #rust_type,
+            /// The value as read, written back as it is.
+            lexical: String,
+          },
+        ),
+        quote!(#struct_name::Other { value, .. } => value.clone(),),
+        quote!(#struct_name::Other { lexical, .. } => f.write_str(lexical),),
+      )
+    };
+
+    Ok(quote!(
+      #[derive(Clone, Debug, PartialEq)]
+      pub enum #struct_name {
+        #(#variants,)*
+        #other_definition
+      }
+
+      impl Default for #struct_name {
+        fn default() -> Self {
+          #struct_name::#first_variant
+        }
+      }
+
+      impl #struct_name {
+        /// Value of the enumeration, in the base type.
+        pub fn value(&self) -> #rust_type {
+          match self {
+            #(#struct_name::#variants => #typed_values,)*
+            #other_value
+          }
+        }
+      }
+
+      impl std::str::FromStr for #struct_name {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          #parse
+        }
+      }
+
+      impl std::fmt::Display for #struct_name {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+          match self {
+            #(#struct_name::#variants => f.write_str(#literals),)*
+            #other_display
+          }
+        }
+      }
+
+      #text_content
+    ))
+  }
+
+  /// Newtype over the base type, the constructor and the parsing reject the values out of the facets.
+  /// The `pattern` and length facets constrain the lexical form, they are checked on the text
+  /// before parsing it, and by the constructor only when the value is its own lexical form.
+  fn implement_validated_type(
+    &self,
+    context: &XsdContext,
+    struct_name: &Ident,
+    base: &str,
+  ) -> Result<TokenStream> {
+    let rust_type = RustTypesMapping::get(context, base)?;
+    let compared_type = compared_type(context, base, &rust_type);
+    let xml_name = struct_name.to_string();
+    let is_binary = RustTypesMapping::is_xs_binary(context, base);
+    let checks = self.implement_checks(&rust_type, &compared_type, &xml_name, is_binary)?;
+    let lexical_checks = self.implement_lexical_checks(&xml_name, is_binary)?;
+
+    let white_space = match self.white_space.as_ref().map(|facet| facet.value.as_str()) {
+      Some("preserve") => quote!(Preserve),
+      Some("replace") => quote!(Replace),
+      Some("collapse") => quote!(Collapse),
+      Some(value) => return Err(Error::new(format!("invalid whiteSpace value {value:?}"))),
+      None => RustTypesMapping::white_space(context, base),
+    };
+
+    let is_text = RustTypesMapping::is_xs_text(context, base);
+    let (validate_lexical, parse_lexical, new_lexical) = if lexical_checks.is_empty() {
+      (quote!(), quote!(), quote!())
+    } else {
+      // the text written back is the one of the value when it is not read
+      let new_lexical = if is_text {
+        quote!(#struct_name::validate_lexical(&content)?;)
+      } else {
+        quote!(#struct_name::validate_lexical(&content.to_string())?;)
+      };
+
+      (
+        quote!(
+          fn validate_lexical(lexical: &str) -> Result<(), String> {
+            #lexical_checks
+            Ok(())
+          }
+        ),
+        quote!(#struct_name::validate_lexical(&s)?;),
+        new_lexical,
+      )
+    };
+
+    let (validate, check_value) = if checks.is_empty() {
+      (quote!(), quote!())
+    } else {
+      (
+        quote!(
+          fn validate(content: &#rust_type) -> Result<(), String> {
+            #checks
+            Ok(())
+          }
+        ),
+        quote!(#struct_name::validate(&content)?;),
+      )
+    };
+
+    // without facets constraining the values, any value of the base type is converted
+    let from_content = if checks.is_empty() && lexical_checks.is_empty() {
+      quote!(
+        impl From<#rust_type> for #struct_name {
+          fn from(content: #rust_type) -> Self {
+            #struct_name { content }
+          }
+        }
+      )
+    } else {
+      quote!()
+    };
+
+    let default_value = default_value::implement(self, context, base, &rust_type)?;
+
+    // the text read is kept to be written back, the lexical facets constraining it rather than
+    // the canonical form of the value
+    let keeps_lexical = !lexical_checks.is_empty() && !is_text;
+    let (derive, lexical_field, equality, display) = if keeps_lexical {
+      (
+        quote!(#[derive(Clone, Debug)]),
+        quote!(lexical: Option<String>,),
+        quote!(
+          /// The values are compared, whatever their lexical forms.
+          impl PartialEq for #struct_name {
+            fn eq(&self, other: &Self) -> bool {
+              self.content == other.content
+            }
+          }
+        ),
+        quote!(match &self.lexical {
+          Some(lexical) => f.write_str(lexical),
+          None => write!(f, "{}", self.content),
+        }),
+      )
+    } else {
+      (
+        quote!(#[derive(Clone, Debug, PartialEq)]),
+        quote!(),
+        quote!(),
+        quote!(write!(f, "{}", self.content)),
+      )
+    };
+    let lexical = |value: TokenStream| {
+      if keeps_lexical {
+        quote!(lexical: #value,)
+      } else {
+        quote!()
+      }
+    };
+    let default_implementation = default_value.map(|(default_value, default_lexical)| {
+      let lexical_default = lexical(match default_lexical {
+        Some(default_lexical) => quote!(Some(#default_lexical.to_string())),
+        None => quote!(None),
+      });
+
+      quote!(
+        /// The first value satisfying the facets, ie. the lower bound.
+        impl Default for #struct_name {
+          fn default() -> Self {
+            #struct_name {
+              content: #default_value,
+              #lexical_default
+            }
+          }
+        }
+      )
+    });
+    let lexical_new = lexical(quote!(None));
+    let lexical_parsed = lexical(quote!(Some(s.into_owned())));
+
+    let text_content = simple_type::implement_text_content(struct_name);
+
+    Ok(quote!(
+      #derive
+      pub struct #struct_name {
+        content: #rust_type,
+        #lexical_field
+      }
+
+      #equality
+
+      #default_implementation
+
+      impl #struct_name {
+        /// Create the value, checking the facets of the type.
+        pub fn new(content: #rust_type) -> Result<Self, String> {
+          #check_value
+          #new_lexical
+          Ok(#struct_name {
+            content,
+            #lexical_new
+          })
+        }
+
+        pub fn value(&self) -> &#rust_type {
+          &self.content
+        }
+
+        pub fn into_value(self) -> #rust_type {
+          self.content
+        }
+
+        #validate
+
+        #validate_lexical
+      }
+
+      impl std::str::FromStr for #struct_name {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          let s = hifa_xml_schema::facets::WhiteSpace::#white_space.normalize(s);
+          #parse_lexical
+          let content = s
+            .parse::<#rust_type>()
+            .map_err(|e| format!("{}: invalid value {:?}: {}", #xml_name, s, e))?;
+
+          #check_value
+          Ok(#struct_name {
+            content,
+            #lexical_parsed
+          })
+        }
+      }
+
+      #from_content
+
+      impl std::fmt::Display for #struct_name {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+          #display
+        }
+      }
+
+      #text_content
+    ))
+  }
+
+  /// Checks of the value: its bounds, its digits, and the length of binary data.
+  fn implement_checks(
+    &self,
+    rust_type: &TokenStream,
+    compared_type: &TokenStream,
+    xml_name: &str,
+    is_binary: bool,
+  ) -> Result<TokenStream> {
+    let mut checks = TokenStream::new();
+
+    let has_bounds = self.min_inclusive.is_some()
+      || self.max_inclusive.is_some()
+      || self.min_exclusive.is_some()
+      || self.max_exclusive.is_some();
+
+    // a decimal kept as text is checked and compared as a `Decimal`
+    if compared_type.to_string() != rust_type.to_string() {
+      let parse = quote!(content
+        .parse::<#compared_type>()
+        .map_err(|e| format!("{}: {}", #xml_name, e))?);
+      checks.extend(if has_bounds {
+        quote!(let compared = &#parse;)
+      } else {
+        quote!(#parse;)
+      });
+    } else if has_bounds {
+      checks.extend(quote!(
+        let compared = content;
+      ));
+    }
+
+    let bounds = [
+      (
+        &self.min_inclusive,
+        "minInclusive",
+        quote!(<),
+        "less than the minimum",
+      ),
+      (
+        &self.max_inclusive,
+        "maxInclusive",
+        quote!(>),
+        "greater than the maximum",
+      ),
+      (
+        &self.min_exclusive,
+        "minExclusive",
+        quote!(<=),
+        "not greater than",
+      ),
+      (
+        &self.max_exclusive,
+        "maxExclusive",
+        quote!(>=),
+        "not less than",
+      ),
+    ];
+
+    for (facet, facet_name, operator, message) in bounds {
+      if let Some(facet) = facet {
+        if compared_type.to_string() == "String" {
+          return Err(Error::new(format!(
+            "{facet_name} is not supported on a base type mapped to String"
+          )));
+        }
+        let bound = facet_value(facet_name, &facet.value, compared_type)?;
+        let message = format!("{{}}: {{}} is {message} {{}}");
+        checks.extend(quote!({
+          let bound: #compared_type = #bound;
+          if compared #operator &bound {
+            return Err(format!(#message, #xml_name, content, bound));
+          }
+        }));
+      }
+    }
+
+    // the length of binary data is its number of octets
+    if is_binary {
+      for (facet, facet_name, operator, message) in self.lengths() {
+        if let Some(facet) = facet {
+          let length = parse_count(facet_name, &facet.value)?;
+          let message = format!("{{}}: the length {{}} of {{:?}} {message} {length}");
+          checks.extend(quote!({
+            let length = content.len();
+            if length #operator #length {
+              return Err(format!(#message, #xml_name, length, content.to_string()));
+            }
+          }));
+        }
+      }
+    }
+
+    // the leading and trailing zeros are not counted, the digits of the value are the ones of
+    // its lexical forms
+    let digits = [
+      (
+        &self.total_digits,
+        "totalDigits",
+        quote!(total_digits),
+        "digits",
+      ),
+      (
+        &self.fraction_digits,
+        "fractionDigits",
+        quote!(fraction_digits),
+        "fraction digits",
+      ),
+    ];
+
+    let mut digits_checks = TokenStream::new();
+    for (facet, facet_name, function, message) in digits {
+      if let Some(facet) = facet {
+        let digits = parse_count(facet_name, &facet.value)?;
+        let message = format!("{{}}: {{}} has more than {digits} {message}");
+        digits_checks.extend(quote!(
+          if hifa_xml_schema::facets::#function(&digits) > #digits {
+            return Err(format!(#message, #xml_name, digits));
+          }
+        ));
+      }
+    }
+
+    if !digits_checks.is_empty() {
+      checks.extend(quote!(
+        let digits = content.to_string();
+        #digits_checks
+      ));
+    }
+
+    Ok(checks)
+  }
+
+  /// Checks of the lexical form of the value: its patterns and its length.
+  fn implement_lexical_checks(&self, xml_name: &str, is_binary: bool) -> Result<TokenStream> {
+    let mut checks = TokenStream::new();
+
+    if !is_binary {
+      for (facet, facet_name, operator, message) in self.lengths() {
+        if let Some(facet) = facet {
+          let length = parse_count(facet_name, &facet.value)?;
+          let message = format!("{{}}: the length {{}} of {{:?}} {message} {length}");
+          checks.extend(quote!({
+            let length = lexical.chars().count();
+            if length #operator #length {
+              return Err(format!(#message, #xml_name, length, lexical));
+            }
+          }));
+        }
+      }
+    }
+
+    if !self.patterns.is_empty() {
+      let patterns: Vec<&String> = self.patterns.iter().map(|facet| &facet.value).collect();
+
+      checks.extend(quote!({
+        static PATTERNS: std::sync::OnceLock<Result<Vec<hifa_xml_schema::facets::Pattern>, String>> =
+          std::sync::OnceLock::new();
+
+        let patterns = PATTERNS
+          .get_or_init(|| {
+            [#(#patterns),*]
+              .iter()
+              .map(|expression| hifa_xml_schema::facets::Pattern::new(expression))
+              .collect()
+          })
+          .as_ref()
+          .map_err(|e| e.clone())?;
+
+        if !patterns.iter().any(|pattern| pattern.is_match(lexical)) {
+          return Err(format!(
+            "{}: {:?} does not match the pattern {:?}",
+            #xml_name,
+            lexical,
+            [#(#patterns),*].join("|")
+          ));
+        }
+      }));
+    }
+
+    Ok(checks)
+  }
+
+  fn lengths(&self) -> [(&Option<Facet>, &'static str, TokenStream, &'static str); 3] {
+    [
+      (&self.length, "length", quote!(!=), "is not"),
+      (&self.min_length, "minLength", quote!(<), "is less than"),
+      (&self.max_length, "maxLength", quote!(>), "is greater than"),
+    ]
+  }
+
   // quote!(
   //   #[derive(Clone, Debug, Default, PartialEq, hifa_yaserde_derive::YaDeserialize, hifa_yaserde_derive::YaSerialize)]
   //   #namespace_definition
@@ -161,3 +809,209 @@ impl Restriction {
   //     )
   //   }
 }
+
+/// The type in which the values of the base type are compared, `Decimal` for a decimal kept as text.
+fn compared_type(context: &XsdContext, base: &str, rust_type: &TokenStream) -> TokenStream {
+  if RustTypesMapping::is_xs_decimal(context, base) && rust_type.to_string() == "String" {
+    quote!(hifa_xml_schema::numeric::Decimal)
+  } else {
+    rust_type.clone()
+  }
+}
+
+/// Value of a facet, as an expression of the base type.
+/// The other types than the primitive ones are parsed at runtime.
+pub fn facet_value(facet_name: &str, value: &str, rust_type: &TokenStream) -> Result<TokenStream> {
+  if let Some(literal) = primitive_literal(facet_name, value, rust_type)? {
+    return Ok(literal);
+  }
+
+  Ok(quote!(
+    #value
+      .parse::<#rust_type>()
+      .map_err(|e| format!("invalid {} value {:?}: {}", #facet_name, #value, e))?
+  ))
+}
+
+/// Literal of a value of a primitive type, checked at compile time: the value is parsed in the
+/// Rust type, and the literal is suffixed with it.
+pub fn primitive_literal(
+  facet_name: &str,
+  value: &str,
+  rust_type: &TokenStream,
+) -> Result<Option<TokenStream>> {
+  let value = value.trim();
+  let invalid = |reason: String| {
+    Error::new(format!(
+      "invalid {facet_name} value {value:?} for the type {rust_type}: {reason}"
+    ))
+  };
+
+  let literal = match rust_type.to_string().as_str() {
+    "i8" => integer_literal::<i8>(value, "i8"),
+    "u8" => integer_literal::<u8>(value, "u8"),
+    "i16" => integer_literal::<i16>(value, "i16"),
+    "u16" => integer_literal::<u16>(value, "u16"),
+    "i32" => integer_literal::<i32>(value, "i32"),
+    "u32" => integer_literal::<u32>(value, "u32"),
+    "i64" => integer_literal::<i64>(value, "i64"),
+    "u64" => integer_literal::<u64>(value, "u64"),
+    "f32" => float_literal::<f32>(value, "f32"),
+    "f64" => float_literal::<f64>(value, "f64"),
+    "bool" => match value {
+      "true" | "1" => Ok(quote!(true)),
+      "false" | "0" => Ok(quote!(false)),
+      _ => Err("expected true, false, 1 or 0".to_string()),
+    },
+    _ => return Ok(None),
+  };
+
+  literal.map(Some).map_err(invalid)
+}
+
+/// Suffixed literal of an integer, parsed in the type of the suffix to check its range.
+fn integer_literal<T>(value: &str, suffix: &str) -> std::result::Result<TokenStream, String>
+where
+  T: std::str::FromStr + Into<i128>,
+  T::Err: std::fmt::Display,
+{
+  let number: i128 = value.parse::<T>().map_err(|e| e.to_string())?.into();
+  let literal =
+    Literal::from_str(&format!("{}{suffix}", number.unsigned_abs())).map_err(|e| e.to_string())?;
+
+  Ok(if number < 0 {
+    quote!(-#literal)
+  } else {
+    quote!(#literal)
+  })
+}
+
+/// Suffixed literal of a floating number, or its `INF`, `-INF` and `NaN` special values.
+fn float_literal<T>(value: &str, suffix: &str) -> std::result::Result<TokenStream, String>
+where
+  T: std::str::FromStr + std::fmt::Debug + Copy + Into<f64>,
+  T::Err: std::fmt::Display,
+{
+  let rust_type = format_ident!("{}", suffix);
+  match value {
+    "INF" | "+INF" => return Ok(quote!(#rust_type::INFINITY)),
+    "-INF" => return Ok(quote!(#rust_type::NEG_INFINITY)),
+    "NaN" => return Ok(quote!(#rust_type::NAN)),
+    _ => {}
+  }
+
+  // Rust also parses `inf` or `nan`, which are not lexical forms of XML Schema
+  if !value
+    .chars()
+    .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+  {
+    return Err("not a number".to_string());
+  }
+
+  let number = value.parse::<T>().map_err(|e| e.to_string())?;
+  if !number.into().is_finite() {
+    return Err("out of range".to_string());
+  }
+
+  let lexical = format!("{number:?}");
+  let (negative, magnitude) = match lexical.strip_prefix('-') {
+    Some(magnitude) => (true, magnitude),
+    None => (false, lexical.as_str()),
+  };
+  let literal = Literal::from_str(&format!("{magnitude}{suffix}")).map_err(|e| e.to_string())?;
+
+  Ok(if negative {
+    quote!(-#literal)
+  } else {
+    quote!(#literal)
+  })
+}
+
+fn parse_count(facet_name: &str, value: &str) -> Result<usize> {
+  value
+    .trim()
+    .parse::<usize>()
+    .map_err(|_| Error::new(format!("invalid {facet_name} value {value:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::xsd::simple_type::SimpleType;
+  use hifa_yaserde::de::from_str;
+
+  fn literal(value: &str, rust_type: TokenStream) -> std::result::Result<String, String> {
+    facet_value("maxInclusive", value, &rust_type)
+      .map(|literal| literal.to_string())
+      .map_err(|error| error.to_string())
+  }
+
+  #[test]
+  fn facet_values() {
+    assert_eq!(literal("-5", quote!(i32)).unwrap(), "- 5i32");
+    assert_eq!(literal("-128", quote!(i8)).unwrap(), "- 128i8");
+    assert_eq!(literal("255", quote!(u8)).unwrap(), "255u8");
+    assert_eq!(literal("2.5", quote!(f64)).unwrap(), "2.5f64");
+    assert_eq!(literal("-1E3", quote!(f32)).unwrap(), "- 1000.0f32");
+    assert_eq!(literal("1", quote!(bool)).unwrap(), "true");
+    assert_eq!(
+      literal("abc", quote!(i32)).unwrap_err(),
+      r#"invalid maxInclusive value "abc" for the type i32: invalid digit found in string"#
+    );
+  }
+
+  #[test]
+  fn out_of_range_values() {
+    assert_eq!(
+      literal("300", quote!(u8)).unwrap_err(),
+      r#"invalid maxInclusive value "300" for the type u8: number too large to fit in target type"#
+    );
+    assert_eq!(
+      literal("-1", quote!(u32)).unwrap_err(),
+      r#"invalid maxInclusive value "-1" for the type u32: invalid digit found in string"#
+    );
+    assert_eq!(
+      literal("1e39", quote!(f32)).unwrap_err(),
+      r#"invalid maxInclusive value "1e39" for the type f32: out of range"#
+    );
+    assert_eq!(
+      literal("inf", quote!(f64)).unwrap_err(),
+      r#"invalid maxInclusive value "inf" for the type f64: not a number"#
+    );
+  }
+
+  #[test]
+  fn special_float_values() {
+    assert_eq!(literal("INF", quote!(f32)).unwrap(), "f32 :: INFINITY");
+    assert_eq!(literal("-INF", quote!(f64)).unwrap(), "f64 :: NEG_INFINITY");
+    assert_eq!(literal("NaN", quote!(f64)).unwrap(), "f64 :: NAN");
+    assert!(literal("INF", quote!(i32)).is_err());
+  }
+
+  #[test]
+  fn out_of_range_enumeration() {
+    let simple_type: SimpleType = from_str(
+      r#"
+      <xs:simpleType name="Level" xmlns:xs="http://www.w3.org/2001/XMLSchema">
+        <xs:restriction base="xs:unsignedByte">
+          <xs:enumeration value="1"/>
+          <xs:enumeration value="256"/>
+        </xs:restriction>
+      </xs:simpleType>
+    "#,
+    )
+    .unwrap();
+
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    let error = simple_type
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      r#"restriction: invalid enumeration value "256" for the type u8: number too large to fit in target type"#
+    );
+  }
+}
@@ -1,4 +1,4 @@
-use crate::xsd::XsdContext;
+use crate::xsd::{Error, Precision, Result, XsdContext};
 use heck::ToUpperCamelCase;
 use proc_macro2::TokenStream;
 use syn::{parse_str, TypePath};
@@ -7,12 +7,12 @@ use syn::{parse_str, TypePath};
 pub struct RustTypesMapping {}
 
 impl RustTypesMapping {
-  pub fn get(context: &XsdContext, kind: &str) -> TokenStream {
+  pub fn get(context: &XsdContext, kind: &str) -> Result<TokenStream> {
     let items: Vec<&str> = kind.split(':').collect();
 
     if items.len() == 2 {
       if context.match_xml_schema_prefix(items.first().unwrap()) {
-        RustTypesMapping::basic_type(items.last().unwrap())
+        RustTypesMapping::basic_type(context, items.last().unwrap())
       } else {
         RustTypesMapping::extern_type(context, items)
       }
@@ -20,10 +20,10 @@ impl RustTypesMapping {
       if context.has_xml_schema_prefix() {
         RustTypesMapping::extern_type(context, items)
       } else {
-        RustTypesMapping::basic_type(items.last().unwrap())
+        RustTypesMapping::basic_type(context, items.last().unwrap())
       }
     } else {
-      panic!("Unknown type {}", kind)
+      Err(Error::new(format!("Unknown type {kind}")))
     }
   }
 
@@ -49,6 +49,68 @@ impl RustTypesMapping {
     false
   }
 
+  /// The type is a built-in type of XML Schema, it is not generated in the `xml_schema_types` module.
+  pub fn is_xs_type(context: &XsdContext, kind: &str) -> bool {
+    let items: Vec<&str> = kind.split(':').collect();
+
+    match items.as_slice() {
+      [prefix, _] => context.match_xml_schema_prefix(prefix),
+      [_] => !context.has_xml_schema_prefix(),
+      _ => false,
+    }
+  }
+
+  /// The `whiteSpace` normalization of a built-in type, a user-defined type normalizes its own values.
+  pub fn white_space(context: &XsdContext, kind: &str) -> TokenStream {
+    if !RustTypesMapping::is_xs_type(context, kind) {
+      return quote!(Preserve);
+    }
+
+    match kind.rsplit(':').next() {
+      Some("string") => quote!(Preserve),
+      Some("normalizedString") => quote!(Replace),
+      _ => quote!(Collapse),
+    }
+  }
+
+  /// `xs:decimal` is mapped to a `String` in native precision, its values are compared as `Decimal`.
+  pub fn is_xs_decimal(context: &XsdContext, kind: &str) -> bool {
+    RustTypesMapping::is_xs_type(context, kind) && kind.rsplit(':').next() == Some("decimal")
+  }
+
+  /// Built-in type whose value is its lexical form, after the `whiteSpace` normalization.
+  pub fn is_xs_text(context: &XsdContext, kind: &str) -> bool {
+    RustTypesMapping::is_xs_type(context, kind)
+      && matches!(
+        kind.rsplit(':').next(),
+        Some(
+          "string"
+            | "normalizedString"
+            | "token"
+            | "language"
+            | "Name"
+            | "NCName"
+            | "NMTOKEN"
+            | "ID"
+            | "IDREF"
+            | "ENTITY"
+            | "anyURI"
+            | "NOTATION"
+        )
+      )
+  }
+
+  /// `xs:QName`, its value is resolved against the namespaces in scope.
+  pub fn is_xs_qname(context: &XsdContext, kind: &str) -> bool {
+    RustTypesMapping::is_xs_type(context, kind) && kind.rsplit(':').next() == Some("QName")
+  }
+
+  /// `xs:base64Binary` and `xs:hexBinary`, the length of their values is a number of octets.
+  pub fn is_xs_binary(context: &XsdContext, kind: &str) -> bool {
+    RustTypesMapping::is_xs_type(context, kind)
+      && matches!(kind.rsplit(':').next(), Some("base64Binary" | "hexBinary"))
+  }
+
   pub fn is_xs_int(context: &XsdContext, kind: &str) -> bool {
     let items: Vec<&str> = kind.split(':').collect();
 
@@ -63,41 +125,64 @@ impl RustTypesMapping {
     false
   }
 
-  fn basic_type(item: &str) -> TokenStream {
-    match item {
-      "bool" => quote!(bool),
-      "boolean" => quote!(bool),
-      "positiveInteger" => quote!(u64),
+  fn basic_type(context: &XsdContext, item: &str) -> Result<TokenStream> {
+    if context.precision() == Precision::Arbitrary {
+      match item {
+        "integer" => return Ok(quote!(hifa_xml_schema::numeric::Integer)),
+        "nonPositiveInteger" => return Ok(quote!(hifa_xml_schema::numeric::NonPositiveInteger)),
+        "negativeInteger" => return Ok(quote!(hifa_xml_schema::numeric::NegativeInteger)),
+        "nonNegativeInteger" => return Ok(quote!(hifa_xml_schema::numeric::NonNegativeInteger)),
+        "positiveInteger" => return Ok(quote!(hifa_xml_schema::numeric::PositiveInteger)),
+        "decimal" => return Ok(quote!(hifa_xml_schema::numeric::Decimal)),
+        _ => {}
+      }
+    }
+
+    let rust_type = match item {
+      "bool" | "boolean" => quote!(bool),
       "byte" => quote!(i8),
       "unsignedByte" => quote!(u8),
       "short" => quote!(i16),
       "unsignedShort" => quote!(u16),
-      "int" | "integer" => quote!(i32),
+      "int" => quote!(i32),
       "unsignedInt" => quote!(u32),
-      "long" => quote!(i64),
+      "long" | "integer" => quote!(i64),
       "unsignedLong" | "nonNegativeInteger" => quote!(u64),
+      "nonPositiveInteger" => quote!(hifa_xml_schema::numeric::NonPositiveI64),
+      "negativeInteger" => quote!(hifa_xml_schema::numeric::NegativeI64),
+      "positiveInteger" => quote!(hifa_xml_schema::numeric::PositiveU64),
       "float" => quote!(f32),
       "double" => quote!(f64),
-      "decimal" => quote!(String), // TODO replace with f64
-      "string" => quote!(String),
-      "normalizedString" => quote!(String),
-      "anyURI" => quote!(String),
-      "token" => quote!(String),
-      "language" => quote!(String),
-      "hexBinary" => quote!(String),
-      "dateTime" => quote!(String),
-      "base64Binary" => quote!(String),
-      "duration" => quote!(String),
-      "gYear" => quote!(u16),
-      "ID" => quote!(String),
-      "IDREF" => quote!(String),
-      "IDREFS" => quote!(String),
-      "anyType" => quote!(String),
-      _ => panic!("Type {:?} not implemented", item),
-    }
+      "decimal" => quote!(String),
+      "string" | "anyURI" | "NOTATION" => quote!(String),
+      "normalizedString" => quote!(hifa_xml_schema::string::NormalizedString),
+      "token" | "language" | "Name" | "NCName" | "NMTOKEN" | "ID" | "IDREF" | "ENTITY" => {
+        quote!(hifa_xml_schema::string::Token)
+      }
+      "QName" => quote!(hifa_xml_schema::qname::QName),
+      "NMTOKENS" | "IDREFS" | "ENTITIES" => quote!(hifa_xml_schema::list::List<String>),
+      "base64Binary" => quote!(hifa_xml_schema::binary::Base64Binary),
+      "hexBinary" => quote!(hifa_xml_schema::binary::HexBinary),
+      "dateTime" => quote!(hifa_xml_schema::temporal::DateTime),
+      "dateTimeStamp" => quote!(hifa_xml_schema::temporal::DateTimeStamp),
+      "date" => quote!(hifa_xml_schema::temporal::Date),
+      "time" => quote!(hifa_xml_schema::temporal::Time),
+      "duration" => quote!(hifa_xml_schema::temporal::Duration),
+      "dayTimeDuration" => quote!(hifa_xml_schema::temporal::DayTimeDuration),
+      "yearMonthDuration" => quote!(hifa_xml_schema::temporal::YearMonthDuration),
+      "gYearMonth" => quote!(hifa_xml_schema::temporal::GYearMonth),
+      "gYear" => quote!(hifa_xml_schema::temporal::GYear),
+      "gMonthDay" => quote!(hifa_xml_schema::temporal::GMonthDay),
+      "gDay" => quote!(hifa_xml_schema::temporal::GDay),
+      "gMonth" => quote!(hifa_xml_schema::temporal::GMonth),
+      "anyType" | "anySimpleType" | "anyAtomicType" => quote!(String),
+      _ => return Err(Error::new(format!("Type {item:?} not implemented"))),
+    };
+
+    Ok(rust_type)
   }
 
-  fn extern_type(context: &XsdContext, items: Vec<&str>) -> TokenStream {
+  fn extern_type(context: &XsdContext, items: Vec<&str>) -> Result<TokenStream> {
     let struct_name = if items.last().unwrap().is_empty() {
       "String".to_string()
     } else {
@@ -126,8 +211,9 @@ impl RustTypesMapping {
     };
 
     let struct_name = format!("{module}{struct_name}");
-    let struct_name = parse_str::<TypePath>(&struct_name).unwrap();
-    quote!(#struct_name)
+    let struct_name = parse_str::<TypePath>(&struct_name)
+      .map_err(|e| Error::new(format!("Invalid type name {struct_name:?}: {e}")))?;
+    Ok(quote!(#struct_name))
   }
 }
 
@@ -142,44 +228,282 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    assert!(RustTypesMapping::get(&context, "xs:boolean").to_string() == "bool");
-    assert!(RustTypesMapping::get(&context, "xs:positiveInteger").to_string() == "u64");
-    assert!(RustTypesMapping::get(&context, "xs:byte").to_string() == "i8");
-    assert!(RustTypesMapping::get(&context, "xs:unsignedByte").to_string() == "u8");
-    assert!(RustTypesMapping::get(&context, "xs:short").to_string() == "i16");
-    assert!(RustTypesMapping::get(&context, "xs:unsignedShort").to_string() == "u16");
-    assert!(RustTypesMapping::get(&context, "xs:int").to_string() == "i32");
-    assert!(RustTypesMapping::get(&context, "xs:integer").to_string() == "i32");
-    assert!(RustTypesMapping::get(&context, "xs:unsignedInt").to_string() == "u32");
-    assert!(RustTypesMapping::get(&context, "xs:long").to_string() == "i64");
-    assert!(RustTypesMapping::get(&context, "xs:unsignedLong").to_string() == "u64");
-    assert!(RustTypesMapping::get(&context, "xs:nonNegativeInteger").to_string() == "u64");
-    assert!(RustTypesMapping::get(&context, "xs:float").to_string() == "f32");
-    assert!(RustTypesMapping::get(&context, "xs:double").to_string() == "f64");
-    assert!(RustTypesMapping::get(&context, "xs:decimal").to_string() == "String");
-    assert!(RustTypesMapping::get(&context, "xs:string").to_string() == "String");
-    assert!(RustTypesMapping::get(&context, "xs:string").to_string() == "String");
-    assert!(RustTypesMapping::get(&context, "xs:ID").to_string() == "String");
-    assert!(RustTypesMapping::get(&context, "xs:IDREF").to_string() == "String");
-    assert!(RustTypesMapping::get(&context, "xs:IDREFS").to_string() == "String");
-    assert!(RustTypesMapping::get(&context, "xs:anyType").to_string() == "String");
-
-    assert!(RustTypesMapping::get(&context, "other:type").to_string() == "Type");
+    assert!(
+      RustTypesMapping::get(&context, "xs:boolean")
+        .unwrap()
+        .to_string()
+        == "bool"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:positiveInteger")
+        .unwrap()
+        .to_string()
+        == "hifa_xml_schema :: numeric :: PositiveU64"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:byte")
+        .unwrap()
+        .to_string()
+        == "i8"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:unsignedByte")
+        .unwrap()
+        .to_string()
+        == "u8"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:short")
+        .unwrap()
+        .to_string()
+        == "i16"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:unsignedShort")
+        .unwrap()
+        .to_string()
+        == "u16"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:int")
+        .unwrap()
+        .to_string()
+        == "i32"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:integer")
+        .unwrap()
+        .to_string()
+        == "i64"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:unsignedInt")
+        .unwrap()
+        .to_string()
+        == "u32"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:long")
+        .unwrap()
+        .to_string()
+        == "i64"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:unsignedLong")
+        .unwrap()
+        .to_string()
+        == "u64"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:nonNegativeInteger")
+        .unwrap()
+        .to_string()
+        == "u64"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:float")
+        .unwrap()
+        .to_string()
+        == "f32"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:double")
+        .unwrap()
+        .to_string()
+        == "f64"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:decimal")
+        .unwrap()
+        .to_string()
+        == "String"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:negativeInteger")
+        .unwrap()
+        .to_string()
+        == "hifa_xml_schema :: numeric :: NegativeI64"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:string")
+        .unwrap()
+        .to_string()
+        == "String"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:string")
+        .unwrap()
+        .to_string()
+        == "String"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:ID")
+        .unwrap()
+        .to_string()
+        == "hifa_xml_schema :: string :: Token"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:IDREF")
+        .unwrap()
+        .to_string()
+        == "hifa_xml_schema :: string :: Token"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:IDREFS")
+        .unwrap()
+        .to_string()
+        == "hifa_xml_schema :: list :: List < String >"
+    );
+    assert!(
+      RustTypesMapping::get(&context, "xs:anyType")
+        .unwrap()
+        .to_string()
+        == "String"
+    );
+
+    assert!(
+      RustTypesMapping::get(&context, "other:type")
+        .unwrap()
+        .to_string()
+        == "Type"
+    );
 
     let context =
       XsdContext::new(r#"<schema xmlns="http://www.w3.org/2001/XMLSchema"></schema>"#).unwrap();
 
-    assert!(RustTypesMapping::get(&context, "boolean").to_string() == "bool");
+    assert!(
+      RustTypesMapping::get(&context, "boolean")
+        .unwrap()
+        .to_string()
+        == "bool"
+    );
+  }
+
+  #[test]
+  fn temporal_mapping_types() {
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    for (kind, rust_type) in [
+      ("xs:dateTime", "DateTime"),
+      ("xs:date", "Date"),
+      ("xs:time", "Time"),
+      ("xs:duration", "Duration"),
+      ("xs:gYear", "GYear"),
+      ("xs:gYearMonth", "GYearMonth"),
+      ("xs:gMonthDay", "GMonthDay"),
+      ("xs:gDay", "GDay"),
+      ("xs:gMonth", "GMonth"),
+    ] {
+      assert_eq!(
+        RustTypesMapping::get(&context, kind).unwrap().to_string(),
+        format!("hifa_xml_schema :: temporal :: {rust_type}")
+      );
+      assert!(RustTypesMapping::is_xs_type(&context, kind));
+    }
+
+    assert!(!RustTypesMapping::is_xs_type(&context, "Other"));
+  }
+
+  #[test]
+  fn built_in_mapping_types() {
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    for (kind, rust_type) in [
+      ("xs:QName", "hifa_xml_schema :: qname :: QName"),
+      ("xs:NCName", "hifa_xml_schema :: string :: Token"),
+      ("xs:Name", "hifa_xml_schema :: string :: Token"),
+      ("xs:NMTOKEN", "hifa_xml_schema :: string :: Token"),
+      ("xs:ENTITY", "hifa_xml_schema :: string :: Token"),
+      ("xs:language", "hifa_xml_schema :: string :: Token"),
+      (
+        "xs:normalizedString",
+        "hifa_xml_schema :: string :: NormalizedString",
+      ),
+      ("xs:NOTATION", "String"),
+      ("xs:anySimpleType", "String"),
+      ("xs:anyAtomicType", "String"),
+      (
+        "xs:nonPositiveInteger",
+        "hifa_xml_schema :: numeric :: NonPositiveI64",
+      ),
+      (
+        "xs:negativeInteger",
+        "hifa_xml_schema :: numeric :: NegativeI64",
+      ),
+      ("xs:nonNegativeInteger", "u64"),
+      (
+        "xs:positiveInteger",
+        "hifa_xml_schema :: numeric :: PositiveU64",
+      ),
+      ("xs:NMTOKENS", "hifa_xml_schema :: list :: List < String >"),
+      ("xs:ENTITIES", "hifa_xml_schema :: list :: List < String >"),
+      (
+        "xs:dateTimeStamp",
+        "hifa_xml_schema :: temporal :: DateTimeStamp",
+      ),
+      (
+        "xs:dayTimeDuration",
+        "hifa_xml_schema :: temporal :: DayTimeDuration",
+      ),
+      (
+        "xs:yearMonthDuration",
+        "hifa_xml_schema :: temporal :: YearMonthDuration",
+      ),
+      (
+        "xs:base64Binary",
+        "hifa_xml_schema :: binary :: Base64Binary",
+      ),
+      ("xs:hexBinary", "hifa_xml_schema :: binary :: HexBinary"),
+    ] {
+      assert_eq!(
+        RustTypesMapping::get(&context, kind).unwrap().to_string(),
+        rust_type,
+        "{kind}"
+      );
+    }
+  }
+
+  #[test]
+  fn arbitrary_precision_mapping_types() {
+    let mut context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+    context.set_precision(Precision::Arbitrary);
+
+    for (kind, rust_type) in [
+      ("xs:integer", "Integer"),
+      ("xs:nonPositiveInteger", "NonPositiveInteger"),
+      ("xs:negativeInteger", "NegativeInteger"),
+      ("xs:nonNegativeInteger", "NonNegativeInteger"),
+      ("xs:positiveInteger", "PositiveInteger"),
+      ("xs:decimal", "Decimal"),
+    ] {
+      assert_eq!(
+        RustTypesMapping::get(&context, kind).unwrap().to_string(),
+        format!("hifa_xml_schema :: numeric :: {rust_type}")
+      );
+    }
+
+    assert_eq!(
+      RustTypesMapping::get(&context, "xs:int")
+        .unwrap()
+        .to_string(),
+      "i32"
+    );
   }
 
   #[test]
-  #[should_panic]
   fn rust_bad_mapping_type() {
     let context =
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    RustTypesMapping::get(&context, "xs:unknown");
+    let error = RustTypesMapping::get(&context, "xs:unknown").unwrap_err();
+    assert_eq!(error.to_string(), r#"Type "unknown" not implemented"#);
   }
 
   #[test]
@@ -203,12 +527,16 @@ mod tests {
 
     let context = context.with_module_namespace_mappings(&mapping);
     assert_eq!(
-      RustTypesMapping::get(&context, "example:MyType").to_string(),
+      RustTypesMapping::get(&context, "example:MyType")
+        .unwrap()
+        .to_string(),
       "rust_example_module :: MyType"
     );
 
     assert_eq!(
-      RustTypesMapping::get(&context, "example:").to_string(),
+      RustTypesMapping::get(&context, "example:")
+        .unwrap()
+        .to_string(),
       "rust_example_module :: String"
     );
   }
@@ -234,12 +562,14 @@ mod tests {
 
     let context = context.with_module_namespace_mappings(&mapping);
     assert_eq!(
-      RustTypesMapping::get(&context, "MyType").to_string(),
+      RustTypesMapping::get(&context, "MyType")
+        .unwrap()
+        .to_string(),
       "rust_example_module :: MyType"
     );
 
     assert_eq!(
-      RustTypesMapping::get(&context, "").to_string(),
+      RustTypesMapping::get(&context, "").unwrap().to_string(),
       "rust_example_module :: String"
     );
   }
@@ -1,8 +1,10 @@
 use crate::xsd::{
-  attribute, attribute_group, complex_type, element, group, import, qualification, simple_type,
-  Implementation, XsdContext,
+  attribute, attribute_group, complex_type, element, group, import, include, opaque, qualification,
+  redefine::Redefine, simple_type, Error, Implementation, Result, XsdContext,
 };
-use proc_macro2::TokenStream;
+use heck::ToUpperCamelCase;
+use proc_macro2::{Span, TokenStream};
+use syn::Ident;
 
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(
@@ -19,6 +21,12 @@ pub struct Schema {
   pub element_form_default: Option<qualification::Qualification>,
   #[yaserde(rename = "attributeFormDefault", attribute = true)]
   pub attribute_form_default: Option<qualification::Qualification>,
+  #[yaserde(rename = "include")]
+  pub includes: Vec<include::Include>,
+  #[yaserde(rename = "redefine")]
+  pub redefines: Vec<Redefine>,
+  #[yaserde(rename = "override")]
+  pub overrides: Vec<Redefine>,
   #[yaserde(rename = "import")]
   pub imports: Vec<import::Import>,
   #[yaserde(rename = "element")]
@@ -41,15 +49,49 @@ impl Implementation for Schema {
     _namespace_definition: &TokenStream,
     target_prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
-    let namespace_definition = generate_namespace_definition(target_prefix, &self.target_namespace);
+  ) -> Result<TokenStream> {
+    self.implement_with_modules(target_prefix, context, &TokenStream::new())
+  }
+}
+
+impl Schema {
+  /// Generate the schema, with `modules` inserted in the `xml_schema_types` module.
+  /// It contains the modules of imported schemas, or the re-export of them.
+  pub fn implement_with_modules(
+    &self,
+    target_prefix: &Option<String>,
+    context: &XsdContext,
+    modules: &TokenStream,
+  ) -> Result<TokenStream> {
+    let namespace_definition =
+      generate_namespace_definition(target_prefix, &self.target_namespace)?;
+
+    let mut context = context.clone();
+    context.add_enumerations(
+      self
+        .simple_type
+        .iter()
+        .filter(|simple_type| simple_type.is_enumeration())
+        .map(|simple_type| simple_type.name.as_str()),
+    );
+    context.add_attribute_groups(&self.attribute_group);
+    context.add_groups(&self.group);
+    context.add_simple_types(&self.simple_type);
+    let context = &context;
 
     log::info!("Generate elements");
     let elements: TokenStream = self
       .elements
       .iter()
-      .map(|element| element.implement(&namespace_definition, target_prefix, context))
-      .collect();
+      .map(|element| {
+        context.implement_component(
+          "element",
+          Some(&element.name),
+          || element.implement(&namespace_definition, target_prefix, context),
+          || opaque_fallback(&element.name),
+        )
+      })
+      .collect::<Result<_>>()?;
 
     log::info!("Generate simple types");
     let simple_types: TokenStream = {
@@ -59,8 +101,15 @@ impl Implementation for Schema {
       self
         .simple_type
         .iter()
-        .map(|simple_type| simple_type.implement(&namespace_definition, target_prefix, &context))
-        .collect()
+        .map(|simple_type| {
+          context.implement_component(
+            "simpleType",
+            Some(&simple_type.name),
+            || simple_type.implement(&namespace_definition, target_prefix, &context),
+            || opaque_fallback(&simple_type.name),
+          )
+        })
+        .collect::<Result<_>>()?
     };
 
     log::info!("Generate complex types");
@@ -71,10 +120,35 @@ impl Implementation for Schema {
       self
         .complex_type
         .iter()
-        .map(|complex_type| complex_type.implement(&namespace_definition, target_prefix, &context))
-        .collect()
+        .map(|complex_type| {
+          context.implement_component(
+            "complexType",
+            Some(&complex_type.name),
+            || complex_type.implement(&namespace_definition, target_prefix, &context),
+            || opaque_fallback(&complex_type.name),
+          )
+        })
+        .collect::<Result<_>>()?
     };
 
+    log::info!("Generate attribute groups");
+    let attribute_groups: TokenStream = self
+      .attribute_group
+      .iter()
+      .map(|attribute_group| {
+        attribute_group
+          .attributes
+          .iter()
+          .map(|attribute| {
+            attribute
+              .get_sub_type_implementation(&namespace_definition, context, target_prefix)
+              .map_err(|error| error.with_component("attribute", attribute.name.as_deref()))
+          })
+          .collect::<Result<TokenStream>>()
+          .map_err(|error| error.with_component("attributeGroup", attribute_group.name.as_deref()))
+      })
+      .collect::<Result<_>>()?;
+
     log::info!("Generate groups");
     let groups: TokenStream = {
       let mut context = context.clone();
@@ -83,36 +157,246 @@ impl Implementation for Schema {
       self
         .group
         .iter()
-        .map(|group| group.implement(&namespace_definition, target_prefix, &context))
-        .collect()
+        .map(|group| {
+          context.implement_component(
+            "group",
+            group.name.as_deref(),
+            || group.implement(&namespace_definition, target_prefix, &context),
+            || opaque_fallback(group.name.as_deref().unwrap_or_default()),
+          )
+        })
+        .collect::<Result<_>>()?
     };
 
-    quote!(
+    Ok(quote!(
       pub mod xml_schema_types {
+        #modules
         #simple_types
         #complex_types
+        #attribute_groups
         #groups
       }
 
       #elements
-    )
+    ))
+  }
+
+  /// Merge the components of an included schema into this one.
+  /// An included schema without target namespace (chameleon include) adopts the namespace of this schema.
+  pub fn include(&mut self, schema: Schema) -> Result<()> {
+    if schema.target_namespace.is_some() && schema.target_namespace != self.target_namespace {
+      return Err(Error::new(format!(
+        "Included schema namespace {:?} does not match the target namespace {:?}",
+        schema.target_namespace.unwrap_or_default(),
+        self.target_namespace.clone().unwrap_or_default()
+      )));
+    }
+
+    self.includes.extend(schema.includes);
+    self.redefines.extend(schema.redefines);
+    self.overrides.extend(schema.overrides);
+    self.imports.extend(schema.imports);
+    self.elements.extend(schema.elements);
+    self.simple_type.extend(schema.simple_type);
+    self.complex_type.extend(schema.complex_type);
+    self.attributes.extend(schema.attributes);
+    self.attribute_group.extend(schema.attribute_group);
+    self.group.extend(schema.group);
+
+    Ok(())
+  }
+
+  /// Replace the components of this schema by the ones of a `xs:redefine` or `xs:override`.
+  /// With `resolve_self_reference` (`xs:redefine`), a type derived from its own name is derived
+  /// from the original definition, which is kept with an `Original` suffix.
+  pub fn redefine(&mut self, redefine: &Redefine, resolve_self_reference: bool) {
+    for simple_type in &redefine.simple_types {
+      let mut simple_type = simple_type.clone();
+      let name = simple_type.name.clone();
+      let base = simple_type
+        .restriction
+        .as_mut()
+        .and_then(|restriction| restriction.base.as_mut());
+
+      match base {
+        Some(base) if resolve_self_reference && local_name(base) == name => {
+          let original = original_name(&name);
+          *base = rename_local_name(base, &original);
+          self
+            .simple_type
+            .iter_mut()
+            .filter(|simple_type| simple_type.name == name)
+            .for_each(|simple_type| simple_type.name = original.clone());
+          self.simple_type.push(simple_type);
+        }
+        _ => replace_component(&mut self.simple_type, simple_type, |simple_type| {
+          Some(&simple_type.name)
+        }),
+      }
+    }
+
+    for complex_type in &redefine.complex_types {
+      let mut complex_type = complex_type.clone();
+      let name = complex_type.name.clone();
+
+      match complex_type_base(&mut complex_type) {
+        Some(base) if resolve_self_reference && local_name(base) == name => {
+          let original = original_name(&name);
+          *base = rename_local_name(base, &original);
+          self
+            .complex_type
+            .iter_mut()
+            .filter(|complex_type| complex_type.name == name)
+            .for_each(|complex_type| complex_type.name = original.clone());
+          self.complex_type.push(complex_type);
+        }
+        _ => replace_component(&mut self.complex_type, complex_type, |complex_type| {
+          Some(&complex_type.name)
+        }),
+      }
+    }
+
+    for group in &redefine.groups {
+      let mut group = group.clone();
+      let name = group.name.clone().unwrap_or_default();
+
+      match group_self_reference(&mut group, &name) {
+        Some(reference) if resolve_self_reference => {
+          let original = original_name(&name);
+          *reference = rename_local_name(reference, &original);
+          self
+            .group
+            .iter_mut()
+            .filter(|group| group.name.as_deref() == Some(name.as_str()))
+            .for_each(|group| group.name = Some(original.clone()));
+          self.group.push(group);
+        }
+        _ => replace_component(&mut self.group, group, |group| group.name.as_deref()),
+      }
+    }
+
+    for attribute_group in &redefine.attribute_groups {
+      let mut attribute_group = attribute_group.clone();
+      let name = attribute_group.name.clone().unwrap_or_default();
+      let self_reference = attribute_group
+        .attribute_groups
+        .iter_mut()
+        .map(|nested| &mut nested.reference)
+        .find(|reference| local_name(reference) == name);
+
+      match self_reference {
+        Some(reference) if resolve_self_reference => {
+          let original = original_name(&name);
+          *reference = rename_local_name(reference, &original);
+          self
+            .attribute_group
+            .iter_mut()
+            .filter(|attribute_group| attribute_group.name.as_deref() == Some(name.as_str()))
+            .for_each(|attribute_group| attribute_group.name = Some(original.clone()));
+          self.attribute_group.push(attribute_group);
+        }
+        _ => replace_component(
+          &mut self.attribute_group,
+          attribute_group,
+          |attribute_group| attribute_group.name.as_deref(),
+        ),
+      }
+    }
+
+    for element in &redefine.elements {
+      replace_component(&mut self.elements, element.clone(), |element| {
+        Some(&element.name)
+      });
+    }
+
+    for attribute in &redefine.attributes {
+      replace_component(&mut self.attributes, attribute.clone(), |attribute| {
+        attribute.name.as_deref()
+      });
+    }
+  }
+}
+
+/// Replace the component with the same name, or append it if it is not defined.
+fn replace_component<T>(components: &mut Vec<T>, component: T, name: impl Fn(&T) -> Option<&str>) {
+  let component_name = name(&component).map(|name| name.to_string());
+
+  if let Some(existing) = components
+    .iter_mut()
+    .find(|existing| component_name.is_some() && name(existing) == component_name.as_deref())
+  {
+    *existing = component;
+  } else {
+    components.push(component);
   }
 }
 
+/// The reference of a redefined group to itself, in its sequence or choice.
+fn group_self_reference<'a>(group: &'a mut group::Group, name: &str) -> Option<&'a mut String> {
+  let references: Vec<&mut group::Group> = match (&mut group.sequence, &mut group.choice) {
+    (Some(sequence), _) => sequence.groups_mut().collect(),
+    (None, Some(choice)) => choice.groups_mut().collect(),
+    (None, None) => return None,
+  };
+
+  references
+    .into_iter()
+    .filter_map(|reference| reference.reference.as_mut())
+    .find(|reference| local_name(reference) == name)
+}
+
+fn complex_type_base(complex_type: &mut complex_type::ComplexType) -> Option<&mut String> {
+  if let Some(extension) = complex_type
+    .complex_content
+    .as_mut()
+    .and_then(|complex_content| complex_content.extension.as_mut())
+  {
+    return Some(&mut extension.base);
+  }
+
+  complex_type
+    .simple_content
+    .as_mut()
+    .map(|simple_content| &mut simple_content.extension.base)
+}
+
+fn local_name(qname: &str) -> &str {
+  qname.rsplit(':').next().unwrap_or(qname)
+}
+
+fn rename_local_name(qname: &str, name: &str) -> String {
+  match qname.rsplit_once(':') {
+    Some((prefix, _)) => format!("{prefix}:{name}"),
+    None => name.to_string(),
+  }
+}
+
+fn original_name(name: &str) -> String {
+  format!("{name}Original")
+}
+
+fn opaque_fallback(name: &str) -> TokenStream {
+  let struct_name = Ident::new(
+    &name.replace('.', "_").to_upper_camel_case(),
+    Span::call_site(),
+  );
+  opaque::implement_opaque(&struct_name, name)
+}
+
 fn generate_namespace_definition(
   target_prefix: &Option<String>,
   target_namespace: &Option<String>,
-) -> TokenStream {
+) -> Result<TokenStream> {
   match (target_prefix, target_namespace) {
-    (None, None) => quote!(),
-    (None, Some(_target_namespace)) => {
-      panic!("undefined prefix attribute, a target namespace is defined")
-    }
-    (Some(_prefix), None) => panic!(
-      "a prefix attribute, but no target namespace is defined, please remove the prefix parameter"
-    ),
+    (None, None) => Ok(quote!()),
+    (None, Some(_target_namespace)) => Err(Error::new(
+      "undefined prefix attribute, a target namespace is defined",
+    )),
+    (Some(_prefix), None) => Err(Error::new(
+      "a prefix attribute, but no target namespace is defined, please remove the prefix parameter",
+    )),
     (Some(prefix), Some(target_namespace)) => {
-      quote!(#[yaserde(prefix=#prefix, namespaces = { #prefix = #target_namespace })])
+      Ok(quote!(#[yaserde(prefix=#prefix, namespaces = { #prefix = #target_namespace })]))
     }
   }
 }
@@ -120,6 +404,7 @@ fn generate_namespace_definition(
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::xsd::sequence::{Particle, Sequence};
 
   #[test]
   fn default_schema_implementation() {
@@ -129,12 +414,16 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    let implementation = format!("{}", schema.implement(&TokenStream::new(), &None, &context));
+    let implementation = format!(
+      "{}",
+      schema
+        .implement(&TokenStream::new(), &None, &context)
+        .unwrap()
+    );
     assert_eq!(implementation, "pub mod xml_schema_types { }");
   }
 
   #[test]
-  #[should_panic]
   fn missing_prefix() {
     let schema = Schema {
       target_namespace: Some("http://example.com".to_string()),
@@ -145,11 +434,16 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    schema.implement(&TokenStream::new(), &None, &context);
+    let error = schema
+      .implement(&TokenStream::new(), &None, &context)
+      .unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "undefined prefix attribute, a target namespace is defined"
+    );
   }
 
   #[test]
-  #[should_panic]
   fn missing_target_namespace() {
     let schema = Schema::default();
 
@@ -157,7 +451,179 @@ mod tests {
       XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
         .unwrap();
 
-    schema.implement(&TokenStream::new(), &Some("ex".to_string()), &context);
+    assert!(schema
+      .implement(&TokenStream::new(), &Some("ex".to_string()), &context)
+      .is_err());
+  }
+
+  #[test]
+  fn include_chameleon_schema() {
+    let mut schema = Schema {
+      target_namespace: Some("http://example.com".to_string()),
+      ..Default::default()
+    };
+
+    let included = Schema {
+      complex_type: vec![complex_type::ComplexType {
+        name: "Included".to_string(),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    schema.include(included).unwrap();
+    assert_eq!(schema.complex_type.len(), 1);
+    assert_eq!(
+      schema.target_namespace,
+      Some("http://example.com".to_string())
+    );
+  }
+
+  #[test]
+  fn include_other_namespace() {
+    let mut schema = Schema {
+      target_namespace: Some("http://example.com".to_string()),
+      ..Default::default()
+    };
+
+    let included = Schema {
+      target_namespace: Some("http://other.com".to_string()),
+      ..Default::default()
+    };
+
+    assert!(schema.include(included).is_err());
+  }
+
+  #[test]
+  fn redefine_self_reference() {
+    let mut schema = Schema {
+      simple_type: vec![simple_type::SimpleType {
+        name: "Code".to_string(),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    let redefine = Redefine {
+      simple_types: vec![simple_type::SimpleType {
+        name: "Code".to_string(),
+        restriction: Some(crate::xsd::restriction::Restriction {
+          base: Some("tns:Code".to_string()),
+          ..Default::default()
+        }),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    schema.redefine(&redefine, true);
+
+    assert_eq!(schema.simple_type.len(), 2);
+    assert_eq!(schema.simple_type[0].name, "CodeOriginal");
+    assert_eq!(schema.simple_type[1].name, "Code");
+    assert_eq!(
+      schema.simple_type[1].restriction.as_ref().unwrap().base,
+      Some("tns:CodeOriginal".to_string())
+    );
+  }
+
+  #[test]
+  fn redefine_attribute_group_self_reference() {
+    let mut schema = Schema {
+      attribute_group: vec![attribute_group::AttributeGroup {
+        name: Some("core".to_string()),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    let redefine = Redefine {
+      attribute_groups: vec![attribute_group::AttributeGroup {
+        name: Some("core".to_string()),
+        attribute_groups: vec![attribute_group::AttributeGroupReference {
+          reference: "core".to_string(),
+        }],
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    schema.redefine(&redefine, true);
+
+    assert_eq!(schema.attribute_group.len(), 2);
+    assert_eq!(
+      schema.attribute_group[0].name,
+      Some("coreOriginal".to_string())
+    );
+    assert_eq!(
+      schema.attribute_group[1].attribute_groups[0].reference,
+      "coreOriginal"
+    );
+  }
+
+  #[test]
+  fn redefine_group_self_reference() {
+    let mut schema = Schema {
+      group: vec![group::Group {
+        name: Some("address".to_string()),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    let redefine = Redefine {
+      groups: vec![group::Group {
+        name: Some("address".to_string()),
+        sequence: Some(Sequence {
+          particles: vec![Particle::Group(group::Group {
+            reference: Some("tns:address".to_string()),
+            ..Default::default()
+          })],
+          ..Default::default()
+        }),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    schema.redefine(&redefine, true);
+
+    assert_eq!(schema.group.len(), 2);
+    assert_eq!(schema.group[0].name, Some("addressOriginal".to_string()));
+    assert_eq!(
+      schema.group[1].sequence.as_ref().unwrap().particles,
+      vec![Particle::Group(group::Group {
+        reference: Some("tns:addressOriginal".to_string()),
+        ..Default::default()
+      })]
+    );
+  }
+
+  #[test]
+  fn override_component() {
+    let mut schema = Schema {
+      complex_type: vec![complex_type::ComplexType {
+        name: "Person".to_string(),
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    let redefine = Redefine {
+      complex_types: vec![complex_type::ComplexType {
+        name: "Person".to_string(),
+        attributes: vec![attribute::Attribute {
+          name: Some("id".to_string()),
+          ..Default::default()
+        }],
+        ..Default::default()
+      }],
+      ..Default::default()
+    };
+
+    schema.redefine(&redefine, false);
+
+    assert_eq!(schema.complex_type, redefine.complex_types);
   }
 
   #[test]
@@ -167,7 +633,7 @@ mod tests {
       &Some("http://example.com".to_string()),
     );
 
-    let implementation = format!("{definition}");
+    let implementation = format!("{}", definition.unwrap());
 
     assert_eq!(
       implementation,
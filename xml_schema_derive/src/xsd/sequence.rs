@@ -1,80 +1,482 @@
-use crate::xsd::{choice::Choice, element::Element, Implementation, XsdContext};
+use crate::xsd::{
+  choice::{self, Choice},
+  element::Element,
+  group::{self, Group},
+  max_occurences::MaxOccurences,
+  Result, XsdContext,
+};
+use hifa_yaserde::{de::Deserializer, YaDeserialize};
 use log::info;
 use proc_macro2::TokenStream;
+use std::borrow::Cow;
+use std::io::Read;
+use syn::Ident;
+use xml::reader::XmlEvent;
 
-#[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
-#[yaserde(prefix = "xs", namespaces = {
-    "xs" = "http://www.w3.org/2001/XMLSchema"
-  })]
+#[derive(Clone, Default, Debug, PartialEq)]
 pub struct Sequence {
-  #[yaserde(rename = "element")]
-  pub elements: Vec<Element>,
-  #[yaserde(rename = "choice")]
-  pub choices: Vec<Choice>,
+  pub min_occurences: Option<u64>,
+  pub max_occurences: Option<MaxOccurences>,
+  /// The particles in their declared order, which is the order of the fields.
+  pub particles: Vec<Particle>,
 }
 
-impl Implementation for Sequence {
-  fn implement(
+#[derive(Clone, Debug, PartialEq)]
+pub enum Particle {
+  Element(Box<Element>),
+  Choice(Choice),
+  Group(Group),
+  Sequence(Sequence),
+}
+
+// the particles of the different kinds are read in a single list, to keep their order,
+// and the end element is left to the parent as in the derived implementations
+impl YaDeserialize for Sequence {
+  fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> std::result::Result<Self, String> {
+    deserialize_particles(reader, "sequence")
+  }
+}
+
+/// The occurrences and the particles of a sequence or a choice, in their declared order.
+pub fn deserialize_particles<R: Read>(
+  reader: &mut Deserializer<R>,
+  compositor: &str,
+) -> std::result::Result<Sequence, String> {
+  let attributes = match reader.next_event()? {
+    XmlEvent::StartElement { attributes, .. } => attributes,
+    _ => return Err(format!("Missing start event for {compositor}")),
+  };
+
+  let mut sequence = Sequence::default();
+  for attribute in attributes {
+    match attribute.name.local_name.as_str() {
+      "minOccurs" => {
+        sequence.min_occurences = Some(attribute.value.parse().map_err(|e| format!("{e}"))?)
+      }
+      "maxOccurs" => sequence.max_occurences = Some(attribute.value.parse()?),
+      _ => {}
+    }
+  }
+
+  loop {
+    let local_name = match reader.peek()? {
+      XmlEvent::StartElement { name, .. } => name.local_name.clone(),
+      XmlEvent::EndElement { .. } => break,
+      _ => {
+        reader.next_event()?;
+        continue;
+      }
+    };
+
+    let particle = match local_name.as_str() {
+      "element" => Particle::Element(Box::new(Element::deserialize(reader)?)),
+      "choice" => Particle::Choice(Choice::deserialize(reader)?),
+      "group" => Particle::Group(Group::deserialize(reader)?),
+      "sequence" => Particle::Sequence(Sequence::deserialize(reader)?),
+      // ie. annotation, any
+      _ => {
+        reader.next_event()?;
+        reader.skip_element(|_| {})?;
+        continue;
+      }
+    };
+    sequence.particles.push(particle);
+    // like the derived structures, a particle leaves its end element to the parent
+    reader.next_event()?;
+  }
+
+  Ok(sequence)
+}
+
+impl Sequence {
+  /// The sequence of a type. A repeated or optional one is nested in a wrapping sequence,
+  /// to keep its occurrences (ie. `sequence_list: Vec<{Struct}Sequence>`).
+  pub fn as_content(&self) -> Cow<'_, Sequence> {
+    if self.is_multiple() || self.min_occurences == Some(0) {
+      Cow::Owned(Sequence {
+        particles: vec![Particle::Sequence(self.clone())],
+        ..Default::default()
+      })
+    } else {
+      Cow::Borrowed(self)
+    }
+  }
+
+  pub fn elements(&self) -> impl Iterator<Item = &Element> {
+    self.particles.iter().filter_map(|particle| match particle {
+      Particle::Element(element) => Some(element.as_ref()),
+      _ => None,
+    })
+  }
+
+  pub fn choices(&self) -> impl Iterator<Item = &Choice> {
+    self.particles.iter().filter_map(|particle| match particle {
+      Particle::Choice(choice) => Some(choice),
+      _ => None,
+    })
+  }
+
+  pub fn groups_mut(&mut self) -> impl Iterator<Item = &mut Group> {
+    self
+      .particles
+      .iter_mut()
+      .filter_map(|particle| match particle {
+        Particle::Group(group) => Some(group),
+        _ => None,
+      })
+  }
+
+  pub fn sequences(&self) -> impl Iterator<Item = &Sequence> {
+    self.particles.iter().filter_map(|particle| match particle {
+      Particle::Sequence(sequence) => Some(sequence),
+      _ => None,
+    })
+  }
+
+  pub fn get_sub_types_implementation(
     &self,
-    _namespace_definition: &TokenStream,
-    prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
-    info!("Generate elements");
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    info!("Generate sub types implementation");
     let elements: TokenStream = self
-      .elements
-      .iter()
-      .map(|element| element.get_field_implementation(context, prefix, false, false))
-      .collect();
+      .elements()
+      .map(|element| {
+        element
+          .get_subtypes_implementation(namespace_definition, prefix, context)
+          .map_err(|error| error.with_component("element", Some(&element.name)))
+      })
+      .collect::<Result<_>>()?;
 
     let choices: TokenStream = self
-      .choices
-      .iter()
-      .map(|choice| choice.get_field_implementation(context, prefix))
-      .collect();
+      .choices()
+      .enumerate()
+      .map(|(index, choice)| {
+        let (_field_name, enum_name) = choice::choice_names(struct_name, index);
+        choice
+          .get_sub_types_implementation(context, namespace_definition, prefix, &enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .collect::<Result<_>>()?;
+
+    let sequences: TokenStream = self
+      .sequences()
+      .enumerate()
+      .map(|(index, sequence)| {
+        let (_field_name, sequence_name) = sequence_names(struct_name, index);
+        let structure = sequence.get_struct_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &sequence_name,
+        )?;
+        let sub_types = sequence.get_sub_types_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &sequence_name,
+        )?;
+
+        Ok(quote!(
+          #structure
+          #sub_types
+        ))
+      })
+      .collect::<Result<TokenStream>>()
+      .map_err(|error| error.with_component("sequence", None))?;
 
-    quote!(
+    Ok(quote!(
       #elements
       #choices
-    )
+      #sequences
+    ))
   }
-}
 
-impl Sequence {
-  pub fn get_sub_types_implementation(
+  /// Enums of the choices of the sequence, and structures of the nested sequences.
+  pub fn get_choices_implementation(
     &self,
     context: &XsdContext,
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
-  ) -> TokenStream {
-    info!("Generate sub types implementation");
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    let choices: TokenStream = self
+      .choices()
+      .enumerate()
+      .map(|(index, choice)| {
+        let (_field_name, enum_name) = choice::choice_names(struct_name, index);
+        choice
+          .get_choices_implementation(context, namespace_definition, prefix, &enum_name)
+          .map_err(|error| error.with_component("choice", None))
+      })
+      .collect::<Result<_>>()?;
+
+    let sequences: TokenStream = self
+      .sequences()
+      .enumerate()
+      .map(|(index, sequence)| {
+        let (_field_name, sequence_name) = sequence_names(struct_name, index);
+        let structure = sequence.get_struct_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &sequence_name,
+        )?;
+        let choices = sequence.get_choices_implementation(
+          context,
+          namespace_definition,
+          prefix,
+          &sequence_name,
+        )?;
+
+        Ok(quote!(
+          #structure
+          #choices
+        ))
+      })
+      .collect::<Result<TokenStream>>()
+      .map_err(|error| error.with_component("sequence", None))?;
+
+    Ok(quote!(
+      #choices
+      #sequences
+    ))
+  }
+
+  pub fn get_field_implementation(
+    &self,
+    context: &XsdContext,
+    prefix: &Option<String>,
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    info!("Generate elements");
+    // the choices and the nested sequences are numbered among those of their kind
+    let mut choice_index = 0;
+    let mut sequence_index = 0;
+
     self
-      .elements
+      .particles
       .iter()
-      .map(|element| element.get_subtypes_implementation(namespace_definition, prefix, context))
+      .map(|particle| match particle {
+        Particle::Element(element) => element
+          .get_field_implementation(context, prefix, false, false)
+          .map_err(|error| error.with_component("element", Some(&element.name))),
+        Particle::Group(group) => group
+          .get_field_implementation(context, prefix)
+          .map_err(|error| error.with_component("group", group.reference.as_deref())),
+        Particle::Sequence(sequence) => {
+          let (field_name, sequence_name) = sequence_names(struct_name, sequence_index);
+          sequence_index += 1;
+          sequence
+            .get_nested_field_implementation(context, &field_name, &sequence_name)
+            .map_err(|error| error.with_component("sequence", None))
+        }
+        Particle::Choice(choice) => {
+          let (field_name, enum_name) = choice::choice_names(struct_name, choice_index);
+          choice_index += 1;
+          choice
+            .get_field_implementation(context, &field_name, &enum_name)
+            .map_err(|error| error.with_component("choice", None))
+        }
+      })
       .collect()
   }
 
-  pub fn get_field_implementation(
+  /// The structure of a nested sequence, read from the children of the parent element as a group.
+  pub fn get_struct_implementation(
     &self,
     context: &XsdContext,
+    namespace_definition: &TokenStream,
     prefix: &Option<String>,
-  ) -> TokenStream {
-    let elements: TokenStream = self
-      .elements
-      .iter()
-      .map(|element| element.get_field_implementation(context, prefix, false, false))
-      .collect();
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    let fields = self.get_field_implementation(context, prefix, struct_name)?;
+    let elements = self.get_elements(context, &mut vec![])?;
 
-    let choices: TokenStream = self
-      .choices
-      .iter()
-      .map(|choice| choice.get_field_implementation(context, prefix))
-      .collect();
+    group::implement_group_struct(namespace_definition, struct_name, fields, &elements)
+  }
+
+  /// The field of a nested sequence, flattened in the parent structure.
+  fn get_nested_field_implementation(
+    &self,
+    context: &XsdContext,
+    field_name: &str,
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    let elements = group::elements_attribute(&group::repeat_elements(
+      self.get_elements(context, &mut vec![])?,
+      self.is_multiple(),
+    ));
+
+    let (field_name, field_type) = if self.is_multiple() {
+      (
+        format_ident!("{}_list", field_name),
+        quote!(Vec<#struct_name>),
+      )
+    } else if self.min_occurences == Some(0) {
+      (
+        format_ident!("{}", field_name),
+        quote!(Option<#struct_name>),
+      )
+    } else {
+      (format_ident!("{}", field_name), quote!(#struct_name))
+    };
 
-    quote!(
+    Ok(quote!(
+      #[yaserde(flatten = true)]
       #elements
-      #choices
+      pub #field_name: #field_type,
+    ))
+  }
+
+  /// The local names of the elements of the sequence, with whether they can be repeated.
+  pub fn get_elements(
+    &self,
+    context: &XsdContext,
+    visited: &mut Vec<String>,
+  ) -> Result<Vec<(String, bool)>> {
+    let mut elements = vec![];
+    for particle in &self.particles {
+      match particle {
+        Particle::Element(element) => elements.push(group::element_entry(element)),
+        Particle::Group(group) => elements.extend(group.get_elements(context, visited)?),
+        Particle::Sequence(sequence) => elements.extend(group::repeat_elements(
+          sequence.get_elements(context, visited)?,
+          sequence.is_multiple(),
+        )),
+        Particle::Choice(choice) => elements.extend(choice.get_elements(context, visited)?),
+      }
+    }
+    Ok(elements)
+  }
+
+  pub fn is_multiple(&self) -> bool {
+    matches!(self.min_occurences, Some(min_occurences) if min_occurences > 1)
+      || matches!(self.max_occurences, Some(MaxOccurences::Unbounded))
+      || matches!(self.max_occurences, Some(MaxOccurences::Number{value}) if value > 1)
+  }
+}
+
+/// Name of the field and of the structure of a nested sequence, numbered from the second one.
+pub fn sequence_names(struct_name: &Ident, index: usize) -> (String, Ident) {
+  if index == 0 {
+    (
+      "sequence".to_string(),
+      format_ident!("{}Sequence", struct_name),
+    )
+  } else {
+    (
+      format!("sequence_{}", index + 1),
+      format_ident!("{}Sequence{}", struct_name, index + 1),
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hifa_yaserde::de::from_str;
+  use proc_macro2::Span;
+
+  #[test]
+  fn nested_sequences() {
+    let xml = r#"
+      <sequence>
+        <element name="id" type="xs:int"/>
+        <sequence maxOccurs="unbounded">
+          <element name="key" type="xs:string"/>
+          <element name="value" type="xs:string" maxOccurs="2"/>
+        </sequence>
+        <sequence minOccurs="0">
+          <element name="note" type="xs:string"/>
+        </sequence>
+      </sequence>
+    "#;
+
+    let sequence: Sequence = from_str(xml).unwrap();
+    assert_eq!(sequence.sequences().count(), 2);
+
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+    let struct_name = Ident::new("Entry", Span::call_site());
+
+    let implementation = sequence
+      .get_field_implementation(&context, &None, &struct_name)
+      .unwrap()
+      .to_string();
+
+    assert_eq!(
+      implementation,
+      "# [yaserde (rename = \"id\")] pub id : i32 , \
+# [yaserde (flatten = true)] # [xml_schema (elements = & [(\"key\" , true) , (\"value\" , true)])] \
+pub sequence_list : Vec < EntrySequence > , \
+# [yaserde (flatten = true)] # [xml_schema (elements = & [(\"note\" , false)])] \
+pub sequence_2 : Option < EntrySequence2 > ,"
+    );
+
+    assert_eq!(
+      sequence.get_elements(&context, &mut vec![]).unwrap(),
+      vec![
+        ("id".to_string(), false),
+        ("key".to_string(), true),
+        ("value".to_string(), true),
+        ("note".to_string(), false),
+      ]
+    );
+  }
+
+  #[test]
+  fn interleaved_particles() {
+    let xml = r#"
+      <sequence>
+        <annotation><documentation>kept in order</documentation></annotation>
+        <element name="a" type="xs:int"/>
+        <choice>
+          <element name="b" type="xs:int"/>
+          <element name="c" type="xs:int"/>
+        </choice>
+        <sequence minOccurs="0">
+          <element name="e" type="xs:int"/>
+        </sequence>
+        <element name="d" type="xs:int"/>
+      </sequence>
+    "#;
+
+    let sequence: Sequence = from_str(xml).unwrap();
+    assert_eq!(sequence.particles.len(), 4);
+
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+    let struct_name = Ident::new("Item", Span::call_site());
+
+    let implementation = sequence
+      .get_field_implementation(&context, &None, &struct_name)
+      .unwrap()
+      .to_string();
+
+    assert_eq!(
+      implementation,
+      "# [yaserde (rename = \"a\")] pub a : i32 , \
+# [yaserde (flatten = true)] # [xml_schema (elements = & [(\"b\" , false) , (\"c\" , false)])] \
+pub choice : ItemChoice , \
+# [yaserde (flatten = true)] # [xml_schema (elements = & [(\"e\" , false)])] \
+pub sequence : Option < ItemSequence > , \
+# [yaserde (rename = \"d\")] pub d : i32 ,"
+    );
+
+    assert_eq!(
+      sequence.get_elements(&context, &mut vec![]).unwrap(),
+      vec![
+        ("a".to_string(), false),
+        ("b".to_string(), false),
+        ("c".to_string(), false),
+        ("e".to_string(), false),
+        ("d".to_string(), false),
+      ]
+    );
+  }
+}
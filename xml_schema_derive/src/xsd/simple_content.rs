@@ -1,5 +1,6 @@
-use crate::xsd::{extension::Extension, Implementation, XsdContext};
+use crate::xsd::{extension::Extension, Implementation, Result, XsdContext};
 use proc_macro2::TokenStream;
+use syn::Ident;
 
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(prefix = "xs", namespaces = {
@@ -16,10 +17,11 @@ impl Implementation for SimpleContent {
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     self
       .extension
       .implement(namespace_definition, prefix, context)
+      .map_err(|error| error.with_component("extension", None))
   }
 }
 
@@ -29,9 +31,11 @@ impl SimpleContent {
     namespace_definition: &TokenStream,
     context: &XsdContext,
     prefix: &Option<String>,
-  ) -> TokenStream {
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
     self
       .extension
-      .get_field_implementation(namespace_definition, context, prefix)
+      .get_field_implementation(namespace_definition, context, prefix, struct_name)
+      .map_err(|error| error.with_component("extension", None))
   }
 }
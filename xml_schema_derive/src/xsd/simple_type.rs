@@ -1,4 +1,10 @@
-use crate::xsd::{list::List, restriction::Restriction, union::Union, Implementation, XsdContext};
+use crate::xsd::{
+  list::List,
+  restriction::{primitive_literal, Restriction},
+  rust_types_mapping::RustTypesMapping,
+  union::Union,
+  Error, Implementation, Precision, Result, XsdContext,
+};
 use heck::ToUpperCamelCase;
 use proc_macro2::{Span, TokenStream};
 use syn::Ident;
@@ -21,38 +27,206 @@ impl Implementation for SimpleType {
     namespace_definition: &TokenStream,
     prefix: &Option<String>,
     context: &XsdContext,
-  ) -> TokenStream {
+  ) -> Result<TokenStream> {
     let struct_name = Ident::new(&self.name.to_upper_camel_case(), Span::call_site());
 
     if let Some(list) = &self.list {
-      return list.implement_childs(namespace_definition, prefix, context, &struct_name);
+      return list
+        .implement_childs(namespace_definition, prefix, context, &struct_name)
+        .map_err(|error| error.with_component("list", None));
     }
 
     if let Some(restriction) = &self.restriction {
-      return restriction.implement_childs(namespace_definition, prefix, context, &struct_name);
+      return restriction
+        .implement_childs(namespace_definition, prefix, context, &struct_name)
+        .map_err(|error| error.with_component("restriction", None));
     }
 
-    unimplemented!("unimplemented SimpleType: {:?}", self);
+    if let Some(union) = &self.union {
+      return union
+        .implement_childs(namespace_definition, prefix, context, &struct_name)
+        .map_err(|error| error.with_component("union", None));
+    }
+
+    Err(Error::new(
+      "simpleType is only supported with a list, a restriction or a union",
+    ))
   }
 }
 
 impl SimpleType {
+  /// The simple type is generated as an enum of its enumeration values.
+  pub fn is_enumeration(&self) -> bool {
+    self
+      .restriction
+      .as_ref()
+      .map(|restriction| !restriction.enumerations.is_empty())
+      .unwrap_or_default()
+  }
+
   pub fn get_type_implementation(
     &self,
     context: &XsdContext,
     prefix: &Option<String>,
     struct_name: Option<&Ident>,
-  ) -> TokenStream {
-    match (self.restriction.as_ref(), self.list.as_ref()) {
-      (Some(restriction), None) => restriction.get_type_implementation(context, prefix),
-      (None, Some(list)) => list.get_type_implementation(
-        context,
-        prefix,
-        struct_name.expect("List type should have a name"),
-      ),
-      _ => panic!("Not implemented Rust type for: {:?}", self),
+  ) -> Result<TokenStream> {
+    match (
+      self.restriction.as_ref(),
+      self.list.as_ref(),
+      self.union.as_ref(),
+      struct_name,
+    ) {
+      (Some(restriction), None, None, _) => restriction
+        .get_type_implementation(context, prefix)
+        .map_err(|error| error.with_component("restriction", None)),
+      (None, Some(list), None, Some(struct_name)) => {
+        Ok(list.get_type_implementation(context, prefix, struct_name))
+      }
+      (None, Some(_), None, None) => Err(Error::new("list type should have a name")),
+      (None, None, Some(_), Some(struct_name)) => Ok(quote!(#struct_name)),
+      (None, None, Some(_), None) => Err(Error::new("union type should have a name")),
+      _ => Err(Error::new(
+        "simpleType is only supported with a list, a restriction or a union",
+      )),
+    }
+  }
+}
+
+impl SimpleType {
+  /// Typed value of the type, the value of a list or a union is parsed at runtime.
+  pub fn typed_value(
+    &self,
+    context: &XsdContext,
+    rust_type: &TokenStream,
+    constraint: &str,
+    value: &str,
+  ) -> Result<Option<TokenStream>> {
+    match (&self.restriction, &self.list, &self.union) {
+      (Some(restriction), None, None) => restriction
+        .typed_value(context, rust_type, constraint, value)
+        .map_err(|error| error.with_component("restriction", None)),
+      _ => Ok(None),
+    }
+  }
+}
+
+/// Expression of the `default` or `fixed` value of an attribute or an element in `rust_type`,
+/// the type of its field: the value is checked when generating the code, so it is used by the
+/// `Default` implementation of the structure without being parsed.
+/// `None` when there is no such value, or when it is parsed at runtime.
+pub fn typed_value_constraint(
+  context: &XsdContext,
+  kind: Option<&str>,
+  simple_type: Option<&SimpleType>,
+  rust_type: &TokenStream,
+  default: Option<&str>,
+  fixed: Option<&str>,
+) -> Result<Option<TokenStream>> {
+  let (constraint, value) = match (default, fixed) {
+    (Some(default), _) => ("default", default),
+    (None, Some(fixed)) => ("fixed", fixed),
+    (None, None) => return Ok(None),
+  };
+
+  match (kind, simple_type) {
+    (Some(kind), _) => typed_value(context, kind, rust_type, constraint, value),
+    (None, Some(simple_type)) => simple_type.typed_value(context, rust_type, constraint, value),
+    (None, None) => Ok(None),
+  }
+}
+
+/// Expression of a value of a type in `rust_type`: a primitive literal or a string of a built-in
+/// type, or a value of a simple type of the schema. `None` when the value of the type can not be
+/// written as a constant, it is then parsed at runtime.
+pub fn typed_value(
+  context: &XsdContext,
+  kind: &str,
+  rust_type: &TokenStream,
+  constraint: &str,
+  value: &str,
+) -> Result<Option<TokenStream>> {
+  if let Some(simple_type) = context.get_simple_type(kind) {
+    return simple_type.typed_value(context, rust_type, constraint, value);
+  }
+
+  if RustTypesMapping::is_xs_type(context, kind) {
+    if RustTypesMapping::is_xs_text(context, kind) {
+      return Ok(Some(quote!(<#rust_type>::from(#value))));
+    }
+    // `xs:decimal` is a `String` in native precision
+    if RustTypesMapping::is_xs_decimal(context, kind)
+      && context.precision() == Precision::Native
+      && is_decimal(value.trim())
+    {
+      return Ok(Some(quote!(<#rust_type>::from(#value.trim()))));
+    }
+    if let Some(literal) = primitive_literal(constraint, value, rust_type)? {
+      return Ok(Some(literal));
     }
   }
+
+  Ok(None)
+}
+
+/// Lexical form of `xs:decimal`: digits with an optional sign and an optional decimal point.
+fn is_decimal(value: &str) -> bool {
+  let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+  let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+  !(integer.is_empty() && fraction.is_empty())
+    && integer
+      .chars()
+      .chain(fraction.chars())
+      .all(|c| c.is_ascii_digit())
+}
+
+/// (De)serialization of a simple type from its text content, through `FromStr` and `Display`.
+pub fn implement_text_content(struct_name: &Ident) -> TokenStream {
+  let xml_name = struct_name.to_string();
+
+  quote!(
+    impl hifa_yaserde::YaDeserialize for #struct_name {
+      fn deserialize<R: std::io::Read>(reader: &mut hifa_yaserde::de::Deserializer<R>) -> Result<Self, String> {
+        if matches!(reader.peek()?, xml::reader::XmlEvent::StartElement{..}) {
+          reader.next_event()?;
+        }
+
+        let content = match reader.peek()?.clone() {
+          xml::reader::XmlEvent::Characters(content) => {
+            reader.next_event()?;
+            content
+          }
+          _ => String::new(),
+        };
+
+        content.parse()
+      }
+    }
+
+    impl hifa_yaserde::YaSerialize for #struct_name {
+      fn serialize<W: std::io::Write>(&self, writer: &mut hifa_yaserde::ser::Serializer<W>) -> Result<(), String> {
+        let skip = writer.skip_start_end();
+
+        if !skip {
+          let name = writer.get_start_event_name().unwrap_or_else(|| #xml_name.to_string());
+          writer.write(xml::writer::XmlEvent::start_element(name.as_str())).map_err(|e| e.to_string())?;
+        }
+
+        let content = self.to_string();
+        writer.write(xml::writer::XmlEvent::characters(&content)).map_err(|e| e.to_string())?;
+
+        if !skip {
+          writer.write(xml::writer::XmlEvent::end_element()).map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+      }
+
+      fn serialize_attributes(&self, source_attributes: Vec<xml::attribute::OwnedAttribute>, source_namespace: xml::namespace::Namespace) -> Result<(Vec<xml::attribute::OwnedAttribute>, xml::namespace::Namespace), String> {
+        Ok((source_attributes, source_namespace))
+      }
+    }
+  )
 }
 
 #[cfg(test)]
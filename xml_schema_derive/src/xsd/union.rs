@@ -1,3 +1,11 @@
+use crate::xsd::{
+  rust_types_mapping::RustTypesMapping,
+  simple_type::{self, SimpleType},
+  Error, Implementation, Result, XsdContext,
+};
+use heck::ToUpperCamelCase;
+use proc_macro2::{Ident, TokenStream};
+
 #[derive(Clone, Default, Debug, PartialEq, YaDeserialize)]
 #[yaserde(prefix = "xs", namespaces = {
     "xs" = "http://www.w3.org/2001/XMLSchema"
@@ -5,4 +13,221 @@
 pub struct Union {
   #[yaserde(rename = "memberTypes", attribute = true)]
   pub member_types: String,
+  #[yaserde(rename = "simpleType")]
+  pub simple_types: Vec<SimpleType>,
+}
+
+/// A member type of the union, ie. a variant of the generated enum.
+struct Member {
+  name: Ident,
+  rust_type: TokenStream,
+  implementation: TokenStream,
+}
+
+impl Implementation for Union {
+  /// The union is generated as an enum with a variant per member type.
+  /// The value is parsed with the first member type accepting it.
+  fn implement_childs(
+    &self,
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
+    context: &XsdContext,
+    struct_name: &Ident,
+  ) -> Result<TokenStream> {
+    let members = self.get_members(namespace_definition, prefix, context, struct_name)?;
+
+    let Some(first_member) = members.first() else {
+      return Err(Error::new("union without member type"));
+    };
+    let first_member_name = &first_member.name;
+
+    let names: Vec<&Ident> = members.iter().map(|member| &member.name).collect();
+    let rust_types: Vec<&TokenStream> = members.iter().map(|member| &member.rust_type).collect();
+    let implementations = members.iter().map(|member| &member.implementation);
+    let xml_name = struct_name.to_string();
+    let text_content = simple_type::implement_text_content(struct_name);
+
+    Ok(quote!(
+      #[derive(Clone, Debug, PartialEq)]
+      pub enum #struct_name {
+        #(#names(#rust_types),)*
+      }
+
+      impl Default for #struct_name {
+        fn default() -> Self {
+          #struct_name::#first_member_name(Default::default())
+        }
+      }
+
+      impl std::str::FromStr for #struct_name {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+          #(
+            if let Ok(value) = s.parse::<#rust_types>() {
+              return Ok(#struct_name::#names(value));
+            }
+          )*
+
+          Err(format!("Invalid value for {}: {}", #xml_name, s))
+        }
+      }
+
+      impl std::fmt::Display for #struct_name {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+          match self {
+            #(#struct_name::#names(value) => write!(f, "{}", value),)*
+          }
+        }
+      }
+
+      #text_content
+
+      #(#implementations)*
+    ))
+  }
+}
+
+impl Union {
+  /// Members of the `memberTypes` attribute, followed by the inline simple types.
+  /// An inline simple type is generated as `{Union}Member{index}` when it is not just a base type.
+  fn get_members(
+    &self,
+    namespace_definition: &TokenStream,
+    prefix: &Option<String>,
+    context: &XsdContext,
+    struct_name: &Ident,
+  ) -> Result<Vec<Member>> {
+    let mut members = vec![];
+
+    for member_type in self.member_types.split_whitespace() {
+      let local_name = member_type.split(':').next_back().unwrap_or_default();
+      members.push(Member {
+        name: format_ident!("{}", local_name.replace('.', "_").to_upper_camel_case()),
+        rust_type: RustTypesMapping::get(context, member_type)?,
+        implementation: TokenStream::new(),
+      });
+    }
+
+    for (index, simple_type) in self.simple_types.iter().enumerate() {
+      let name = format_ident!("Member{}", index + 1);
+
+      let is_base_type = simple_type.list.is_none()
+        && simple_type.union.is_none()
+        && simple_type
+          .restriction
+          .as_ref()
+          .map(|restriction| restriction.enumerations.is_empty() && !restriction.has_facets())
+          .unwrap_or_default();
+
+      let member = if is_base_type {
+        Member {
+          name,
+          rust_type: simple_type
+            .get_type_implementation(context, prefix, None)
+            .map_err(|error| error.with_component("simpleType", None))?,
+          implementation: TokenStream::new(),
+        }
+      } else {
+        let type_name = format_ident!("{}{}", struct_name, name);
+        let implementation = SimpleType {
+          name: type_name.to_string(),
+          ..simple_type.clone()
+        }
+        .implement(namespace_definition, prefix, context)
+        .map_err(|error| error.with_component("simpleType", None))?;
+
+        Member {
+          name,
+          rust_type: quote!(#type_name),
+          implementation,
+        }
+      };
+
+      members.push(member);
+    }
+
+    let mut names: Vec<Ident> = vec![];
+    for member in &mut members {
+      let base_name = member.name.clone();
+      let mut index: usize = 2;
+      while names.contains(&member.name) {
+        member.name = format_ident!("{}{}", base_name, index);
+        index += 1;
+      }
+      names.push(member.name.clone());
+    }
+
+    Ok(members)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hifa_yaserde::de::from_str;
+  use proc_macro2::Span;
+
+  #[test]
+  fn union_members() {
+    let xml = r#"
+      <union memberTypes="xs:int Size xs:string">
+        <simpleType>
+          <restriction base="xs:string">
+            <enumeration value="auto"/>
+          </restriction>
+        </simpleType>
+        <simpleType>
+          <restriction base="xs:string"/>
+        </simpleType>
+      </union>
+    "#;
+
+    let union: Union = from_str(xml).unwrap();
+
+    let context =
+      XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+        .unwrap();
+
+    let struct_name = Ident::new("Width", Span::call_site());
+
+    let members: Vec<(String, String)> = union
+      .get_members(&TokenStream::new(), &None, &context, &struct_name)
+      .unwrap()
+      .iter()
+      .map(|member| (member.name.to_string(), member.rust_type.to_string()))
+      .collect();
+
+    assert_eq!(
+      members,
+      vec![
+        ("Int".to_string(), "i32".to_string()),
+        ("Size".to_string(), "Size".to_string()),
+        ("String".to_string(), "String".to_string()),
+        ("Member1".to_string(), "WidthMember1".to_string()),
+        ("Member2".to_string(), "String".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn union_member_names_unique() {
+    let union: Union = from_str(r#"<union memberTypes="xs:int other:int Int2 xs:int"/>"#).unwrap();
+
+    let context = XsdContext::new(
+      r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:other="http://other.com"></xs:schema>"#,
+    )
+    .unwrap();
+
+    let struct_name = Ident::new("Count", Span::call_site());
+
+    let names: Vec<String> = union
+      .get_members(&TokenStream::new(), &None, &context, &struct_name)
+      .unwrap()
+      .iter()
+      .map(|member| member.name.to_string())
+      .collect();
+
+    assert_eq!(names, vec!["Int", "Int2", "Int22", "Int3"]);
+  }
 }
@@ -1,14 +1,105 @@
-use std::collections::BTreeMap;
+use crate::xsd::{
+  attribute_group::AttributeGroup, group::Group, rust_types_mapping::RustTypesMapping,
+  simple_type::SimpleType, Error,
+};
+use proc_macro2::TokenStream;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Cursor;
+use std::rc::Rc;
+use std::str::FromStr;
 use xml::namespace::Namespace;
 use xml::reader::{EventReader, XmlEvent};
 
+const XML_SCHEMA_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// Behaviour of the generation on unsupported XSD constructs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Mode {
+  /// Abort the generation with an error.
+  #[default]
+  Strict,
+  /// Replace the unsupported components with a fallback and report them.
+  Lenient,
+}
+
+impl FromStr for Mode {
+  type Err = Error;
+
+  fn from_str(mode: &str) -> Result<Self, Self::Err> {
+    match mode {
+      "strict" => Ok(Mode::Strict),
+      "lenient" => Ok(Mode::Lenient),
+      _ => Err(Error::new(format!(
+        "unknown mode {mode:?}, expected \"strict\" or \"lenient\""
+      ))),
+    }
+  }
+}
+
+/// Value of a generated enumeration when it is not present in the document.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EnumDefault {
+  /// The enumeration defaults to its first value.
+  #[default]
+  First,
+  /// The required fields of an enumeration type are wrapped in an `Option`.
+  Optional,
+}
+
+impl FromStr for EnumDefault {
+  type Err = Error;
+
+  fn from_str(enum_default: &str) -> Result<Self, Self::Err> {
+    match enum_default {
+      "first" => Ok(EnumDefault::First),
+      "option" => Ok(EnumDefault::Optional),
+      _ => Err(Error::new(format!(
+        "unknown enum_default {enum_default:?}, expected \"first\" or \"option\""
+      ))),
+    }
+  }
+}
+
+/// Rust types of the unbounded numeric types of XML Schema.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Precision {
+  /// Native primitives, ie. `i32` for `xs:integer`, and `String` for `xs:decimal` to keep its digits.
+  #[default]
+  Native,
+  /// The `Integer` and `Decimal` types of the `numeric` module, without overflow nor rounding.
+  Arbitrary,
+}
+
+impl FromStr for Precision {
+  type Err = Error;
+
+  fn from_str(precision: &str) -> Result<Self, Self::Err> {
+    match precision {
+      "native" => Ok(Precision::Native),
+      "arbitrary" => Ok(Precision::Arbitrary),
+      _ => Err(Error::new(format!(
+        "unknown precision {precision:?}, expected \"native\" or \"arbitrary\""
+      ))),
+    }
+  }
+}
+
 #[derive(Clone, Debug)]
 pub struct XsdContext {
   module_namespace_mappings: BTreeMap<String, String>,
   pub namespace: Namespace,
   xml_schema_prefix: Option<String>,
   is_in_sub_module: bool,
+  mode: Mode,
+  enum_default: EnumDefault,
+  enumerations: BTreeSet<String>,
+  simple_types: BTreeMap<String, SimpleType>,
+  attribute_groups: BTreeMap<String, AttributeGroup>,
+  groups: BTreeMap<String, Group>,
+  open_enums: bool,
+  precision: Precision,
+  skipped: Rc<RefCell<Vec<Error>>>,
 }
 
 impl XsdContext {
@@ -20,20 +111,27 @@ impl XsdContext {
       match xml_element {
         Ok(XmlEvent::StartElement {
           name, namespace, ..
-        }) => {
-          if name.namespace == Some("http://www.w3.org/2001/XMLSchema".to_string())
-            && name.local_name == "schema"
-          {
-            let module_namespace_mappings = BTreeMap::new();
-            let xml_schema_prefix = name.prefix;
-
-            return Ok(XsdContext {
-              module_namespace_mappings,
-              namespace,
-              xml_schema_prefix,
-              is_in_sub_module: false,
-            });
-          }
+        }) if name.namespace.as_deref() == Some(XML_SCHEMA_NAMESPACE)
+          && name.local_name == "schema" =>
+        {
+          let module_namespace_mappings = BTreeMap::new();
+          let xml_schema_prefix = name.prefix;
+
+          return Ok(XsdContext {
+            module_namespace_mappings,
+            namespace,
+            xml_schema_prefix,
+            is_in_sub_module: false,
+            mode: Mode::default(),
+            enum_default: EnumDefault::default(),
+            enumerations: BTreeSet::new(),
+            simple_types: BTreeMap::new(),
+            attribute_groups: BTreeMap::new(),
+            groups: BTreeMap::new(),
+            open_enums: false,
+            precision: Precision::default(),
+            skipped: Rc::default(),
+          });
         }
         Err(_) => break,
         _ => {}
@@ -51,12 +149,64 @@ impl XsdContext {
     self
   }
 
+  pub fn add_module_namespace_mapping(&mut self, namespace: &str, module: &str) {
+    self
+      .module_namespace_mappings
+      .insert(namespace.to_string(), module.to_string());
+  }
+
+  /// Add the module mappings of a schema merged in this one, without overriding the existing ones.
+  pub fn merge_module_namespace_mappings(&mut self, other: &XsdContext) {
+    for (namespace, module) in &other.module_namespace_mappings {
+      self
+        .module_namespace_mappings
+        .entry(namespace.clone())
+        .or_insert_with(|| module.clone());
+    }
+  }
+
+  pub fn has_module_namespace_mapping(&self, namespace: &str) -> bool {
+    self.module_namespace_mappings.contains_key(namespace)
+  }
+
+  pub fn get_prefix(&self, namespace: &str) -> Option<String> {
+    self
+      .namespace
+      .0
+      .iter()
+      .find(|(prefix, uri)| {
+        !prefix.is_empty() && *prefix != "xml" && *prefix != "xmlns" && uri.as_str() == namespace
+      })
+      .map(|(prefix, _uri)| prefix.to_owned())
+  }
+
+  /// Add the namespace bindings of an included schema, without overriding the existing prefixes.
+  pub fn merge_namespaces(&mut self, other: &XsdContext) {
+    for (prefix, uri) in other.namespace.0.iter() {
+      match self.namespace.get(prefix) {
+        Some(existing_uri) if existing_uri != uri => {
+          log::warn!(
+            "Prefix {:?} is bound to {:?}, ignore the binding to {:?}",
+            prefix,
+            existing_uri,
+            uri
+          );
+        }
+        Some(_) => {}
+        None => {
+          self.namespace.put(prefix.as_str(), uri.as_str());
+        }
+      }
+    }
+  }
+
   pub fn has_xml_schema_prefix(&self) -> bool {
     self.xml_schema_prefix.is_some()
   }
 
   pub fn match_xml_schema_prefix(&self, value: &str) -> bool {
     self.xml_schema_prefix == Some(value.to_string())
+      || (!value.is_empty() && self.namespace.get(value) == Some(XML_SCHEMA_NAMESPACE))
   }
 
   pub fn get_module(&self, prefix: &str) -> Option<String> {
@@ -79,6 +229,170 @@ impl XsdContext {
   pub fn is_in_sub_module(&self) -> bool {
     self.is_in_sub_module
   }
+
+  pub fn set_mode(&mut self, mode: Mode) {
+    self.mode = mode;
+  }
+
+  pub fn is_lenient(&self) -> bool {
+    self.mode == Mode::Lenient
+  }
+
+  pub fn set_enum_default(&mut self, enum_default: EnumDefault) {
+    self.enum_default = enum_default;
+  }
+
+  pub fn set_open_enums(&mut self, open_enums: bool) {
+    self.open_enums = open_enums;
+  }
+
+  /// The enumerations accept the unknown values of their base type in an `Other` variant.
+  pub fn has_open_enums(&self) -> bool {
+    self.open_enums
+  }
+
+  pub fn set_precision(&mut self, precision: Precision) {
+    self.precision = precision;
+  }
+
+  pub fn precision(&self) -> Precision {
+    self.precision
+  }
+
+  /// Register the simple types of the schema generated as enumerations.
+  pub fn add_enumerations<'a, I: IntoIterator<Item = &'a str>>(&mut self, names: I) {
+    self
+      .enumerations
+      .extend(names.into_iter().map(|name| name.to_string()));
+  }
+
+  /// A required field of this type is wrapped in an `Option`, as the enumeration has no default.
+  pub fn is_optional_enumeration(&self, kind: &str) -> bool {
+    if self.enum_default != EnumDefault::Optional {
+      return false;
+    }
+
+    let (prefix, name) = kind.split_once(':').unwrap_or(("", kind));
+    self.get_module(prefix).is_none() && self.enumerations.contains(name)
+  }
+
+  /// Register the simple types of the schema, to check the values of their type.
+  pub fn add_simple_types<'a, I: IntoIterator<Item = &'a SimpleType>>(&mut self, simple_types: I) {
+    for simple_type in simple_types {
+      self
+        .simple_types
+        .insert(simple_type.name.clone(), simple_type.clone());
+    }
+  }
+
+  /// The type is a built-in type or a simple type of the schema, its values are text.
+  pub fn is_simple_type(&self, kind: &str) -> bool {
+    if RustTypesMapping::is_xs_type(self, kind) {
+      return true;
+    }
+
+    let (prefix, name) = kind.split_once(':').unwrap_or(("", kind));
+    self.get_module(prefix).is_none() && self.simple_types.contains_key(name)
+  }
+
+  pub fn get_simple_type(&self, kind: &str) -> Option<&SimpleType> {
+    let (prefix, name) = kind.split_once(':').unwrap_or(("", kind));
+    if self.get_module(prefix).is_some() {
+      return None;
+    }
+    self.simple_types.get(name)
+  }
+
+  /// Register the named attribute groups of the schema, to resolve their references.
+  pub fn add_attribute_groups<'a, I: IntoIterator<Item = &'a AttributeGroup>>(
+    &mut self,
+    attribute_groups: I,
+  ) {
+    for attribute_group in attribute_groups {
+      if let Some(name) = &attribute_group.name {
+        self
+          .attribute_groups
+          .insert(name.clone(), attribute_group.clone());
+      }
+    }
+  }
+
+  pub fn get_attribute_group(&self, reference: &str) -> Option<&AttributeGroup> {
+    let (prefix, name) = reference.split_once(':').unwrap_or(("", reference));
+    if self.get_module(prefix).is_some() {
+      return None;
+    }
+    self.attribute_groups.get(name)
+  }
+
+  /// Register the named groups of the schema, to resolve their references.
+  pub fn add_groups<'a, I: IntoIterator<Item = &'a Group>>(&mut self, groups: I) {
+    for group in groups {
+      if let Some(name) = &group.name {
+        self.groups.insert(name.clone(), group.clone());
+      }
+    }
+  }
+
+  pub fn get_group(&self, reference: &str) -> Option<&Group> {
+    let (prefix, name) = reference.split_once(':').unwrap_or(("", reference));
+    if self.get_module(prefix).is_some() {
+      return None;
+    }
+    self.groups.get(name)
+  }
+
+  /// In lenient mode, record the error of an unsupported component and use the fallback.
+  pub fn fallback<F>(
+    &self,
+    kind: &str,
+    name: Option<&str>,
+    result: Result<TokenStream, Error>,
+    fallback: F,
+  ) -> Result<TokenStream, Error>
+  where
+    F: FnOnce() -> TokenStream,
+  {
+    match result {
+      Err(error) if self.is_lenient() => {
+        self
+          .skipped
+          .borrow_mut()
+          .push(error.with_component(kind, name));
+        Ok(fallback())
+      }
+      result => result,
+    }
+  }
+
+  /// Implement a component, the components skipped inside it are located in it.
+  pub fn implement_component<I, F>(
+    &self,
+    kind: &str,
+    name: Option<&str>,
+    implement: I,
+    fallback: F,
+  ) -> Result<TokenStream, Error>
+  where
+    I: FnOnce() -> Result<TokenStream, Error>,
+    F: FnOnce() -> TokenStream,
+  {
+    let skipped_count = self.skipped.borrow().len();
+    let result = implement();
+
+    for error in self.skipped.borrow_mut().iter_mut().skip(skipped_count) {
+      *error = error.clone().with_component(kind, name);
+    }
+
+    self
+      .fallback(kind, name, result, fallback)
+      .map_err(|error| error.with_component(kind, name))
+  }
+
+  /// The errors of the components replaced by a fallback.
+  pub fn skipped(&self) -> Vec<Error> {
+    self.skipped.borrow().clone()
+  }
 }
 
 #[test]
@@ -108,6 +422,92 @@ fn get_module() {
   assert_eq!(context.get_module("other"), None);
 }
 
+#[test]
+fn get_prefix() {
+  let context = XsdContext::new(
+    r#"
+    <xs:schema
+      xmlns:xs="http://www.w3.org/2001/XMLSchema"
+      xmlns:example="http://example.com"
+      >
+    </xs:schema>
+  "#,
+  )
+  .unwrap();
+
+  assert_eq!(
+    context.get_prefix("http://example.com"),
+    Some("example".to_string())
+  );
+  assert_eq!(context.get_prefix("http://other.com"), None);
+}
+
+#[test]
+fn merge_namespaces() {
+  let mut context = XsdContext::new(
+    r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:a="http://a.com"></xs:schema>"#,
+  )
+  .unwrap();
+
+  let included = XsdContext::new(
+    r#"<xsd:schema xmlns:xsd="http://www.w3.org/2001/XMLSchema" xmlns:a="http://other.com" xmlns:b="http://b.com"></xsd:schema>"#,
+  )
+  .unwrap();
+
+  context.merge_namespaces(&included);
+
+  assert_eq!(context.namespace.get("a"), Some("http://a.com"));
+  assert_eq!(context.namespace.get("b"), Some("http://b.com"));
+  assert!(context.match_xml_schema_prefix("xs"));
+  assert!(context.match_xml_schema_prefix("xsd"));
+}
+
+#[test]
+fn lenient_fallback() {
+  let mut context =
+    XsdContext::new(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"></xs:schema>"#)
+      .unwrap();
+
+  let implementation = context.implement_component(
+    "complexType",
+    Some("Parent"),
+    || Err(Error::new("unsupported")),
+    || quote!(String),
+  );
+  assert_eq!(
+    implementation.unwrap_err().to_string(),
+    r#"complexType "Parent": unsupported"#
+  );
+  assert!(context.skipped().is_empty());
+
+  context.set_mode(Mode::Lenient);
+
+  let implementation = context.implement_component(
+    "complexType",
+    Some("Parent"),
+    || {
+      context.fallback(
+        "element",
+        Some("child"),
+        Err(Error::new("unsupported")),
+        || quote!(String),
+      )
+    },
+    || quote!(),
+  );
+  assert_eq!(implementation.unwrap().to_string(), "String");
+
+  let skipped: Vec<String> = context
+    .skipped()
+    .iter()
+    .map(|error| error.to_string())
+    .collect();
+  assert_eq!(
+    skipped,
+    vec![r#"complexType "Parent" / element "child": unsupported"#]
+  );
+}
+
 #[test]
 fn bad_schema_definition() {
   let context = XsdContext::new(
@@ -121,3 +521,52 @@ fn bad_schema_definition() {
 
   assert!(context.is_err());
 }
+
+#[test]
+fn optional_enumeration() {
+  let mut context = XsdContext::new(
+    r#"
+    <xs:schema
+      xmlns:xs="http://www.w3.org/2001/XMLSchema"
+      xmlns:example="http://example.com"
+      >
+    </xs:schema>
+  "#,
+  )
+  .unwrap();
+  context.add_enumerations(["Size"]);
+  assert!(!context.is_optional_enumeration("Size"));
+
+  context.set_enum_default("option".parse().unwrap());
+  assert!(context.is_optional_enumeration("Size"));
+  assert!(!context.is_optional_enumeration("Color"));
+
+  context.add_module_namespace_mapping("http://example.com", "crate::example");
+  assert!(!context.is_optional_enumeration("example:Size"));
+
+  assert!("other".parse::<EnumDefault>().is_err());
+}
+
+#[test]
+fn simple_types() {
+  let mut context = XsdContext::new(
+    r#"
+    <xs:schema
+      xmlns:xs="http://www.w3.org/2001/XMLSchema"
+      xmlns:example="http://example.com"
+      >
+    </xs:schema>
+  "#,
+  )
+  .unwrap();
+  context.add_simple_types(&[SimpleType {
+    name: "Size".to_string(),
+    ..Default::default()
+  }]);
+  context.add_module_namespace_mapping("http://example.com", "crate::example");
+
+  assert!(context.is_simple_type("xs:int"));
+  assert!(context.is_simple_type("Size"));
+  assert!(!context.is_simple_type("Person"));
+  assert!(!context.is_simple_type("example:Size"));
+}